@@ -50,7 +50,7 @@ mod mac {
                 BenchmarkId::new("compute mac", *size),
                 &input,
                 |b, input_message| {
-                    b.iter(|| poly1305::Poly1305::poly1305(&key, &input_message).unwrap())
+                    b.iter(|| poly1305::Poly1305::poly1305(&key, input_message).unwrap())
                 },
             );
         }
@@ -70,7 +70,7 @@ mod mac {
                 BenchmarkId::new("compute mac", *size),
                 &input,
                 |b, input_message| {
-                    b.iter(|| hmac::sha256::HmacSha256::hmac(&key, &input_message).unwrap())
+                    b.iter(|| hmac::sha256::HmacSha256::hmac(&key, input_message).unwrap())
                 },
             );
         }
@@ -90,12 +90,135 @@ mod mac {
                 BenchmarkId::new("compute mac", *size),
                 &input,
                 |b, input_message| {
-                    b.iter(|| hmac::sha512::HmacSha512::hmac(&key, &input_message).unwrap())
+                    b.iter(|| hmac::sha512::HmacSha512::hmac(&key, input_message).unwrap())
                 },
             );
         }
     }
 
+    pub fn bench_hmac_small_message(c: &mut Criterion) {
+        let mut group = c.benchmark_group("HMAC small message");
+        let input = [0u8; 32];
+        let key256 = hmac::sha256::SecretKey::generate();
+        let key512 = hmac::sha512::SecretKey::generate();
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(BenchmarkId::new("HMAC-SHA256 one-shot", input.len()), |b| {
+            b.iter(|| hmac::sha256::HmacSha256::hmac(&key256, &input).unwrap())
+        });
+        group.bench_function(
+            BenchmarkId::new("HMAC-SHA256 streaming", input.len()),
+            |b| {
+                b.iter(|| {
+                    let mut ctx = hmac::sha256::HmacSha256::new(&key256);
+                    ctx.update(input).unwrap();
+                    ctx.finalize().unwrap()
+                })
+            },
+        );
+        group.bench_function(BenchmarkId::new("HMAC-SHA512 one-shot", input.len()), |b| {
+            b.iter(|| hmac::sha512::HmacSha512::hmac(&key512, &input).unwrap())
+        });
+        group.bench_function(
+            BenchmarkId::new("HMAC-SHA512 streaming", input.len()),
+            |b| {
+                b.iter(|| {
+                    let mut ctx = hmac::sha512::HmacSha512::new(&key512);
+                    ctx.update(input).unwrap();
+                    ctx.finalize().unwrap()
+                })
+            },
+        );
+    }
+
+    pub fn bench_tag_eq(c: &mut Criterion) {
+        // Tag equality is constant time, so all three cases should take about the same
+        // time. An early-exit comparison would be fastest for "first byte differs".
+        // See tests/ct for a statistical test of this.
+        let mut group = c.benchmark_group("Tag equality");
+        let tag = hmac::sha512::Tag::from([0u8; 64]);
+        let mut first = [0u8; 64];
+        first[0] = 1;
+        let mut last = [0u8; 64];
+        last[63] = 1;
+
+        for (name, other) in [
+            ("all equal", hmac::sha512::Tag::from([0u8; 64])),
+            ("first byte differs", hmac::sha512::Tag::from(first)),
+            ("last byte differs", hmac::sha512::Tag::from(last)),
+        ] {
+            group.bench_function(name, |b| b.iter(|| black_box(&tag) == black_box(&other)));
+        }
+    }
+
+    pub fn bench_mac_chunked(c: &mut Criterion) {
+        // Realistic update() sizes: small messages, network packets and TLS records.
+        static CHUNK_SIZES: [usize; 3] = [64, 1500, 16 * 1024];
+        static TOTAL_SIZES: [usize; 2] = [16 * 1024, 256 * 1024];
+
+        let poly_key = poly1305::OneTimeKey::generate();
+        let hmac_key = hmac::sha512::SecretKey::generate();
+
+        for total in TOTAL_SIZES.iter() {
+            let mut group = c.benchmark_group(format!("MAC chunked {} bytes", total));
+            let input = vec![0u8; *total];
+            group.throughput(Throughput::Bytes(*total as u64));
+
+            for chunk_size in CHUNK_SIZES.iter() {
+                group.bench_with_input(
+                    BenchmarkId::new("Poly1305", *chunk_size),
+                    chunk_size,
+                    |b, chunk_size| {
+                        b.iter(|| {
+                            let mut ctx = poly1305::Poly1305::new(&poly_key);
+                            for chunk in input.chunks(*chunk_size) {
+                                ctx.update(chunk).unwrap();
+                            }
+                            ctx.finalize().unwrap()
+                        })
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("HMAC-SHA512", *chunk_size),
+                    chunk_size,
+                    |b, chunk_size| {
+                        b.iter(|| {
+                            let mut ctx = hmac::sha512::HmacSha512::new(&hmac_key);
+                            for chunk in input.chunks(*chunk_size) {
+                                ctx.update(chunk).unwrap();
+                            }
+                            ctx.finalize().unwrap()
+                        })
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn bench_hmac_update_calls(c: &mut Criterion) {
+        // The same 1 MiB, passed to update() in 1, 1024 and 1M calls, to show the
+        // overhead of each call.
+        static CALL_COUNTS: [usize; 3] = [1, 1024, 1024 * 1024];
+        const TOTAL: usize = 1024 * 1024;
+
+        let mut group = c.benchmark_group("HMAC-SHA512 update calls");
+        let key = hmac::sha512::SecretKey::generate();
+        let input = vec![0u8; TOTAL];
+        group.throughput(Throughput::Bytes(TOTAL as u64));
+
+        for calls in CALL_COUNTS.iter() {
+            group.bench_with_input(BenchmarkId::new("1 MiB", *calls), calls, |b, calls| {
+                b.iter(|| {
+                    let mut ctx = hmac::sha512::HmacSha512::new(&key);
+                    for chunk in input.chunks(TOTAL / *calls) {
+                        ctx.update(chunk).unwrap();
+                    }
+                    ctx.finalize().unwrap()
+                })
+            });
+        }
+    }
+
     criterion_group! {
         name = mac_benches;
         config = Criterion::default();
@@ -103,6 +226,10 @@ mod mac {
         bench_poly1305,
         bench_hmac_sha256,
         bench_hmac_sha512,
+        bench_hmac_small_message,
+        bench_tag_eq,
+        bench_mac_chunked,
+        bench_hmac_update_calls,
     }
 }
 
@@ -124,8 +251,7 @@ mod aead {
                 &input,
                 |b, input_message| {
                     b.iter(|| {
-                        chacha20poly1305::seal(&key, &nonce, &input_message, None, &mut out)
-                            .unwrap()
+                        chacha20poly1305::seal(&key, &nonce, input_message, None, &mut out).unwrap()
                     })
                 },
             );
@@ -147,7 +273,7 @@ mod aead {
                 &input,
                 |b, input_message| {
                     b.iter(|| {
-                        xchacha20poly1305::seal(&key, &nonce, &input_message, None, &mut out)
+                        xchacha20poly1305::seal(&key, &nonce, input_message, None, &mut out)
                             .unwrap()
                     })
                 },
@@ -177,7 +303,7 @@ mod hash {
             group.bench_with_input(
                 BenchmarkId::new("compute hash", *size),
                 &input,
-                |b, input_message| b.iter(|| sha2::sha256::Sha256::digest(&input_message).unwrap()),
+                |b, input_message| b.iter(|| sha2::sha256::Sha256::digest(input_message).unwrap()),
             );
         }
     }
@@ -192,7 +318,7 @@ mod hash {
             group.bench_with_input(
                 BenchmarkId::new("compute hash", *size),
                 &input,
-                |b, input_message| b.iter(|| sha2::sha384::Sha384::digest(&input_message).unwrap()),
+                |b, input_message| b.iter(|| sha2::sha384::Sha384::digest(input_message).unwrap()),
             );
         }
     }
@@ -207,7 +333,7 @@ mod hash {
             group.bench_with_input(
                 BenchmarkId::new("compute hash", *size),
                 &input,
-                |b, input_message| b.iter(|| sha2::sha512::Sha512::digest(&input_message).unwrap()),
+                |b, input_message| b.iter(|| sha2::sha512::Sha512::digest(input_message).unwrap()),
             );
         }
     }
@@ -225,7 +351,7 @@ mod hash {
                 |b, input_message| {
                     b.iter(|| {
                         blake2::blake2b::Hasher::Blake2b512
-                            .digest(&input_message)
+                            .digest(input_message)
                             .unwrap()
                     })
                 },
@@ -261,7 +387,7 @@ mod stream {
                 BenchmarkId::new("xor-stream", *size),
                 &input,
                 |b, input_message| {
-                    b.iter(|| chacha20::encrypt(&key, &nonce, 0, &input_message, &mut out).unwrap())
+                    b.iter(|| chacha20::encrypt(&key, &nonce, 0, input_message, &mut out).unwrap())
                 },
             );
         }
@@ -281,20 +407,48 @@ mod stream {
                 BenchmarkId::new("xor-stream", *size),
                 &input,
                 |b, input_message| {
-                    b.iter(|| {
-                        xchacha20::encrypt(&key, &nonce, 0, &input_message, &mut out).unwrap()
-                    })
+                    b.iter(|| xchacha20::encrypt(&key, &nonce, 0, input_message, &mut out).unwrap())
                 },
             );
         }
     }
 
+    pub fn bench_chacha20_keystream_paths(c: &mut Criterion) {
+        // The same 64 KiB, encrypted in one call (four blocks at a time) and in one call
+        // per 64-byte block (single-block path). Building with `--features force-reference`
+        // runs the one-call case on the single-block path as well.
+        const TOTAL: usize = 64 * 1024;
+
+        let mut group = c.benchmark_group("ChaCha20 keystream paths");
+        let key = chacha20poly1305::SecretKey::generate();
+        let nonce = chacha20poly1305::Nonce::from([0u8; 12]);
+        let input = vec![0u8; TOTAL];
+        let mut out = vec![0u8; TOTAL];
+        group.throughput(Throughput::Bytes(TOTAL as u64));
+
+        group.bench_function(BenchmarkId::new("wide", TOTAL), |b| {
+            b.iter(|| chacha20::encrypt(&key, &nonce, 0, &input, &mut out).unwrap())
+        });
+        group.bench_function(BenchmarkId::new("single-block", TOTAL), |b| {
+            b.iter(|| {
+                for (counter, (block_in, block_out)) in (0u32..).zip(
+                    input
+                        .chunks(chacha20::CHACHA_BLOCKSIZE)
+                        .zip(out.chunks_mut(chacha20::CHACHA_BLOCKSIZE)),
+                ) {
+                    chacha20::encrypt(&key, &nonce, counter, block_in, block_out).unwrap();
+                }
+            })
+        });
+    }
+
     criterion_group! {
         name = stream_benches;
         config = Criterion::default();
         targets =
         bench_chacha20,
         bench_xchacha20,
+        bench_chacha20_keystream_paths,
     }
 }
 
@@ -412,6 +566,66 @@ mod kdf {
         }
     }
 
+    /// PBKDF2-HMAC-SHA512 for a single 64-byte block, re-initializing HMAC with the
+    /// password for every iteration. Only used as a baseline for `derive_key()`,
+    /// which prepares the HMAC state once and reuses it.
+    fn pbkdf2_sha512_naive(password: &[u8], salt: &[u8], iterations: usize, dest: &mut [u8; 64]) {
+        let key = hmac::sha512::SecretKey::from_slice_padded(password).unwrap();
+
+        let mut ctx = hmac::sha512::HmacSha512::new(&key);
+        ctx.update(salt).unwrap();
+        ctx.update(1u32.to_be_bytes()).unwrap();
+        let mut u_step = ctx.finalize().unwrap();
+        dest.copy_from_slice(u_step.unprotected_as_bytes());
+
+        for _ in 1..iterations {
+            let mut ctx = hmac::sha512::HmacSha512::new(&key);
+            ctx.update(u_step.unprotected_as_bytes()).unwrap();
+            u_step = ctx.finalize().unwrap();
+            for (out, u) in dest.iter_mut().zip(u_step.unprotected_as_bytes()) {
+                *out ^= u;
+            }
+        }
+    }
+
+    pub fn bench_pbkdf2_sha512_state_reuse(c: &mut Criterion) {
+        let mut group = c.benchmark_group("PBKDF2-HMAC-SHA512 state reuse");
+        // 10 is the lowest acceptable sample size.
+        group.sample_size(10);
+        group.measurement_time(core::time::Duration::new(30, 0));
+
+        let iterations = 100_000;
+        let password = [0u8; 64];
+        let salt = [0u8; 64];
+        let mut dk_naive = [0u8; 64];
+        let mut dk_out = [0u8; 64];
+
+        pbkdf2_sha512_naive(&password, &salt, iterations, &mut dk_naive);
+        pbkdf2::sha512::derive_key(
+            &pbkdf2::sha512::Password::from_slice(&password).unwrap(),
+            &salt,
+            iterations,
+            &mut dk_out,
+        )
+        .unwrap();
+        assert_eq!(dk_naive, dk_out);
+
+        group.bench_function(BenchmarkId::new("naive", iterations), |b| {
+            b.iter(|| pbkdf2_sha512_naive(&password, &salt, iterations, &mut dk_naive))
+        });
+        group.bench_function(BenchmarkId::new("reused state", iterations), |b| {
+            b.iter(|| {
+                pbkdf2::sha512::derive_key(
+                    &pbkdf2::sha512::Password::from_slice(&password).unwrap(),
+                    &salt,
+                    iterations,
+                    &mut dk_out,
+                )
+                .unwrap()
+            })
+        });
+    }
+
     pub fn bench_argon2i(c: &mut Criterion) {
         let mut group = c.benchmark_group("Argon2i");
 
@@ -447,6 +661,7 @@ mod kdf {
         bench_hkdf_sha512,
         bench_pbkdf2_sha256,
         bench_pbkdf2_sha512,
+        bench_pbkdf2_sha512_state_reuse,
     }
 }
 
@@ -463,10 +678,9 @@ mod ecc {
 
         group.sample_size(100);
         group.bench_function("key_agreement", move |b| {
-            b.iter_with_setup(
-                || x25519::PrivateKey::generate(),
-                |bob_sk| x25519::key_agreement(&bob_sk, &alice_pk).unwrap(),
-            )
+            b.iter_with_setup(x25519::PrivateKey::generate, |bob_sk| {
+                x25519::key_agreement(&bob_sk, &alice_pk).unwrap()
+            })
         });
     }
 
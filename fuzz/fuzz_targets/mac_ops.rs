@@ -0,0 +1,45 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use orion::test_framework::mac_ops::{run_ops, Mac, Op};
+
+/// Decode the fuzzer input as: MAC selector, key length, key, and then a sequence
+/// of operations, where `Update` is followed by a length byte and the data.
+fn decode(data: &[u8]) -> Option<(Mac, &[u8], Vec<Op>)> {
+    let (&selector, data) = data.split_first()?;
+    let mac = match selector % 4 {
+        0 => Mac::HmacSha256,
+        1 => Mac::HmacSha384,
+        2 => Mac::HmacSha512,
+        _ => Mac::Poly1305,
+    };
+
+    let (&key_len, data) = data.split_first()?;
+    let (key, mut data) = data.split_at_checked(key_len as usize)?;
+
+    let mut ops = Vec::new();
+    while let Some((&op, rest)) = data.split_first() {
+        data = rest;
+        match op % 3 {
+            0 => {
+                let len = data.first().map_or(0, |len| *len as usize);
+                let rest = data.get(1..).unwrap_or_default();
+                let (update, rest) = rest.split_at(len.min(rest.len()));
+                ops.push(Op::Update(update.to_vec()));
+                data = rest;
+            }
+            1 => ops.push(Op::Finalize),
+            _ => ops.push(Op::Reset),
+        }
+    }
+
+    Some((mac, key, ops))
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((mac, key, ops)) = decode(data) {
+        // Invalid keys are expected to be rejected, everything else is checked
+        // inside run_ops().
+        let _ = run_ops(mac, key, &ops);
+    }
+});
@@ -22,8 +22,33 @@
 
 use core::fmt;
 
+/// Record a warning through the [`log`](https://docs.rs/log) crate that a streaming
+/// state was misused, when the `log` feature is enabled. Without it, this expands to
+/// nothing. Only pass static descriptions of the misuse, never data from the state.
+macro_rules! log_misuse (($msg:literal) => (
+    #[cfg(feature = "log")]
+    {
+        log::warn!(target: "orion", $msg);
+    }
+));
+
 #[allow(clippy::derive_partial_eq_without_eq)]
 /// Opaque error.
+///
+/// This is a single unit struct rather than an enum, so that failures do not reveal
+/// why an operation failed. There are no variants to match on; code handling it
+/// should match on `Err(_)` or `Err(UnknownCryptoError)`.
+///
+/// # Example:
+/// ```rust
+/// use orion::errors::UnknownCryptoError;
+/// use orion::hazardous::mac::poly1305::OneTimeKey;
+///
+/// match OneTimeKey::from_slice(&[0u8; 16]) {
+///     Ok(_) => panic!("a 16-byte key should be rejected"),
+///     Err(UnknownCryptoError) => (),
+/// }
+/// ```
 #[derive(Clone, Copy, PartialEq)]
 pub struct UnknownCryptoError;
 
@@ -45,6 +70,39 @@ impl core::error::Error for UnknownCryptoError {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Error telling why a tag failed to verify, for the few functions that opt into it,
+/// such as [`HmacSha512::verify_slices_detailed()`].
+///
+/// Everything else returns the opaque [`UnknownCryptoError`]. This is only used where
+/// the cause is not secret. It converts into [`UnknownCryptoError`], so `?` can be used
+/// in functions returning that.
+///
+/// [`HmacSha512::verify_slices_detailed()`]: crate::hazardous::mac::hmac::sha512::HmacSha512::verify_slices_detailed
+pub enum CryptoError {
+    /// The input is malformed, for example the expected tag has the wrong length.
+    InvalidInput,
+    /// The input is well-formed, but the tag does not match.
+    Validation,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::InvalidInput => write!(f, "CryptoError: invalid input"),
+            CryptoError::Validation => write!(f, "CryptoError: validation failed"),
+        }
+    }
+}
+
+impl core::error::Error for CryptoError {}
+
+impl From<CryptoError> for UnknownCryptoError {
+    fn from(_: CryptoError) -> Self {
+        UnknownCryptoError
+    }
+}
+
 #[cfg(feature = "safe_api")]
 impl From<getrandom::Error> for UnknownCryptoError {
     fn from(_: getrandom::Error) -> Self {
@@ -52,7 +110,7 @@ impl From<getrandom::Error> for UnknownCryptoError {
     }
 }
 
-#[cfg(feature = "safe_api")]
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
 impl From<ct_codecs::Error> for UnknownCryptoError {
     fn from(_: ct_codecs::Error) -> Self {
         UnknownCryptoError
@@ -93,6 +151,25 @@ fn test_unknown_crypto_from_getrandom() {
     assert_eq!(err, "UnknownCryptoError");
 }
 
+#[test]
+#[cfg(feature = "safe_api")]
+// format! is only available with std
+fn test_crypto_error() {
+    assert_eq!(
+        format!("{}", CryptoError::InvalidInput),
+        "CryptoError: invalid input"
+    );
+    assert_eq!(
+        format!("{}", CryptoError::Validation),
+        "CryptoError: validation failed"
+    );
+    assert_eq!(format!("{:?}", CryptoError::Validation), "Validation");
+    assert_eq!(
+        UnknownCryptoError::from(CryptoError::InvalidInput),
+        UnknownCryptoError
+    );
+}
+
 #[test]
 fn test_source() {
     use core::error::Error;
@@ -135,3 +212,126 @@ fn test_unknown_crypto_from_parseint_error() {
     );
     assert_eq!(err, "UnknownCryptoError:UnknownCryptoError");
 }
+
+#[cfg(all(test, feature = "log", feature = "safe_api"))]
+mod test_log_misuse {
+    use crate::hazardous::mac::{hmac, poly1305};
+    use std::sync::{Mutex, Once};
+
+    static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct CapturingLogger;
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+            metadata.target() == "orion"
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            if self.enabled(record.metadata()) {
+                RECORDS
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}: {}", record.level(), record.args()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn install_logger() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&CapturingLogger).unwrap();
+            log::set_max_level(log::LevelFilter::Warn);
+        });
+    }
+
+    fn count_records(expected: &str) -> usize {
+        RECORDS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.as_str() == expected)
+            .count()
+    }
+
+    #[test]
+    fn test_warns_on_misuse() {
+        install_logger();
+
+        let key = hmac::sha256::SecretKey::from_slice_padded(&[0u8; 32]).unwrap();
+        let mut hmac_state = hmac::sha256::HmacSha256::new(&key);
+        let okm = poly1305::OneTimeKey::from_slice(&[0u8; 32]).unwrap();
+        let mut poly1305_state = poly1305::Poly1305::new(&okm);
+
+        let update_hmac = "WARN: HMAC: update() called on a finalized state without reset()";
+        let finalize_hmac = "WARN: HMAC: finalize() called on a finalized state without reset()";
+        let update_poly = "WARN: Poly1305: update() called on a finalized state without reset()";
+        let finalize_poly =
+            "WARN: Poly1305: finalize() called on a finalized state without reset()";
+        // Other tests running in parallel may misuse states on purpose as well,
+        // so only an increase is checked for.
+        let before = [
+            count_records(update_hmac),
+            count_records(finalize_hmac),
+            count_records(update_poly),
+            count_records(finalize_poly),
+        ];
+
+        hmac_state.update(b"data").unwrap();
+        let _ = hmac_state.finalize().unwrap();
+        assert!(hmac_state.update(b"data").is_err());
+        assert!(hmac_state.finalize().is_err());
+
+        poly1305_state.update(b"data").unwrap();
+        let _ = poly1305_state.finalize().unwrap();
+        assert!(poly1305_state.update(b"data").is_err());
+        assert!(poly1305_state.finalize().is_err());
+
+        assert!(count_records(update_hmac) > before[0]);
+        assert!(count_records(finalize_hmac) > before[1]);
+        assert!(count_records(update_poly) > before[2]);
+        assert!(count_records(finalize_poly) > before[3]);
+    }
+
+    #[test]
+    fn test_warns_on_weak_hmac_key() {
+        install_logger();
+
+        let weak_key = "WARN: HMAC: new() called with a secret key shorter than 64 bytes";
+        let before = count_records(weak_key);
+        let _ = hmac::sha512::HmacSha512::new(
+            &hmac::sha512::SecretKey::from_slice_padded(&[0u8; 32]).unwrap(),
+        );
+        assert!(count_records(weak_key) > before);
+    }
+
+    #[test]
+    fn test_warns_on_wrong_tag_length() {
+        install_logger();
+
+        let wrong_length =
+            "WARN: HMAC: verify_slices() called with an expected tag of the wrong length";
+        let key = [0u8; 64];
+        let tag = hmac::sha512::HmacSha512::hmac(
+            &hmac::sha512::SecretKey::from_slice_padded(&key).unwrap(),
+            b"data",
+        )
+        .unwrap();
+        let tag = tag.unprotected_as_bytes();
+
+        let before = count_records(wrong_length);
+        // A truncated tag is logged as such.
+        assert!(hmac::sha512::HmacSha512::verify_slices(&tag[..32], &key, b"data").is_err());
+        assert!(hmac::sha256::HmacSha256::verify_slices(tag, &key, b"data").is_err());
+        assert!(hmac::sha384::HmacSha384::verify_slices(&[], &key, b"data").is_err());
+        assert!(count_records(wrong_length) >= before + 3);
+
+        // A mismatch is reported with the same error, without a warning.
+        let mut bad_tag = tag.to_vec();
+        bad_tag[0] ^= 1;
+        assert!(hmac::sha512::HmacSha512::verify_slices(&bad_tag, &key, b"data").is_err());
+        assert!(hmac::sha512::HmacSha512::verify_slices(tag, &key, b"data").is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! A builder for additional authenticated data (AAD) made up of several fields.
+//!
+//! Concatenating fields directly can make two different sets of fields produce the
+//! same AAD, for example `"ab" || "c"` and `"a" || "bc"`. [`Aad`] avoids this by
+//! encoding each field with a type identifier and, for byte fields, its length:
+//! - [`Aad::add_u64()`]: `0x00 || LE64(value)`
+//! - [`Aad::add_bytes()`]: `0x01 || LE64(bytes.len()) || bytes`
+//!
+//! The output of [`Aad::finish()`] can be used as the `ad` parameter of, for example,
+//! [`seal()`] and [`open()`].
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::aead::{aad::Aad, xchacha20poly1305::*};
+//!
+//! let secret_key = SecretKey::generate();
+//! let nonce = Nonce::generate();
+//! let ad = Aad::new().add_u64(1).add_bytes(b"header").finish();
+//!
+//! let plaintext = b"Secret message";
+//! let mut ciphertext_with_tag = [0u8; 14 + 16];
+//! seal(&secret_key, &nonce, plaintext, Some(&ad), &mut ciphertext_with_tag)?;
+//!
+//! let mut decrypted = [0u8; 14];
+//! open(&secret_key, &nonce, &ciphertext_with_tag, Some(&ad), &mut decrypted)?;
+//! assert_eq!(&decrypted, plaintext);
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [`seal()`]: crate::hazardous::aead::xchacha20poly1305::seal
+//! [`open()`]: crate::hazardous::aead::xchacha20poly1305::open
+//! [`Aad`]: crate::hazardous::aead::aad::Aad
+//! [`Aad::add_u64()`]: crate::hazardous::aead::aad::Aad::add_u64
+//! [`Aad::add_bytes()`]: crate::hazardous::aead::aad::Aad::add_bytes
+//! [`Aad::finish()`]: crate::hazardous::aead::aad::Aad::finish
+
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::vec::Vec;
+
+/// Type identifier of a `u64` field.
+const FIELD_U64: u8 = 0x00;
+/// Type identifier of a length-prefixed byte field.
+const FIELD_BYTES: u8 = 0x01;
+
+#[derive(Clone, Debug, Default)]
+/// Builder for canonically encoded additional authenticated data.
+pub struct Aad {
+    buffer: Vec<u8>,
+}
+
+impl Aad {
+    /// Initialize an empty `Aad`.
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Append a `u64` field.
+    pub fn add_u64(mut self, value: u64) -> Self {
+        self.buffer.push(FIELD_U64);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Append a length-prefixed byte field.
+    pub fn add_bytes(mut self, bytes: &[u8]) -> Self {
+        self.buffer.push(FIELD_BYTES);
+        self.buffer
+            .extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        self.buffer.extend_from_slice(bytes);
+        self
+    }
+
+    /// Return the encoded additional authenticated data.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        assert!(Aad::new().finish().is_empty());
+        assert!(Aad::default().finish().is_empty());
+    }
+
+    #[test]
+    fn test_encoding() {
+        let ad = Aad::new().add_u64(1).add_bytes(b"ab").finish();
+        assert_eq!(
+            ad,
+            [
+                0x00, 1, 0, 0, 0, 0, 0, 0, 0, // u64
+                0x01, 2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', // bytes
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reordering_changes_aad() {
+        let ad_0 = Aad::new().add_u64(7).add_bytes(b"header").finish();
+        let ad_1 = Aad::new().add_bytes(b"header").add_u64(7).finish();
+        assert_ne!(ad_0, ad_1);
+
+        let ad_0 = Aad::new().add_bytes(b"ab").add_bytes(b"c").finish();
+        let ad_1 = Aad::new().add_bytes(b"c").add_bytes(b"ab").finish();
+        assert_ne!(ad_0, ad_1);
+    }
+
+    #[test]
+    fn test_field_boundaries_are_unambiguous() {
+        let ad_0 = Aad::new().add_bytes(b"ab").add_bytes(b"c").finish();
+        let ad_1 = Aad::new().add_bytes(b"a").add_bytes(b"bc").finish();
+        assert_ne!(ad_0, ad_1);
+
+        let ad_0 = Aad::new().add_u64(0).finish();
+        let ad_1 = Aad::new().add_bytes(&[]).finish();
+        assert_ne!(ad_0, ad_1);
+    }
+}
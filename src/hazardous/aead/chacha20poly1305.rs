@@ -27,8 +27,12 @@
 //! - `ciphertext_with_tag`: The encrypted data with the corresponding 16 byte
 //!   Poly1305 tag appended to it.
 //! - `plaintext`: The data to be encrypted.
+//! - `ciphertext`: The encrypted data, without a tag, when calling [`open_detached()`].
+//! - `expected`: The expected Poly1305 tag, when calling [`open_detached()`].
 //! - `dst_out`: Destination array that will hold the
 //!   `ciphertext_with_tag`/`plaintext` after encryption/decryption.
+//! - `in_out`: The plaintext, which is replaced by the ciphertext when calling
+//!   [`seal_in_place_detached()`].
 //!
 //! `ad`: "A typical use for these data is to authenticate version numbers,
 //! timestamps or monotonically increasing counters in order to discard previous
@@ -49,12 +53,15 @@
 //! - The length of `dst_out` is less than `ciphertext_with_tag` - [`POLY1305_OUTSIZE`] when
 //!   calling [`open()`].
 //! - The length of `ciphertext_with_tag` is not at least [`POLY1305_OUTSIZE`].
-//! - The received tag does not match the calculated tag when  calling [`open()`].
+//! - The length of `dst_out` is less than `plaintext` when calling [`seal_detached()`].
+//! - The length of `dst_out` is less than `ciphertext` when calling [`open_detached()`].
+//! - The received tag does not match the calculated tag when  calling [`open()`] or [`open_detached()`].
 //! - `plaintext.len()` + [`POLY1305_OUTSIZE`] overflows when  calling [`seal()`].
 //! - Converting `usize` to `u64` would be a lossy conversion.
 //! - `plaintext.len() >` [`P_MAX`]
 //! - `ad.len() >` [`A_MAX`]
 //! - `ciphertext_with_tag.len() >` [`C_MAX`]
+//! - The streaming [`ChaCha20Poly1305`] state is used out of order, see its documentation.
 //!
 //! # Panics:
 //! A panic will occur if:
@@ -104,12 +111,17 @@
 //! [`POLY1305_OUTSIZE`]: super::mac::poly1305::POLY1305_OUTSIZE
 //! [`seal()`]: chacha20poly1305::seal
 //! [`open()`]: chacha20poly1305::open
+//! [`seal_detached()`]: chacha20poly1305::seal_detached
+//! [`open_detached()`]: chacha20poly1305::open_detached
+//! [`seal_in_place_detached()`]: chacha20poly1305::seal_in_place_detached
+//! [`ChaCha20Poly1305`]: chacha20poly1305::ChaCha20Poly1305
 //! [RFC]: https://tools.ietf.org/html/rfc8439#section-3
 //! [libsodium docs]: https://download.libsodium.org/doc/secret-key_cryptography/aead#additional-data
 //! [`P_MAX`]: chacha20poly1305::P_MAX
 //! [`A_MAX`]: chacha20poly1305::A_MAX
 //! [`C_MAX`]: chacha20poly1305::C_MAX
 
+pub use crate::hazardous::mac::poly1305::Tag;
 pub use crate::hazardous::stream::chacha20::{Nonce, SecretKey};
 use crate::{
     errors::UnknownCryptoError,
@@ -120,7 +132,7 @@ use crate::{
     util,
 };
 use core::convert::TryInto;
-use zeroize::Zeroizing;
+use zeroize::{Zeroize, Zeroizing};
 
 /// The initial counter used for encryption and decryption.
 pub(crate) const ENC_CTR: u32 = 1;
@@ -154,8 +166,16 @@ pub(crate) fn process_authentication(
 ) -> Result<(), UnknownCryptoError> {
     auth_ctx.process_pad_to_blocksize(ad)?;
     auth_ctx.process_pad_to_blocksize(ciphertext)?;
+    process_lengths(auth_ctx, ad.len(), ciphertext.len())
+}
 
-    let (ad_len, ct_len): (u64, u64) = match (ad.len().try_into(), ciphertext.len().try_into()) {
+/// Authenticates the lengths of the ad and ciphertext, as the last Poly1305 block.
+fn process_lengths(
+    auth_ctx: &mut Poly1305,
+    ad_len: usize,
+    ct_len: usize,
+) -> Result<(), UnknownCryptoError> {
+    let (ad_len, ct_len): (u64, u64) = match (ad_len.try_into(), ct_len.try_into()) {
         (Ok(alen), Ok(clen)) => (alen, clen),
         _ => return Err(UnknownCryptoError),
     };
@@ -166,6 +186,56 @@ pub(crate) fn process_authentication(
     auth_ctx.update(tmp_pad.as_ref())
 }
 
+/// Check that `text_len` is not greater than [`P_MAX`] and `ad` is not longer than
+/// [`A_MAX`], and return `ad` or an empty slice if it is [`None`].
+fn check_lengths(text_len: usize, ad: Option<&[u8]>) -> Result<&[u8], UnknownCryptoError> {
+    if u64::try_from(text_len).map_err(|_| UnknownCryptoError)? > P_MAX {
+        return Err(UnknownCryptoError);
+    }
+
+    let ad = ad.unwrap_or(&[0u8; 0]);
+    #[allow(clippy::absurd_extreme_comparisons)]
+    if u64::try_from(ad.len()).map_err(|_| UnknownCryptoError)? > A_MAX {
+        return Err(UnknownCryptoError);
+    }
+
+    Ok(ad)
+}
+
+/// Encrypt `plaintext` into `dst_out` and return the Poly1305 tag.
+///
+/// NOTE: The caller must ensure that `dst_out` is at least `plaintext.len()` and that
+/// `plaintext` and `ad` are not longer than [`P_MAX`] and [`A_MAX`] respectively.
+fn _seal(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    ad: &[u8],
+    dst_out: &mut [u8],
+) -> Result<Tag, UnknownCryptoError> {
+    debug_assert!(dst_out.len() >= plaintext.len());
+
+    let mut stream =
+        ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
+    let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
+
+    let mut auth_ctx = Poly1305::new(&poly1305_key_gen(&mut stream, &mut tmp));
+    let ad_len = ad.len();
+    let ct_len = plaintext.len();
+
+    auth_ctx.process_pad_to_blocksize(ad)?;
+
+    if ct_len != 0 {
+        chacha20::xor_keystream_into(&mut stream, ENC_CTR, tmp.as_mut(), plaintext, dst_out)?;
+        // `CHACHA_BLOCKSIZE` is evenly divisible by 16, so only the last block of
+        // the ciphertext is padded.
+        auth_ctx.process_pad_to_blocksize(&dst_out[..ct_len])?;
+    }
+
+    process_lengths(&mut auth_ctx, ad_len, ct_len)?;
+    auth_ctx.finalize()
+}
+
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
 /// AEAD ChaCha20Poly1305 encryption and authentication as specified in the [RFC 8439](https://tools.ietf.org/html/rfc8439).
 pub fn seal(
@@ -175,15 +245,7 @@ pub fn seal(
     ad: Option<&[u8]>,
     dst_out: &mut [u8],
 ) -> Result<(), UnknownCryptoError> {
-    if u64::try_from(plaintext.len()).map_err(|_| UnknownCryptoError)? > P_MAX {
-        return Err(UnknownCryptoError);
-    }
-
-    let ad = ad.unwrap_or(&[0u8; 0]);
-    #[allow(clippy::absurd_extreme_comparisons)]
-    if u64::try_from(ad.len()).map_err(|_| UnknownCryptoError)? > A_MAX {
-        return Err(UnknownCryptoError);
-    }
+    let ad = check_lengths(plaintext.len(), ad)?;
 
     match plaintext.len().checked_add(POLY1305_OUTSIZE) {
         Some(out_min_len) => {
@@ -194,67 +256,98 @@ pub fn seal(
         None => return Err(UnknownCryptoError),
     };
 
+    let tag = _seal(secret_key, nonce, plaintext, ad, dst_out)?;
+    dst_out[plaintext.len()..(plaintext.len() + POLY1305_OUTSIZE)]
+        .copy_from_slice(tag.unprotected_as_bytes());
+
+    Ok(())
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD ChaCha20Poly1305 encryption and authentication as specified in the [RFC 8439](https://tools.ietf.org/html/rfc8439),
+/// returning the Poly1305 tag separately from the ciphertext.
+pub fn seal_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    ad: Option<&[u8]>,
+    dst_out: &mut [u8],
+) -> Result<Tag, UnknownCryptoError> {
+    let ad = check_lengths(plaintext.len(), ad)?;
+
+    if dst_out.len() < plaintext.len() {
+        return Err(UnknownCryptoError);
+    }
+
+    _seal(secret_key, nonce, plaintext, ad, dst_out)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD ChaCha20Poly1305 encryption and authentication as specified in the [RFC 8439](https://tools.ietf.org/html/rfc8439),
+/// encrypting `in_out` in place and returning the Poly1305 tag separately.
+///
+/// Each chunk of ciphertext is authenticated right after it is encrypted, while it is still
+/// in cache, instead of in a second pass over the whole buffer. The ciphertext and tag
+/// are the same as with [`seal_detached()`].
+pub fn seal_in_place_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    in_out: &mut [u8],
+    ad: Option<&[u8]>,
+) -> Result<Tag, UnknownCryptoError> {
+    let ad = check_lengths(in_out.len(), ad)?;
+
     let mut stream =
         ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
     let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
 
     let mut auth_ctx = Poly1305::new(&poly1305_key_gen(&mut stream, &mut tmp));
-    let ad_len = ad.len();
-    let ct_len = plaintext.len();
-
     auth_ctx.process_pad_to_blocksize(ad)?;
 
-    if ct_len != 0 {
-        for (ctr, (p_block, c_block)) in plaintext
-            .chunks(CHACHA_BLOCKSIZE)
-            .zip(dst_out.chunks_mut(CHACHA_BLOCKSIZE))
-            .enumerate()
-        {
-            match ENC_CTR.checked_add(ctr as u32) {
-                Some(counter) => {
-                    // See https://github.com/orion-rs/orion/issues/308
-                    stream.next_produceable()?;
-                    // We know at this point that:
-                    // 1) ENC_CTR + ctr does __not__ overflow/panic
-                    // 2) ChaCha20 instance will not panic on the next keystream produced
-
-                    // Copy keystream directly into the dst_out block in there is
-                    // enough space, too avoid copying from `tmp` each time and only
-                    // on the last block instead. `c_block` must be full blocksize,
-                    // or we leave behind keystream data in it, if `p_block` is not full length
-                    // while `c_block` is.
-                    if p_block.len() == CHACHA_BLOCKSIZE && c_block.len() == CHACHA_BLOCKSIZE {
-                        stream.keystream_block(counter, c_block);
-                        xor_slices!(p_block, c_block);
-                        auth_ctx.update(c_block)?;
-                    }
-
-                    // We only pad this last block to the Poly1305 blocksize since `CHACHA_BLOCKSIZE`
-                    // already is evenly divisible by 16, so the previous full-length blocks do not matter.
-                    if p_block.len() < CHACHA_BLOCKSIZE {
-                        stream.keystream_block(counter, tmp.as_mut());
-                        xor_slices!(p_block, tmp.as_mut());
-                        c_block[..p_block.len()].copy_from_slice(&tmp.as_ref()[..p_block.len()]);
-                        auth_ctx.process_pad_to_blocksize(&c_block[..p_block.len()])?;
-                    }
-                }
-                None => return Err(UnknownCryptoError),
-            }
-        }
+    if !in_out.is_empty() {
+        // Every chunk but the last is a multiple of `CHACHA_BLOCKSIZE`, which is evenly
+        // divisible by 16, so only the last chunk is padded.
+        chacha20::xor_keystream_then(&mut stream, ENC_CTR, tmp.as_mut(), in_out, |chunk| {
+            auth_ctx.process_pad_to_blocksize(chunk)
+        })?;
     }
 
-    let (adlen, ctlen): (u64, u64) = match (ad_len.try_into(), ct_len.try_into()) {
-        (Ok(alen), Ok(clen)) => (alen, clen),
-        _ => return Err(UnknownCryptoError),
-    };
+    process_lengths(&mut auth_ctx, ad.len(), in_out.len())?;
+    auth_ctx.finalize()
+}
 
-    let mut tmp_pad = [0u8; 16];
-    tmp_pad[0..8].copy_from_slice(&adlen.to_le_bytes());
-    tmp_pad[8..16].copy_from_slice(&ctlen.to_le_bytes());
-    auth_ctx.update(tmp_pad.as_ref())?;
+/// Verify `expected_tag` in constant time and, only if it matches, decrypt `ciphertext` into `dst_out`.
+///
+/// NOTE: The caller must ensure that `dst_out` is at least `ciphertext.len()` and that
+/// `ciphertext` and `ad` are not longer than [`P_MAX`] and [`A_MAX`] respectively.
+fn _open(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+    ad: &[u8],
+    expected_tag: &[u8],
+    dst_out: &mut [u8],
+) -> Result<(), UnknownCryptoError> {
+    debug_assert!(dst_out.len() >= ciphertext.len());
+
+    let mut dec_ctx =
+        ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
+    let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
+    let mut auth_ctx = Poly1305::new(&poly1305_key_gen(&mut dec_ctx, &mut tmp));
 
-    dst_out[ct_len..(ct_len + POLY1305_OUTSIZE)]
-        .copy_from_slice(auth_ctx.finalize()?.unprotected_as_bytes());
+    let ciphertext_len = ciphertext.len();
+    process_authentication(&mut auth_ctx, ad, ciphertext)?;
+    util::secure_cmp(auth_ctx.finalize()?.unprotected_as_bytes(), expected_tag)?;
+
+    if ciphertext_len != 0 {
+        dst_out[..ciphertext_len].copy_from_slice(ciphertext);
+        chacha20::xor_keystream(
+            &mut dec_ctx,
+            ENC_CTR,
+            tmp.as_mut(),
+            &mut dst_out[..ciphertext_len],
+        )?;
+    }
 
     Ok(())
 }
@@ -283,29 +376,287 @@ pub fn open(
         return Err(UnknownCryptoError);
     }
 
-    let mut dec_ctx =
-        ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
-    let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
-    let mut auth_ctx = Poly1305::new(&poly1305_key_gen(&mut dec_ctx, &mut tmp));
-
     let ciphertext_len = ciphertext_with_tag.len() - POLY1305_OUTSIZE;
-    process_authentication(&mut auth_ctx, ad, &ciphertext_with_tag[..ciphertext_len])?;
-    util::secure_cmp(
-        auth_ctx.finalize()?.unprotected_as_bytes(),
+    _open(
+        secret_key,
+        nonce,
+        &ciphertext_with_tag[..ciphertext_len],
+        ad,
         &ciphertext_with_tag[ciphertext_len..],
-    )?;
+        dst_out,
+    )
+}
 
-    if ciphertext_len != 0 {
-        dst_out[..ciphertext_len].copy_from_slice(&ciphertext_with_tag[..ciphertext_len]);
-        chacha20::xor_keystream(
-            &mut dec_ctx,
-            ENC_CTR,
-            tmp.as_mut(),
-            &mut dst_out[..ciphertext_len],
-        )?;
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD ChaCha20Poly1305 decryption and authentication as specified in the [RFC 8439](https://tools.ietf.org/html/rfc8439),
+/// using a Poly1305 tag that is separate from the ciphertext.
+pub fn open_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+    ad: Option<&[u8]>,
+    expected: &Tag,
+    dst_out: &mut [u8],
+) -> Result<(), UnknownCryptoError> {
+    let ad = check_lengths(ciphertext.len(), ad)?;
+    if dst_out.len() < ciphertext.len() {
+        return Err(UnknownCryptoError);
     }
 
-    Ok(())
+    _open(
+        secret_key,
+        nonce,
+        ciphertext,
+        ad,
+        expected.unprotected_as_bytes(),
+        dst_out,
+    )
+}
+
+/// ChaCha20Poly1305 streaming state, where the additional data and the
+/// ciphertext can be given in pieces.
+///
+/// All additional data must be given with [`Self::update_aad()`] before the
+/// first call to [`Self::seal_update()`] or [`Self::update_ciphertext()`].
+/// The Poly1305 padding between the two sections is inserted automatically.
+///
+/// A sender encrypts with [`Self::seal_update()`] and gets the tag from
+/// [`Self::finalize()`]. A receiver authenticates the ciphertext with
+/// [`Self::update_ciphertext()`] and checks the tag with [`Self::finalize_verify()`],
+/// before decrypting it, for example with [`open_detached()`].
+///
+/// # Errors:
+/// An error will be returned if:
+/// - [`Self::update_aad()`] is called after [`Self::seal_update()`] or
+///   [`Self::update_ciphertext()`].
+/// - Any method is called after [`Self::finalize()`] or [`Self::finalize_verify()`].
+/// - The length of `dst_out` is less than `plaintext` when calling [`Self::seal_update()`].
+/// - [`Self::seal_update()`] and [`Self::update_ciphertext()`] are both called
+///   on the same state.
+/// - The total length of the ciphertext is greater than [`P_MAX`].
+/// - The calculated tag does not match `expected` when calling [`Self::finalize_verify()`].
+///
+/// # Security:
+/// - The same requirements for the nonce apply as for [`seal()`]. A state should
+///   only be used to either seal or open a single message.
+pub struct ChaCha20Poly1305 {
+    stream: ChaCha20,
+    auth_ctx: Poly1305,
+    /// The keystream block that the ciphertext so far ends in, if it ends
+    /// part of the way through one.
+    keystream_block: [u8; CHACHA_BLOCKSIZE],
+    ad_len: u64,
+    ct_len: u64,
+    mode: CiphertextMode,
+    is_ct_started: bool,
+    is_finalized: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How the ciphertext of a [`ChaCha20Poly1305`] state is given to it.
+enum CiphertextMode {
+    /// Neither [`ChaCha20Poly1305::seal_update()`] nor
+    /// [`ChaCha20Poly1305::update_ciphertext()`] has been called yet.
+    Unset,
+    /// Produced by [`ChaCha20Poly1305::seal_update()`].
+    Seal,
+    /// Given to [`ChaCha20Poly1305::update_ciphertext()`].
+    Authenticate,
+}
+
+impl Drop for ChaCha20Poly1305 {
+    fn drop(&mut self) {
+        self.keystream_block.zeroize();
+    }
+}
+
+impl core::fmt::Debug for ChaCha20Poly1305 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ChaCha20Poly1305 {{ stream: [***OMITTED***], auth_ctx: [***OMITTED***], keystream_block: [***OMITTED***], ad_len: {:?}, ct_len: {:?}, mode: {:?}, is_ct_started: {:?}, is_finalized: {:?} }}",
+            self.ad_len, self.ct_len, self.mode, self.is_ct_started, self.is_finalized
+        )
+    }
+}
+
+impl ChaCha20Poly1305 {
+    /// Initialize a `ChaCha20Poly1305` struct with a given secret key and nonce.
+    pub fn new(secret_key: &SecretKey, nonce: &Nonce) -> Self {
+        let mut stream =
+            ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
+        let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
+        let auth_ctx = Poly1305::new(&poly1305_key_gen(&mut stream, &mut tmp));
+
+        Self {
+            stream,
+            auth_ctx,
+            keystream_block: [0u8; CHACHA_BLOCKSIZE],
+            ad_len: 0,
+            ct_len: 0,
+            mode: CiphertextMode::Unset,
+            is_ct_started: false,
+            is_finalized: false,
+        }
+    }
+
+    /// Use the state for ciphertext given in `mode`, which must be the same for
+    /// the whole message.
+    fn set_mode(&mut self, mode: CiphertextMode) -> Result<(), UnknownCryptoError> {
+        if self.is_finalized {
+            return Err(UnknownCryptoError);
+        }
+
+        match self.mode {
+            CiphertextMode::Unset => {
+                self.mode = mode;
+                Ok(())
+            }
+            current if current == mode => Ok(()),
+            _ => Err(UnknownCryptoError),
+        }
+    }
+
+    /// Pad the additional data to the Poly1305 blocksize, once.
+    fn start_ciphertext(&mut self) -> Result<(), UnknownCryptoError> {
+        if !self.is_ct_started {
+            let rem = (self.ad_len % 16) as usize;
+            if rem != 0 {
+                self.auth_ctx.update(&[0u8; 16][rem..])?;
+            }
+            self.is_ct_started = true;
+        }
+
+        Ok(())
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Update the state with additional data. This can be called multiple times.
+    pub fn update_aad(&mut self, ad: &[u8]) -> Result<(), UnknownCryptoError> {
+        if self.is_finalized || self.is_ct_started {
+            return Err(UnknownCryptoError);
+        }
+
+        let ad_len = u64::try_from(ad.len()).map_err(|_| UnknownCryptoError)?;
+        self.ad_len = self.ad_len.checked_add(ad_len).ok_or(UnknownCryptoError)?;
+        self.auth_ctx.update(ad)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Update the state with ciphertext to authenticate, without decrypting it.
+    /// This can be called multiple times.
+    pub fn update_ciphertext(&mut self, ciphertext: &[u8]) -> Result<(), UnknownCryptoError> {
+        self.set_mode(CiphertextMode::Authenticate)?;
+        self.authenticate(ciphertext)
+    }
+
+    /// Pass `ciphertext` to Poly1305 and count it.
+    fn authenticate(&mut self, ciphertext: &[u8]) -> Result<(), UnknownCryptoError> {
+        let ct_len = u64::try_from(ciphertext.len()).map_err(|_| UnknownCryptoError)?;
+        let ct_len = self.ct_len.checked_add(ct_len).ok_or(UnknownCryptoError)?;
+        if ct_len > P_MAX {
+            return Err(UnknownCryptoError);
+        }
+
+        self.start_ciphertext()?;
+        self.ct_len = ct_len;
+        self.auth_ctx.update(ciphertext)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Encrypt `plaintext` into `dst_out` and update the state with the resulting
+    /// ciphertext. This can be called multiple times.
+    pub fn seal_update(
+        &mut self,
+        plaintext: &[u8],
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        self.set_mode(CiphertextMode::Seal)?;
+        if dst_out.len() < plaintext.len() {
+            return Err(UnknownCryptoError);
+        }
+        let pt_len = u64::try_from(plaintext.len()).map_err(|_| UnknownCryptoError)?;
+        if self.ct_len.checked_add(pt_len).ok_or(UnknownCryptoError)? > P_MAX {
+            return Err(UnknownCryptoError);
+        }
+        if plaintext.is_empty() {
+            return Ok(());
+        }
+
+        // Use what is left of the last keystream block first.
+        let offset = (self.ct_len % CHACHA_BLOCKSIZE as u64) as usize;
+        let mut processed = 0;
+        if offset != 0 {
+            processed = core::cmp::min(CHACHA_BLOCKSIZE - offset, plaintext.len());
+            dst_out[..processed].copy_from_slice(&plaintext[..processed]);
+            xor_slices!(self.keystream_block[offset..], dst_out[..processed]);
+        }
+
+        if processed < plaintext.len() {
+            // The rest starts at a block boundary before `P_MAX`, so the counter
+            // cannot overflow.
+            let position = self.ct_len + processed as u64;
+            let mut counter = ENC_CTR + u32::try_from(position / CHACHA_BLOCKSIZE as u64).unwrap();
+            let remaining = &plaintext[processed..];
+            let full = remaining.len() - (remaining.len() % CHACHA_BLOCKSIZE);
+            let (blocks, tail) = remaining.split_at(full);
+            let dst = &mut dst_out[processed..plaintext.len()];
+            if !blocks.is_empty() {
+                let mut tmp = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
+                chacha20::xor_keystream_into(
+                    &mut self.stream,
+                    counter,
+                    tmp.as_mut(),
+                    blocks,
+                    &mut dst[..full],
+                )?;
+                counter += (full / CHACHA_BLOCKSIZE) as u32;
+            }
+            if !tail.is_empty() {
+                // Keep the block, since the rest of it is used by the next call.
+                self.stream.next_produceable()?;
+                self.stream
+                    .keystream_block(counter, &mut self.keystream_block);
+                dst[full..].copy_from_slice(tail);
+                xor_slices!(self.keystream_block, dst[full..]);
+            }
+        }
+
+        self.authenticate(&dst_out[..plaintext.len()])
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return the Poly1305 tag of the additional data and ciphertext.
+    pub fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+        if self.is_finalized {
+            return Err(UnknownCryptoError);
+        }
+
+        self.start_ciphertext()?;
+        let rem = (self.ct_len % 16) as usize;
+        if rem != 0 {
+            self.auth_ctx.update(&[0u8; 16][rem..])?;
+        }
+
+        let mut tmp_pad = [0u8; 16];
+        tmp_pad[0..8].copy_from_slice(&self.ad_len.to_le_bytes());
+        tmp_pad[8..16].copy_from_slice(&self.ct_len.to_le_bytes());
+        self.auth_ctx.update(tmp_pad.as_ref())?;
+        self.is_finalized = true;
+
+        self.auth_ctx.finalize()
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Verify that the Poly1305 tag of the additional data and ciphertext
+    /// matches `expected`, in constant time.
+    pub fn finalize_verify(&mut self, expected: &Tag) -> Result<(), UnknownCryptoError> {
+        if &self.finalize()? == expected {
+            Ok(())
+        } else {
+            Err(UnknownCryptoError)
+        }
+    }
 }
 
 // Testing public functions in the module.
@@ -333,6 +684,335 @@ mod public {
         test_diff_params_err(&seal, &open, &input, POLY1305_OUTSIZE);
         true
     }
+
+    mod test_seal_in_place {
+        use super::*;
+
+        #[quickcheck]
+        /// Sealing in a single pass should give the same ciphertext and tag as
+        /// encrypting and then authenticating in two passes.
+        fn prop_single_pass_equals_two_pass(input: Vec<u8>, ad: Vec<u8>) -> bool {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::from_slice(&[0u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let mut two_pass = vec![0u8; input.len()];
+            let expected_tag =
+                seal_detached(&secret_key, &nonce, &input, Some(&ad), &mut two_pass).unwrap();
+
+            let mut in_out = input.clone();
+            let tag = seal_in_place_detached(&secret_key, &nonce, &mut in_out, Some(&ad)).unwrap();
+            if in_out != two_pass || tag != expected_tag {
+                return false;
+            }
+
+            let mut plaintext = vec![0u8; input.len()];
+            open_detached(
+                &secret_key,
+                &nonce,
+                &in_out,
+                Some(&ad),
+                &tag,
+                &mut plaintext,
+            )
+            .unwrap();
+
+            plaintext == input
+        }
+
+        #[test]
+        /// Lengths around the chunk sizes of the keystream, with and without `ad`.
+        fn test_single_pass_equals_two_pass_lengths() {
+            let secret_key = SecretKey::from_slice(&[1u8; 32]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+            let input = [3u8; 4 * 256 + 17];
+
+            for len in [
+                0,
+                1,
+                15,
+                16,
+                17,
+                63,
+                64,
+                65,
+                255,
+                256,
+                257,
+                1024,
+                4 * 256 + 17,
+            ] {
+                for ad in [None, Some(&b"ad"[..])] {
+                    let mut two_pass = vec![0u8; len];
+                    let expected_tag =
+                        seal_detached(&secret_key, &nonce, &input[..len], ad, &mut two_pass)
+                            .unwrap();
+
+                    let mut in_out = input[..len].to_vec();
+                    let tag = seal_in_place_detached(&secret_key, &nonce, &mut in_out, ad).unwrap();
+                    assert_eq!(in_out, two_pass);
+                    assert_eq!(tag, expected_tag);
+                }
+            }
+        }
+    }
+
+    mod test_detached {
+        use super::*;
+
+        #[quickcheck]
+        /// Detached seal/open should be equivalent to the combined seal/open.
+        fn prop_detached_equals_combined(input: Vec<u8>, ad: Vec<u8>) -> bool {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::from_slice(&[0u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let mut combined = vec![0u8; input.len() + POLY1305_OUTSIZE];
+            seal(&secret_key, &nonce, &input, Some(&ad), &mut combined).unwrap();
+
+            let mut ciphertext = vec![0u8; input.len()];
+            let tag =
+                seal_detached(&secret_key, &nonce, &input, Some(&ad), &mut ciphertext).unwrap();
+            if combined[..input.len()] != ciphertext[..] || tag != &combined[input.len()..] {
+                return false;
+            }
+
+            let mut plaintext = vec![0u8; input.len()];
+            open_detached(
+                &secret_key,
+                &nonce,
+                &ciphertext,
+                Some(&ad),
+                &tag,
+                &mut plaintext,
+            )
+            .unwrap();
+
+            plaintext == input
+        }
+
+        #[test]
+        fn test_tampered_tag_err() {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::from_slice(&[0u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+            let input = b"Data to protect";
+
+            let mut ciphertext = [0u8; 15];
+            let tag = seal_detached(&secret_key, &nonce, input, None, &mut ciphertext).unwrap();
+
+            let mut bad_tag = [0u8; POLY1305_OUTSIZE];
+            bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+            bad_tag[0] ^= 1;
+
+            let mut plaintext = [0u8; 15];
+            assert!(open_detached(
+                &secret_key,
+                &nonce,
+                &ciphertext,
+                None,
+                &Tag::from(bad_tag),
+                &mut plaintext
+            )
+            .is_err());
+            // No plaintext should be released on an invalid tag.
+            assert_eq!(plaintext, [0u8; 15]);
+
+            assert!(
+                open_detached(&secret_key, &nonce, &ciphertext, None, &tag, &mut plaintext).is_ok()
+            );
+            assert_eq!(&plaintext, input);
+        }
+
+        #[test]
+        fn test_dst_out_length() {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::from_slice(&[0u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+            let input = [0u8; 64];
+
+            let mut ciphertext = [0u8; 63];
+            assert!(seal_detached(&secret_key, &nonce, &input, None, &mut ciphertext).is_err());
+            let mut ciphertext = [0u8; 64];
+            let tag = seal_detached(&secret_key, &nonce, &input, None, &mut ciphertext).unwrap();
+
+            let mut plaintext = [0u8; 63];
+            assert!(
+                open_detached(&secret_key, &nonce, &ciphertext, None, &tag, &mut plaintext)
+                    .is_err()
+            );
+            let mut plaintext = [0u8; 65];
+            assert!(
+                open_detached(&secret_key, &nonce, &ciphertext, None, &tag, &mut plaintext).is_ok()
+            );
+            assert_eq!(plaintext[..64], input);
+        }
+
+        #[test]
+        fn test_empty_plaintext() {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::from_slice(&[0u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let tag = seal_detached(&secret_key, &nonce, &[], Some(b"ad"), &mut []).unwrap();
+            assert!(open_detached(&secret_key, &nonce, &[], Some(b"ad"), &tag, &mut []).is_ok());
+            assert!(open_detached(&secret_key, &nonce, &[], None, &tag, &mut []).is_err());
+        }
+    }
+
+    mod test_streaming_state {
+        use super::*;
+
+        fn one_shot(input: &[u8], ad: &[u8]) -> (Vec<u8>, Tag) {
+            let secret_key = SecretKey::from_slice(&[1u8; 32]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap();
+            let mut ciphertext = vec![0u8; input.len()];
+            let tag = seal_detached(&secret_key, &nonce, input, Some(ad), &mut ciphertext).unwrap();
+
+            (ciphertext, tag)
+        }
+
+        fn new_state() -> ChaCha20Poly1305 {
+            ChaCha20Poly1305::new(
+                &SecretKey::from_slice(&[1u8; 32]).unwrap(),
+                &Nonce::from_slice(&[2u8; chacha20::IETF_CHACHA_NONCESIZE]).unwrap(),
+            )
+        }
+
+        #[test]
+        fn test_chunked_aad_same_as_one_shot() {
+            let ad: [u8; 100] = core::array::from_fn(|i| i as u8);
+            let input = [7u8; 150];
+
+            for chunk_size in [1, 7, 16, 17, 64, 100] {
+                let (expected_ct, expected_tag) = one_shot(&input, &ad);
+
+                let mut state = new_state();
+                for chunk in ad.chunks(chunk_size) {
+                    state.update_aad(chunk).unwrap();
+                }
+                let mut ciphertext = [0u8; 150];
+                for (pt, ct) in input
+                    .chunks(chunk_size)
+                    .zip(ciphertext.chunks_mut(chunk_size))
+                {
+                    state.seal_update(pt, ct).unwrap();
+                }
+                assert_eq!(&ciphertext[..], &expected_ct[..]);
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+
+                // The receiving side.
+                let mut state = new_state();
+                for chunk in ad.chunks(chunk_size) {
+                    state.update_aad(chunk).unwrap();
+                }
+                for chunk in ciphertext.chunks(chunk_size) {
+                    state.update_ciphertext(chunk).unwrap();
+                }
+                assert!(state.finalize_verify(&expected_tag).is_ok());
+            }
+        }
+
+        #[quickcheck]
+        fn prop_split_same_as_one_shot(input: Vec<u8>, ad: Vec<u8>, split: usize) -> bool {
+            let (expected_ct, expected_tag) = one_shot(&input, &ad);
+            let ad_split = if ad.is_empty() { 0 } else { split % ad.len() };
+            let pt_split = if input.is_empty() {
+                0
+            } else {
+                split % input.len()
+            };
+
+            let mut state = new_state();
+            state.update_aad(&ad[..ad_split]).unwrap();
+            state.update_aad(&ad[ad_split..]).unwrap();
+            let mut ciphertext = vec![0u8; input.len()];
+            let (first, second) = ciphertext.split_at_mut(pt_split);
+            state.seal_update(&input[..pt_split], first).unwrap();
+            state.seal_update(&input[pt_split..], second).unwrap();
+
+            ciphertext == expected_ct && state.finalize().unwrap() == expected_tag
+        }
+
+        #[test]
+        fn test_empty_inputs_same_as_one_shot() {
+            let (_, expected_tag) = one_shot(&[], &[]);
+            let mut state = new_state();
+            state.update_aad(&[]).unwrap();
+            state.seal_update(&[], &mut []).unwrap();
+            assert_eq!(state.finalize().unwrap(), expected_tag);
+
+            let (_, expected_tag) = one_shot(&[], b"ad");
+            let mut state = new_state();
+            state.update_aad(b"a").unwrap();
+            state.update_aad(b"d").unwrap();
+            assert_eq!(state.finalize().unwrap(), expected_tag);
+        }
+
+        #[test]
+        fn test_out_of_order_err() {
+            let mut state = new_state();
+            state.update_aad(b"ad").unwrap();
+            state.update_ciphertext(b"ct").unwrap();
+            assert!(state.update_aad(b"ad").is_err());
+
+            let mut state = new_state();
+            state.seal_update(b"pt", &mut [0u8; 2]).unwrap();
+            assert!(state.update_aad(b"ad").is_err());
+            let _ = state.finalize().unwrap();
+            assert!(state.finalize().is_err());
+            assert!(state.update_aad(b"ad").is_err());
+            assert!(state.update_ciphertext(b"ct").is_err());
+            assert!(state.seal_update(b"pt", &mut [0u8; 2]).is_err());
+        }
+
+        #[test]
+        fn test_mixed_seal_and_authenticate_err() {
+            let mut state = new_state();
+            state.seal_update(b"pt", &mut [0u8; 2]).unwrap();
+            assert!(state.update_ciphertext(b"ct").is_err());
+            state.seal_update(b"pt", &mut [0u8; 2]).unwrap();
+
+            let mut state = new_state();
+            state.update_ciphertext(b"ct").unwrap();
+            assert!(state.seal_update(b"pt", &mut [0u8; 2]).is_err());
+            state.update_ciphertext(b"ct").unwrap();
+
+            // Empty input also decides how the state is used.
+            let mut state = new_state();
+            state.seal_update(&[], &mut []).unwrap();
+            assert!(state.update_ciphertext(&[]).is_err());
+        }
+
+        #[test]
+        fn test_small_updates_use_each_keystream_block_once() {
+            let input = [7u8; 200];
+            let (expected_ct, expected_tag) = one_shot(&input, &[]);
+
+            let mut state = new_state();
+            let mut ciphertext = [0u8; 200];
+            for (pt, ct) in input.iter().zip(ciphertext.iter_mut()) {
+                state
+                    .seal_update(&[*pt], core::slice::from_mut(ct))
+                    .unwrap();
+            }
+            // One block for the Poly1305 key and four for the 200 bytes of keystream.
+            assert_eq!(state.stream.blocks_produced(), 1 + 4);
+            assert_eq!(&ciphertext[..], &expected_ct[..]);
+            assert_eq!(state.finalize().unwrap(), expected_tag);
+        }
+
+        #[test]
+        fn test_verify_err() {
+            let (ciphertext, tag) = one_shot(b"Data to protect", b"ad");
+            let mut state = new_state();
+            state.update_aad(b"ad").unwrap();
+            state.update_ciphertext(&ciphertext[1..]).unwrap();
+            assert!(state.finalize_verify(&tag).is_err());
+        }
+
+        #[test]
+        fn test_dst_out_length() {
+            let mut state = new_state();
+            assert!(state.seal_update(&[0u8; 16], &mut [0u8; 15]).is_err());
+            assert!(state.seal_update(&[0u8; 16], &mut [0u8; 17]).is_ok());
+        }
+    }
 }
 
 // Testing any test vectors that aren't put into library's /tests folder.
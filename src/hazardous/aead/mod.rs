@@ -28,3 +28,13 @@ pub mod xchacha20poly1305;
 
 /// Streaming AEAD based on XChaCha20Poly1305.
 pub mod streaming;
+
+#[cfg(feature = "experimental")]
+#[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+/// STREAM construction based on XChaCha20Poly1305. __WARNING:__ Experimental feature.
+pub mod stream;
+
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+/// Builder for structured additional authenticated data.
+pub mod aad;
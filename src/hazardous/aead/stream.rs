@@ -0,0 +1,536 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! The STREAM construction by [Hoang et al.] for encrypting a long message as a sequence
+//! of chunks, using XChaCha20Poly1305 as the underlying AEAD.
+//!
+//! Each chunk is encrypted with its own nonce, made up of a nonce prefix chosen by the
+//! caller, a 32-bit big-endian chunk counter and a one-byte flag marking the last chunk:
+//!
+//! `nonce_prefix (19 bytes) || BE32(counter) || last_chunk_flag (1 byte)`
+//!
+//! Because the position of a chunk and whether it is the last are part of its nonce,
+//! reordering, dropping or truncating chunks makes decryption fail.
+//!
+//! # Parameters:
+//! - `secret_key`: The secret key.
+//! - `nonce_prefix`: The nonce prefix used for every chunk in the stream.
+//! - `ad`: Additional data to authenticate (this is not encrypted and can be [`None`]).
+//! - `plaintext`: The chunk to be encrypted.
+//! - `ciphertext_with_tag`: The encrypted chunk with the corresponding 16 byte
+//!   Poly1305 tag appended to it.
+//! - `dst_out`: Destination array that will hold the
+//!   `ciphertext_with_tag`/`plaintext` after encryption/decryption.
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - The length of `dst_out` is less than `plaintext` + [`ABYTES`] when encrypting.
+//! - The length of `dst_out` is less than `ciphertext_with_tag` - [`ABYTES`] when
+//!   decrypting.
+//! - The length of `ciphertext_with_tag` is less than [`ABYTES`].
+//! - The received tag does not match the calculated tag when decrypting. This can
+//!   indicate a reordered, dropped or truncated chunk.
+//! - [`encrypt_next()`]/[`decrypt_next()`] is called after `2^32-1` chunks have been
+//!   processed. The last chunk can still be processed with [`encrypt_last()`]/[`decrypt_last()`].
+//! - More than `2^32-1 * 64` bytes of data are processed in a single chunk.
+//! - Any of the errors of [`seal()`]/[`open()`] occur for a single chunk.
+//!
+//! # Security:
+//! - It is critical for security that a given nonce prefix is not re-used with a given
+//!   key. Should this happen, the security of all data that has been encrypted with
+//!   that given key is compromised.
+//! - The nonce prefix can be randomly generated using a CSPRNG. [`NoncePrefix::generate()`]
+//!   can be used for this.
+//! - To securely generate a strong key, use [`SecretKey::generate()`].
+//! - The stream must be ended with [`encrypt_last()`]. Otherwise, the decrypting side
+//!   cannot detect that chunks at the end of the stream are missing.
+//! - Plaintext from [`decrypt_next()`] should not be considered complete until
+//!   [`decrypt_last()`] has succeeded.
+//! - The lengths of the chunks are not hidden, only their contents.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::aead::stream::*;
+//!
+//! let secret_key = SecretKey::generate();
+//! let nonce_prefix = NoncePrefix::generate();
+//!
+//! let mut first = [0u8; 5 + ABYTES];
+//! let mut last = [0u8; 6 + ABYTES];
+//!
+//! let mut encryptor = Encryptor::new(&secret_key, &nonce_prefix);
+//! encryptor.encrypt_next(b"Large", None, &mut first)?;
+//! encryptor.encrypt_last(b" input", None, &mut last)?;
+//!
+//! let mut first_pt = [0u8; 5];
+//! let mut last_pt = [0u8; 6];
+//!
+//! let mut decryptor = Decryptor::new(&secret_key, &nonce_prefix);
+//! decryptor.decrypt_next(&first, None, &mut first_pt)?;
+//! decryptor.decrypt_last(&last, None, &mut last_pt)?;
+//!
+//! assert_eq!(&first_pt, b"Large");
+//! assert_eq!(&last_pt, b" input");
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [Hoang et al.]: https://eprint.iacr.org/2015/189.pdf
+//! [`ABYTES`]: stream::ABYTES
+//! [`encrypt_next()`]: stream::Encryptor::encrypt_next
+//! [`encrypt_last()`]: stream::Encryptor::encrypt_last
+//! [`decrypt_next()`]: stream::Decryptor::decrypt_next
+//! [`decrypt_last()`]: stream::Decryptor::decrypt_last
+//! [`seal()`]: crate::hazardous::aead::xchacha20poly1305::seal
+//! [`open()`]: crate::hazardous::aead::xchacha20poly1305::open
+//! [`NoncePrefix::generate()`]: stream::NoncePrefix::generate
+//! [`SecretKey::generate()`]: crate::hazardous::stream::chacha20::SecretKey::generate
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::aead::chacha20poly1305;
+use crate::hazardous::mac::poly1305::POLY1305_OUTSIZE;
+pub use crate::hazardous::stream::chacha20::SecretKey;
+use crate::hazardous::stream::chacha20::{Nonce as IETFNonce, IETF_CHACHA_NONCESIZE};
+use crate::hazardous::stream::xchacha20::{subkey_and_nonce, Nonce, XCHACHA_NONCESIZE};
+
+/// The size of the chunk counter.
+const COUNTERBYTES: usize = 4;
+/// The size of the last chunk flag.
+const FLAGBYTES: usize = 1;
+/// The size of the nonce prefix.
+pub const NONCE_PREFIXSIZE: usize = XCHACHA_NONCESIZE - COUNTERBYTES - FLAGBYTES;
+/// Size of additional data appended to each chunk.
+pub const ABYTES: usize = POLY1305_OUTSIZE;
+
+construct_public! {
+    /// A type that represents the nonce prefix used for every chunk in a STREAM.
+    ///
+    /// # Errors:
+    /// An error will be returned if:
+    /// - `slice` is not 19 bytes.
+    ///
+    /// # Panics:
+    /// A panic will occur if:
+    /// - Failure to generate random bytes securely.
+    (NoncePrefix, test_nonce_prefix, NONCE_PREFIXSIZE, NONCE_PREFIXSIZE, NONCE_PREFIXSIZE)
+}
+
+impl_from_trait!(NoncePrefix, NONCE_PREFIXSIZE);
+
+/// Per-stream state shared by [`Encryptor`] and [`Decryptor`].
+struct StreamState {
+    // The first 16 bytes of the prefix are fixed for a stream, so the
+    // HChaCha20 subkey only needs to be derived once.
+    subkey: SecretKey,
+    nonce: [u8; IETF_CHACHA_NONCESIZE],
+    counter: u32,
+}
+
+impl StreamState {
+    fn new(secret_key: &SecretKey, nonce_prefix: &NoncePrefix) -> Self {
+        let mut nonce = [0u8; XCHACHA_NONCESIZE];
+        nonce[..NONCE_PREFIXSIZE].copy_from_slice(nonce_prefix.as_ref());
+        let (subkey, ietf_nonce) = subkey_and_nonce(secret_key, &Nonce::from(nonce));
+
+        let mut nonce = [0u8; IETF_CHACHA_NONCESIZE];
+        nonce.copy_from_slice(ietf_nonce.as_ref());
+
+        Self {
+            subkey,
+            nonce,
+            counter: 0,
+        }
+    }
+
+    /// Return the IETF nonce for the current chunk.
+    fn get_nonce(&self, last: bool) -> IETFNonce {
+        let mut nonce = self.nonce;
+        let counter_pos = IETF_CHACHA_NONCESIZE - COUNTERBYTES - FLAGBYTES;
+        nonce[counter_pos..counter_pos + COUNTERBYTES].copy_from_slice(&self.counter.to_be_bytes());
+        nonce[IETF_CHACHA_NONCESIZE - FLAGBYTES] = u8::from(last);
+
+        IETFNonce::from(nonce)
+    }
+
+    /// Return the IETF nonce for a chunk that is not the last, and advance the counter.
+    fn next_nonce(&mut self) -> Result<IETFNonce, UnknownCryptoError> {
+        // A counter equal to u32::MAX is left for the last chunk.
+        if self.counter == u32::MAX {
+            return Err(UnknownCryptoError);
+        }
+
+        let nonce = self.get_nonce(false);
+        self.counter += 1;
+
+        Ok(nonce)
+    }
+}
+
+/// STREAM encryption state.
+pub struct Encryptor {
+    state: StreamState,
+}
+
+impl core::fmt::Debug for Encryptor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Encryptor {{ subkey: [***OMITTED***], nonce: [***OMITTED***], counter: [***OMITTED***] }}",
+        )
+    }
+}
+
+impl Encryptor {
+    /// Initialize an `Encryptor` with a given secret key and nonce prefix.
+    pub fn new(secret_key: &SecretKey, nonce_prefix: &NoncePrefix) -> Self {
+        Self {
+            state: StreamState::new(secret_key, nonce_prefix),
+        }
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Encrypt a chunk that is not the last in the stream.
+    pub fn encrypt_next(
+        &mut self,
+        plaintext: &[u8],
+        ad: Option<&[u8]>,
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        let nonce = self.state.next_nonce()?;
+        chacha20poly1305::seal(&self.state.subkey, &nonce, plaintext, ad, dst_out)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Encrypt the last chunk in the stream. This consumes the `Encryptor`.
+    pub fn encrypt_last(
+        self,
+        plaintext: &[u8],
+        ad: Option<&[u8]>,
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        chacha20poly1305::seal(
+            &self.state.subkey,
+            &self.state.get_nonce(true),
+            plaintext,
+            ad,
+            dst_out,
+        )
+    }
+}
+
+/// STREAM decryption state.
+pub struct Decryptor {
+    state: StreamState,
+}
+
+impl core::fmt::Debug for Decryptor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Decryptor {{ subkey: [***OMITTED***], nonce: [***OMITTED***], counter: [***OMITTED***] }}",
+        )
+    }
+}
+
+impl Decryptor {
+    /// Initialize a `Decryptor` with a given secret key and nonce prefix.
+    pub fn new(secret_key: &SecretKey, nonce_prefix: &NoncePrefix) -> Self {
+        Self {
+            state: StreamState::new(secret_key, nonce_prefix),
+        }
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Decrypt a chunk that is not the last in the stream. The counter is only
+    /// advanced if decryption succeeds.
+    pub fn decrypt_next(
+        &mut self,
+        ciphertext_with_tag: &[u8],
+        ad: Option<&[u8]>,
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        if self.state.counter == u32::MAX {
+            return Err(UnknownCryptoError);
+        }
+
+        chacha20poly1305::open(
+            &self.state.subkey,
+            &self.state.get_nonce(false),
+            ciphertext_with_tag,
+            ad,
+            dst_out,
+        )?;
+        self.state.counter += 1;
+
+        Ok(())
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Decrypt the last chunk in the stream. This consumes the `Decryptor`.
+    pub fn decrypt_last(
+        self,
+        ciphertext_with_tag: &[u8],
+        ad: Option<&[u8]>,
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        chacha20poly1305::open(
+            &self.state.subkey,
+            &self.state.get_nonce(true),
+            ciphertext_with_tag,
+            ad,
+            dst_out,
+        )
+    }
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+#[cfg(feature = "safe_api")]
+mod public {
+    use super::*;
+    use crate::hazardous::aead::xchacha20poly1305;
+
+    /// Encrypt `chunks` as a stream, returning each encrypted chunk.
+    fn encrypt_stream(
+        secret_key: &SecretKey,
+        nonce_prefix: &NoncePrefix,
+        chunks: &[&[u8]],
+    ) -> Vec<Vec<u8>> {
+        let mut encryptor = Encryptor::new(secret_key, nonce_prefix);
+        let mut out = Vec::new();
+        for chunk in &chunks[..chunks.len() - 1] {
+            let mut dst_out = vec![0u8; chunk.len() + ABYTES];
+            encryptor.encrypt_next(chunk, None, &mut dst_out).unwrap();
+            out.push(dst_out);
+        }
+        let chunk = chunks[chunks.len() - 1];
+        let mut dst_out = vec![0u8; chunk.len() + ABYTES];
+        encryptor.encrypt_last(chunk, None, &mut dst_out).unwrap();
+        out.push(dst_out);
+
+        out
+    }
+
+    /// Decrypt a stream, returning the concatenated plaintext.
+    fn decrypt_stream(
+        secret_key: &SecretKey,
+        nonce_prefix: &NoncePrefix,
+        chunks: &[Vec<u8>],
+    ) -> Result<Vec<u8>, UnknownCryptoError> {
+        let mut decryptor = Decryptor::new(secret_key, nonce_prefix);
+        let mut out = Vec::new();
+        for chunk in &chunks[..chunks.len() - 1] {
+            let mut dst_out = vec![0u8; chunk.len() - ABYTES];
+            decryptor.decrypt_next(chunk, None, &mut dst_out)?;
+            out.extend_from_slice(&dst_out);
+        }
+        let chunk = &chunks[chunks.len() - 1];
+        let mut dst_out = vec![0u8; chunk.len() - ABYTES];
+        decryptor.decrypt_last(chunk, None, &mut dst_out)?;
+        out.extend_from_slice(&dst_out);
+
+        Ok(out)
+    }
+
+    #[test]
+    fn test_debug_impl() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encryptor = Encryptor::new(&secret_key, &nonce_prefix);
+        let decryptor = Decryptor::new(&secret_key, &nonce_prefix);
+
+        assert!(format!("{:?}", encryptor).contains("[***OMITTED***]"));
+        assert!(format!("{:?}", decryptor).contains("[***OMITTED***]"));
+    }
+
+    #[test]
+    fn test_chunked_roundtrip() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let input = [7u8; 1000];
+
+        for chunk_size in [1, 15, 16, 64, 333, 999, 1000] {
+            let chunks: Vec<&[u8]> = input.chunks(chunk_size).collect();
+            let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &chunks);
+            assert_eq!(
+                decrypt_stream(&secret_key, &nonce_prefix, &encrypted).unwrap(),
+                input
+            );
+        }
+
+        // A stream may consist of only an empty last chunk.
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[&[]]);
+        assert_eq!(encrypted[0].len(), ABYTES);
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &encrypted)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_chunk_nonces_match_xchacha20poly1305() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[b"first", b"last"]);
+
+        let mut nonce = [0u8; XCHACHA_NONCESIZE];
+        nonce[..NONCE_PREFIXSIZE].copy_from_slice(nonce_prefix.as_ref());
+        let mut dst_out = [0u8; 5 + ABYTES];
+        xchacha20poly1305::seal(
+            &secret_key,
+            &Nonce::from(nonce),
+            b"first",
+            None,
+            &mut dst_out,
+        )
+        .unwrap();
+        assert_eq!(encrypted[0], dst_out);
+
+        nonce[NONCE_PREFIXSIZE..NONCE_PREFIXSIZE + COUNTERBYTES]
+            .copy_from_slice(&1u32.to_be_bytes());
+        nonce[XCHACHA_NONCESIZE - 1] = 1;
+        let mut dst_out = [0u8; 4 + ABYTES];
+        xchacha20poly1305::seal(
+            &secret_key,
+            &Nonce::from(nonce),
+            b"last",
+            None,
+            &mut dst_out,
+        )
+        .unwrap();
+        assert_eq!(encrypted[1], dst_out);
+    }
+
+    #[test]
+    fn test_reject_truncated_stream() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[b"a", b"b", b"c"]);
+
+        // Dropping the last chunk makes the second chunk be treated as the last.
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &encrypted[..2]).is_err());
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &encrypted[..1]).is_err());
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &encrypted).is_ok());
+    }
+
+    #[test]
+    fn test_reject_reordered_or_dropped_chunks() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[b"a", b"b", b"c"]);
+
+        let reordered = vec![
+            encrypted[1].clone(),
+            encrypted[0].clone(),
+            encrypted[2].clone(),
+        ];
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &reordered).is_err());
+
+        let dropped = vec![encrypted[0].clone(), encrypted[2].clone()];
+        assert!(decrypt_stream(&secret_key, &nonce_prefix, &dropped).is_err());
+
+        // A last chunk cannot be decrypted as a non-last chunk.
+        let mut decryptor = Decryptor::new(&secret_key, &nonce_prefix);
+        let mut dst_out = [0u8; 1];
+        let single = encrypt_stream(&secret_key, &nonce_prefix, &[b"a"]);
+        assert!(decryptor
+            .decrypt_next(&single[0], None, &mut dst_out)
+            .is_err());
+    }
+
+    #[test]
+    fn test_failed_decrypt_does_not_advance() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[b"a", b"b"]);
+
+        let mut decryptor = Decryptor::new(&secret_key, &nonce_prefix);
+        let mut dst_out = [0u8; 1];
+        assert!(decryptor
+            .decrypt_next(&encrypted[1], None, &mut dst_out)
+            .is_err());
+        assert!(decryptor
+            .decrypt_next(&encrypted[0], None, &mut dst_out)
+            .is_ok());
+        assert_eq!(&dst_out, b"a");
+        assert!(decryptor
+            .decrypt_last(&encrypted[1], None, &mut dst_out)
+            .is_ok());
+        assert_eq!(&dst_out, b"b");
+    }
+
+    #[test]
+    fn test_diff_prefix_or_key_err() {
+        let secret_key = SecretKey::generate();
+        let nonce_prefix = NoncePrefix::generate();
+        let encrypted = encrypt_stream(&secret_key, &nonce_prefix, &[b"a", b"b"]);
+
+        assert!(decrypt_stream(&secret_key, &NoncePrefix::generate(), &encrypted).is_err());
+        assert!(decrypt_stream(&SecretKey::generate(), &nonce_prefix, &encrypted).is_err());
+    }
+}
+
+// Testing private functions in the module.
+#[cfg(test)]
+mod private {
+    use super::*;
+
+    #[test]
+    fn test_nonce_layout() {
+        let secret_key = SecretKey::from_slice(&[0u8; 32]).unwrap();
+        let nonce_prefix = NoncePrefix::from([255u8; NONCE_PREFIXSIZE]);
+        let mut state = StreamState::new(&secret_key, &nonce_prefix);
+
+        assert_eq!(
+            state.get_nonce(false).as_ref(),
+            &[0, 0, 0, 0, 255, 255, 255, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            state.next_nonce().unwrap().as_ref(),
+            &[0, 0, 0, 0, 255, 255, 255, 0, 0, 0, 0, 0]
+        );
+        state.counter = 0x01020304;
+        assert_eq!(
+            state.get_nonce(true).as_ref(),
+            &[0, 0, 0, 0, 255, 255, 255, 1, 2, 3, 4, 1]
+        );
+    }
+
+    #[test]
+    fn test_counter_overflow() {
+        let secret_key = SecretKey::from_slice(&[0u8; 32]).unwrap();
+        let nonce_prefix = NoncePrefix::from([0u8; NONCE_PREFIXSIZE]);
+        let mut dst_out = [0u8; ABYTES];
+
+        let mut encryptor = Encryptor::new(&secret_key, &nonce_prefix);
+        encryptor.state.counter = u32::MAX - 1;
+        assert!(encryptor.encrypt_next(&[], None, &mut dst_out).is_ok());
+        assert!(encryptor.encrypt_next(&[], None, &mut dst_out).is_err());
+        assert!(encryptor.encrypt_last(&[], None, &mut dst_out).is_ok());
+
+        let mut decryptor = Decryptor::new(&secret_key, &nonce_prefix);
+        decryptor.state.counter = u32::MAX;
+        let mut pt = [0u8; 0];
+        assert!(decryptor.decrypt_next(&dst_out, None, &mut pt).is_err());
+        assert!(decryptor.decrypt_last(&dst_out, None, &mut pt).is_ok());
+    }
+}
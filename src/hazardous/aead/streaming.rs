@@ -249,7 +249,7 @@ impl StreamXChaCha20Poly1305 {
                 .unwrap())
             .to_le_bytes(),
         );
-        poly.update(&pad)?;
+        poly.update(pad)?;
 
         poly.finalize()
     }
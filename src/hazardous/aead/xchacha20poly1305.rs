@@ -27,6 +27,8 @@
 //! - `ciphertext_with_tag`: The encrypted data with the corresponding 16 byte
 //!   Poly1305 tag appended to it.
 //! - `plaintext`: The data to be encrypted.
+//! - `ciphertext`: The encrypted data, without a tag, when calling [`open_detached()`].
+//! - `expected`: The expected Poly1305 tag, when calling [`open_detached()`].
 //! - `dst_out`: Destination array that will hold the
 //!   `ciphertext_with_tag`/`plaintext` after encryption/decryption.
 //!
@@ -43,7 +45,9 @@
 //! - The length of `dst_out` is less than `ciphertext_with_tag` - [`POLY1305_OUTSIZE`] when
 //!   calling [`open()`].
 //! - The length of the `ciphertext_with_tag` is not at least [`POLY1305_OUTSIZE`].
-//! - The received tag does not match the calculated tag when  calling [`open()`].
+//! - The length of `dst_out` is less than `plaintext` when calling [`seal_detached()`].
+//! - The length of `dst_out` is less than `ciphertext` when calling [`open_detached()`].
+//! - The received tag does not match the calculated tag when  calling [`open()`] or [`open_detached()`].
 //! - `plaintext.len()` + [`POLY1305_OUTSIZE`] overflows when  calling [`seal()`].
 //! - Converting `usize` to `u64` would be a lossy conversion.
 //! - `plaintext.len() >` [`chacha20poly1305::P_MAX`]
@@ -92,8 +96,11 @@
 //! [`POLY1305_OUTSIZE`]: super::mac::poly1305::POLY1305_OUTSIZE
 //! [`seal()`]: xchacha20poly1305::seal
 //! [`open()`]: xchacha20poly1305::open
+//! [`seal_detached()`]: xchacha20poly1305::seal_detached
+//! [`open_detached()`]: xchacha20poly1305::open_detached
 //! [libsodium docs]: https://download.libsodium.org/doc/secret-key_cryptography/aead#additional-data
 
+pub use crate::hazardous::mac::poly1305::Tag;
 use crate::hazardous::stream::xchacha20::subkey_and_nonce;
 pub use crate::hazardous::stream::{chacha20::SecretKey, xchacha20::Nonce};
 use crate::{errors::UnknownCryptoError, hazardous::aead::chacha20poly1305};
@@ -124,6 +131,49 @@ pub fn open(
     chacha20poly1305::open(&subkey, &ietf_nonce, ciphertext_with_tag, ad, dst_out)
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD XChaCha20Poly1305 encryption as specified in the [draft RFC](https://github.com/bikeshedders/xchacha-rfc),
+/// returning the Poly1305 tag separately from the ciphertext.
+pub fn seal_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    ad: Option<&[u8]>,
+    dst_out: &mut [u8],
+) -> Result<Tag, UnknownCryptoError> {
+    let (subkey, ietf_nonce) = subkey_and_nonce(secret_key, nonce);
+    chacha20poly1305::seal_detached(&subkey, &ietf_nonce, plaintext, ad, dst_out)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD XChaCha20Poly1305 encryption as specified in the [draft RFC](https://github.com/bikeshedders/xchacha-rfc),
+/// encrypting `in_out` in place and authenticating it in the same pass, returning the
+/// Poly1305 tag separately.
+pub fn seal_in_place_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    in_out: &mut [u8],
+    ad: Option<&[u8]>,
+) -> Result<Tag, UnknownCryptoError> {
+    let (subkey, ietf_nonce) = subkey_and_nonce(secret_key, nonce);
+    chacha20poly1305::seal_in_place_detached(&subkey, &ietf_nonce, in_out, ad)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// AEAD XChaCha20Poly1305 decryption as specified in the [draft RFC](https://github.com/bikeshedders/xchacha-rfc),
+/// using a Poly1305 tag that is separate from the ciphertext.
+pub fn open_detached(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+    ad: Option<&[u8]>,
+    expected: &Tag,
+    dst_out: &mut [u8],
+) -> Result<(), UnknownCryptoError> {
+    let (subkey, ietf_nonce) = subkey_and_nonce(secret_key, nonce);
+    chacha20poly1305::open_detached(&subkey, &ietf_nonce, ciphertext, ad, expected, dst_out)
+}
+
 // Testing public functions in the module.
 #[cfg(test)]
 #[cfg(feature = "safe_api")]
@@ -150,4 +200,66 @@ mod public {
         test_diff_params_err(&seal, &open, &input, POLY1305_OUTSIZE);
         true
     }
+
+    #[quickcheck]
+    #[cfg(feature = "safe_api")]
+    /// Detached seal/open should be equivalent to the combined seal/open.
+    fn prop_detached_equals_combined(input: Vec<u8>, ad: Vec<u8>) -> bool {
+        let secret_key = SecretKey::generate();
+        let nonce = Nonce::generate();
+
+        let mut combined = vec![0u8; input.len() + POLY1305_OUTSIZE];
+        seal(&secret_key, &nonce, &input, Some(&ad), &mut combined).unwrap();
+
+        let mut ciphertext = vec![0u8; input.len()];
+        let tag = seal_detached(&secret_key, &nonce, &input, Some(&ad), &mut ciphertext).unwrap();
+        if combined[..input.len()] != ciphertext[..] || tag != &combined[input.len()..] {
+            return false;
+        }
+
+        let mut bad_tag = [0u8; POLY1305_OUTSIZE];
+        bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+        bad_tag[0] ^= 1;
+        let mut plaintext = vec![0u8; input.len()];
+        if open_detached(
+            &secret_key,
+            &nonce,
+            &ciphertext,
+            Some(&ad),
+            &Tag::from(bad_tag),
+            &mut plaintext,
+        )
+        .is_ok()
+        {
+            return false;
+        }
+
+        open_detached(
+            &secret_key,
+            &nonce,
+            &ciphertext,
+            Some(&ad),
+            &tag,
+            &mut plaintext,
+        )
+        .unwrap();
+        plaintext == input
+    }
+
+    #[quickcheck]
+    #[cfg(feature = "safe_api")]
+    /// Sealing in place should be equivalent to the detached seal.
+    fn prop_seal_in_place_equals_detached(input: Vec<u8>, ad: Vec<u8>) -> bool {
+        let secret_key = SecretKey::generate();
+        let nonce = Nonce::generate();
+
+        let mut ciphertext = vec![0u8; input.len()];
+        let expected_tag =
+            seal_detached(&secret_key, &nonce, &input, Some(&ad), &mut ciphertext).unwrap();
+
+        let mut in_out = input;
+        let tag = seal_in_place_detached(&secret_key, &nonce, &mut in_out, Some(&ad)).unwrap();
+
+        in_out == ciphertext && tag == expected_tag
+    }
 }
@@ -0,0 +1,120 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Sizes of keys, nonces, blocks and outputs, for sizing buffers.
+//!
+//! These are the same constants as those in each primitive's module, gathered
+//! in one place. They are part of the stable API and will only change with a
+//! breaking release. Constants used only in the internals of a primitive are
+//! not re-exported here.
+//!
+//! # Example:
+//! ```rust
+//! use orion::hazardous::constants::{POLY1305_OUTSIZE, SHA512_OUTSIZE};
+//!
+//! // Room for a Poly1305 tag and a SHA512 digest on the stack.
+//! let mut tags = [0u8; POLY1305_OUTSIZE + SHA512_OUTSIZE];
+//! # assert_eq!(tags.len(), 80);
+//! ```
+
+// SHA-2. The output size is also the length of an HMAC tag (HLEN) with that hash function.
+pub use super::hash::sha2::sha256::{SHA256_BLOCKSIZE, SHA256_OUTSIZE};
+pub use super::hash::sha2::sha384::{SHA384_BLOCKSIZE, SHA384_OUTSIZE};
+pub use super::hash::sha2::sha512::{SHA512_BLOCKSIZE, SHA512_OUTSIZE};
+
+// Poly1305.
+pub use super::mac::poly1305::{POLY1305_BLOCKSIZE, POLY1305_KEYSIZE, POLY1305_OUTSIZE};
+
+// ChaCha20 and XChaCha20.
+pub use super::stream::chacha20::{CHACHA_BLOCKSIZE, CHACHA_KEYSIZE, IETF_CHACHA_NONCESIZE};
+pub use super::stream::xchacha20::XCHACHA_NONCESIZE;
+
+#[cfg(test)]
+mod public {
+    use super::*;
+    use crate::hazardous::hash::sha2::{sha256, sha384, sha512};
+    use crate::hazardous::mac::{hmac, poly1305};
+    use crate::hazardous::stream::{chacha20, xchacha20};
+
+    #[test]
+    fn test_sizes_match_types() {
+        assert_eq!(sha256::Sha256::digest(b"").unwrap().len(), SHA256_OUTSIZE);
+        assert_eq!(sha384::Sha384::digest(b"").unwrap().len(), SHA384_OUTSIZE);
+        assert_eq!(sha512::Sha512::digest(b"").unwrap().len(), SHA512_OUTSIZE);
+
+        // HMAC keys are padded to the blocksize, and tags are the output size.
+        let key = hmac::sha256::SecretKey::from_slice_padded(&[0u8; 1]).unwrap();
+        assert_eq!(key.len(), SHA256_BLOCKSIZE);
+        assert_eq!(
+            hmac::sha256::HmacSha256::hmac(&key, b"").unwrap().len(),
+            SHA256_OUTSIZE
+        );
+        let key = hmac::sha384::SecretKey::from_slice_padded(&[0u8; 1]).unwrap();
+        assert_eq!(key.len(), SHA384_BLOCKSIZE);
+        assert_eq!(
+            hmac::sha384::HmacSha384::hmac(&key, b"").unwrap().len(),
+            SHA384_OUTSIZE
+        );
+        let key = hmac::sha512::SecretKey::from_slice_padded(&[0u8; 1]).unwrap();
+        assert_eq!(key.len(), SHA512_BLOCKSIZE);
+        assert_eq!(
+            hmac::sha512::HmacSha512::hmac(&key, b"").unwrap().len(),
+            SHA512_OUTSIZE
+        );
+
+        let key = poly1305::OneTimeKey::from_slice(&[0u8; POLY1305_KEYSIZE]).unwrap();
+        assert!(poly1305::OneTimeKey::from_slice(&[0u8; POLY1305_KEYSIZE + 1]).is_err());
+        assert_eq!(
+            poly1305::Poly1305::poly1305(&key, b"").unwrap().len(),
+            POLY1305_OUTSIZE
+        );
+        // Poly1305 processes 16-byte blocks, so update_aligned() leaves a partial block.
+        let mut state = poly1305::Poly1305::new(&key);
+        let rest = state
+            .update_aligned(&[0u8; POLY1305_BLOCKSIZE + 1])
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+
+        assert!(chacha20::SecretKey::from_slice(&[0u8; CHACHA_KEYSIZE]).is_ok());
+        assert!(chacha20::SecretKey::from_slice(&[0u8; CHACHA_KEYSIZE + 1]).is_err());
+        assert!(chacha20::Nonce::from_slice(&[0u8; IETF_CHACHA_NONCESIZE]).is_ok());
+        assert!(chacha20::Nonce::from_slice(&[0u8; IETF_CHACHA_NONCESIZE + 1]).is_err());
+        assert!(xchacha20::Nonce::from_slice(&[0u8; XCHACHA_NONCESIZE]).is_ok());
+        assert!(xchacha20::Nonce::from_slice(&[0u8; XCHACHA_NONCESIZE + 1]).is_err());
+
+        // A counter increment covers exactly one block of keystream.
+        let key = chacha20::SecretKey::from_slice(&[0u8; CHACHA_KEYSIZE]).unwrap();
+        let nonce = chacha20::Nonce::from_slice(&[0u8; IETF_CHACHA_NONCESIZE]).unwrap();
+        let mut two_blocks = [0u8; 2 * CHACHA_BLOCKSIZE];
+        let mut second_block = [0u8; CHACHA_BLOCKSIZE];
+        chacha20::encrypt(
+            &key,
+            &nonce,
+            0,
+            &[0u8; 2 * CHACHA_BLOCKSIZE],
+            &mut two_blocks,
+        )
+        .unwrap();
+        chacha20::encrypt(&key, &nonce, 1, &[0u8; CHACHA_BLOCKSIZE], &mut second_block).unwrap();
+        assert_eq!(&two_blocks[CHACHA_BLOCKSIZE..], &second_block[..]);
+    }
+}
@@ -31,7 +31,8 @@
 //! # Security:
 //! - Multiple different `private_key`/`public_key` pairs can produce the same shared key. Therefore,
 //! using the resulting `SharedKey`, directly from `key_agreement()`, is not recommended. This is handled
-//! automatically in [`orion::kex`].
+//! automatically in [`orion::kex`]. Otherwise, [`derive_session_key()`] can be used to derive a
+//! key for the AEADs with HKDF-SHA512.
 //! - To securely generate a strong key, use [`PrivateKey::generate()`].
 //!
 //! # Recommendation:
@@ -61,6 +62,7 @@
 //! ```
 //! [`PrivateKey::generate()`]: crate::hazardous::ecc::x25519::PrivateKey::generate
 //! [`orion::kex`]: crate::kex
+//! [`derive_session_key()`]: crate::hazardous::ecc::x25519::derive_session_key
 
 use crate::errors::UnknownCryptoError;
 use crate::util::secure_cmp;
@@ -93,7 +95,7 @@ const LOW_ORDER_POINT_RESULT: [u8; 32] = [0u8; 32];
 
 #[derive(Clone, Copy)]
 /// Represent an element in the curve field.
-struct FieldElement(fiat_25519_tight_field_element);
+pub(crate) struct FieldElement(fiat_25519_tight_field_element);
 
 impl Eq for FieldElement {}
 
@@ -160,21 +162,21 @@ impl Sub for FieldElement {
 
 impl FieldElement {
     /// Create a `FieldElement` that is `0`.
-    fn zero() -> Self {
+    pub(crate) fn zero() -> Self {
         Self(fiat_25519_tight_field_element([
             0u64, 0u64, 0u64, 0u64, 0u64,
         ]))
     }
 
     /// Create a `FieldElement` that is `1`.
-    fn one() -> Self {
+    pub(crate) fn one() -> Self {
         Self(fiat_25519_tight_field_element([
             1u64, 0u64, 0u64, 0u64, 0u64,
         ]))
     }
 
     /// Serialize the `FieldElement` as a byte-array.
-    fn as_bytes(&self) -> [u8; 32] {
+    pub(crate) fn as_bytes(&self) -> [u8; 32] {
         // The function fiat_25519_to_bytes serializes a field element to bytes in little-endian order.
         use fiat_curve25519_u64::fiat_25519_to_bytes;
 
@@ -187,7 +189,7 @@ impl FieldElement {
     /// Deserialize the `FieldElement` from a byte-array in little-endian.
     ///
     /// Masks the MSB in the final byte of the input bytes.
-    fn from_bytes(bytes: &[u8; 32]) -> Self {
+    pub(crate) fn from_bytes(bytes: &[u8; 32]) -> Self {
         // The function fiat_25519_from_bytes deserializes a field element from bytes in little-endian order
         use fiat_curve25519_u64::fiat_25519_from_bytes;
 
@@ -203,7 +205,7 @@ impl FieldElement {
     }
 
     /// A conditional-swap operation.
-    fn conditional_swap(swap: u8, a: &mut Self, b: &mut Self) {
+    pub(crate) fn conditional_swap(swap: u8, a: &mut Self, b: &mut Self) {
         // The function fiat_25519_selectznz is a multi-limb conditional select.
         use fiat_curve25519_u64::fiat_25519_selectznz;
 
@@ -218,7 +220,7 @@ impl FieldElement {
     }
 
     /// Square the `FieldElement` and reduce the result.
-    fn square(&self) -> Self {
+    pub(crate) fn square(&self) -> Self {
         let mut self_relaxed = fiat_25519_loose_field_element([0u64; 5]);
         let mut ret = fiat_25519_tight_field_element([0u64; 5]);
 
@@ -242,7 +244,7 @@ impl FieldElement {
     /// Compute the multiplicative inverse of the `FieldElement`.
     ///
     /// Ref: https://github.com/golang/crypto/blob/0c34fe9e7dc2486962ef9867e3edb3503537209f/curve25519/curve25519_generic.go#L718
-    fn invert(&mut self) {
+    pub(crate) fn invert(&mut self) {
         let mut t0 = self.square();
         let mut t1 = t0.square();
         t1 = t1.square();
@@ -628,12 +630,77 @@ pub fn key_agreement(
     Ok(SharedKey::from(field_element))
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Compute the `PublicKey` corresponding to `private_key`.
+///
+/// This is the same as `PublicKey::try_from(private_key)`.
+pub fn public_from_private(private_key: &PrivateKey) -> Result<PublicKey, UnknownCryptoError> {
+    PublicKey::try_from(private_key)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Derive a key for ChaCha20-Poly1305 and XChaCha20-Poly1305 from `shared_key`, using
+/// HKDF-SHA512 with `salt` and `info`.
+///
+/// The output of [`key_agreement()`] should never be used as a key directly.
+pub fn derive_session_key(
+    shared_key: &SharedKey,
+    salt: &[u8],
+    info: Option<&[u8]>,
+) -> Result<crate::hazardous::aead::chacha20poly1305::SecretKey, UnknownCryptoError> {
+    use crate::hazardous::aead::chacha20poly1305::SecretKey;
+    use zeroize::Zeroize;
+
+    let mut okm = [0u8; 32];
+    crate::hazardous::kdf::hkdf::sha512::derive_key(
+        salt,
+        shared_key.unprotected_as_bytes(),
+        info,
+        &mut okm,
+    )?;
+    let session_key = SecretKey::from(okm);
+    okm.zeroize();
+
+    Ok(session_key)
+}
+
 #[cfg(test)]
 mod public {
     use crate::hazardous::ecc::x25519::{
-        key_agreement, PrivateKey, PublicKey, SharedKey, BASEPOINT,
+        derive_session_key, key_agreement, public_from_private, PrivateKey, PublicKey, SharedKey,
+        BASEPOINT,
     };
 
+    #[test]
+    fn test_derive_session_key() {
+        // Shared key from RFC 7748, section 6.1.
+        let mut shared = [0u8; 32];
+        hex::decode_to_slice(
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742",
+            &mut shared,
+        )
+        .unwrap();
+        let shared = SharedKey::from(shared);
+
+        let mut expected = [0u8; 32];
+        hex::decode_to_slice(
+            "4053703e97b845aa98aae156af22bad1d9de732c9909db26be91ce620173f5ab",
+            &mut expected,
+        )
+        .unwrap();
+        let session_key =
+            derive_session_key(&shared, b"orion x25519 salt", Some(b"orion session key")).unwrap();
+        assert_eq!(session_key.unprotected_as_bytes(), expected);
+
+        hex::decode_to_slice(
+            "d9fbf186ac2aa89816a6a60295924ddd5735edfb098cea7a9d0f3ecc67e7b713",
+            &mut expected,
+        )
+        .unwrap();
+        let session_key = derive_session_key(&shared, &[], None).unwrap();
+        assert_eq!(session_key.unprotected_as_bytes(), expected);
+    }
+
     #[test]
     fn test_public_key_ignores_highbit() {
         let u = [0u8; 32];
@@ -790,6 +857,10 @@ mod public {
             key_agreement(&PrivateKey::from(alice_priv), &PublicKey::from(BASEPOINT)).unwrap(),
             PublicKey::from(alice_pub).to_bytes().as_ref()
         );
+        assert_eq!(
+            public_from_private(&PrivateKey::from(alice_priv)).unwrap(),
+            PublicKey::from(alice_pub)
+        );
 
         hex::decode_to_slice(
             "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
@@ -805,6 +876,10 @@ mod public {
             key_agreement(&PrivateKey::from(bob_priv), &PublicKey::from(BASEPOINT)).unwrap(),
             PublicKey::from(bob_pub).to_bytes().as_ref()
         );
+        assert_eq!(
+            public_from_private(&PrivateKey::from(bob_priv)).unwrap(),
+            PublicKey::from(bob_pub)
+        );
 
         hex::decode_to_slice(
             "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742",
@@ -28,3 +28,100 @@ pub mod sha3;
 
 /// BLAKE2 hash functions.
 pub mod blake2;
+
+use crate::errors::UnknownCryptoError;
+
+/// A streaming hash function, for code that is generic over the hash primitive.
+///
+/// # Example:
+/// ```rust
+/// use orion::errors::UnknownCryptoError;
+/// use orion::hazardous::hash::{sha2, Hash};
+///
+/// fn hash_chunks<H: Hash>(chunks: &[&[u8]]) -> Result<H::Digest, UnknownCryptoError> {
+///     let mut state = H::new();
+///     for chunk in chunks {
+///         state.update(chunk)?;
+///     }
+///
+///     state.finalize()
+/// }
+///
+/// let digest = hash_chunks::<sha2::sha384::Sha384>(&[b"Some ", b"data."])?;
+/// assert_eq!(digest.as_ref().len(), sha2::sha384::SHA384_OUTSIZE);
+/// assert_eq!(digest, sha2::sha384::Sha384::digest(b"Some data.")?);
+/// # Ok::<(), UnknownCryptoError>(())
+/// ```
+pub trait Hash: Sized {
+    /// The size of the digest in bytes.
+    const OUTSIZE: usize;
+
+    /// The digest type returned when finalizing.
+    type Digest: AsRef<[u8]> + PartialEq;
+
+    /// Initialize a new state.
+    fn new() -> Self;
+
+    /// Update state with `data`. This can be called multiple times.
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError>;
+
+    /// Return the digest of all data passed to [`Self::update()`].
+    fn finalize(&mut self) -> Result<Self::Digest, UnknownCryptoError>;
+
+    /// Reset to [`Self::new()`] state.
+    fn reset(&mut self);
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Calculate a digest of some `data`.
+    fn digest(data: &[u8]) -> Result<Self::Digest, UnknownCryptoError> {
+        let mut state = Self::new();
+        state.update(data)?;
+        state.finalize()
+    }
+}
+
+#[cfg(test)]
+mod test_hash_trait {
+    use super::*;
+
+    /// Run the same checks on any `Hash`, comparing against its own one-shot `expected`.
+    fn check_hash<H: Hash>(expected: &H::Digest) {
+        let data = [0u8; 300];
+        assert_eq!(expected.as_ref().len(), H::OUTSIZE);
+
+        let mut state = H::new();
+        for chunk in data.chunks(7) {
+            state.update(chunk).unwrap();
+        }
+        let digest = state.finalize().unwrap();
+        assert_eq!(digest.as_ref().len(), H::OUTSIZE);
+        assert!(&digest == expected);
+        assert!(state.update(&data).is_err());
+        assert!(state.finalize().is_err());
+
+        state.reset();
+        state.update(&data).unwrap();
+        assert!(&state.finalize().unwrap() == expected);
+
+        assert!(&H::digest(&data).unwrap() == expected);
+        assert!(&H::digest(&data[1..]).unwrap() != expected);
+    }
+
+    #[test]
+    fn test_sha256() {
+        let expected = sha2::sha256::Sha256::digest(&[0u8; 300]).unwrap();
+        check_hash::<sha2::sha256::Sha256>(&expected);
+    }
+
+    #[test]
+    fn test_sha384() {
+        let expected = sha2::sha384::Sha384::digest(&[0u8; 300]).unwrap();
+        check_hash::<sha2::sha384::Sha384>(&expected);
+    }
+
+    #[test]
+    fn test_sha512() {
+        let expected = sha2::sha512::Sha512::digest(&[0u8; 300]).unwrap();
+        check_hash::<sha2::sha512::Sha512>(&expected);
+    }
+}
@@ -145,11 +145,7 @@ pub(crate) mod sha2_core {
         > Drop for State<W, T, BLOCKSIZE, OUTSIZE, N_CONSTS>
     {
         fn drop(&mut self) {
-            self.working_state.iter_mut().zeroize();
-            self.buffer.iter_mut().zeroize();
-            self.message_len.iter_mut().zeroize();
-            self.leftover.zeroize();
-            self.is_finalized.zeroize();
+            self._wipe();
         }
     }
 
@@ -392,6 +388,73 @@ pub(crate) mod sha2_core {
             Ok(())
         }
 
+        /// The size of a state serialized with `_export()`.
+        ///
+        /// Layout: working state (8 words), buffer (BLOCKSIZE bytes), leftover (u64 BE),
+        /// message length (2 words) and a finalization flag (1 byte).
+        pub(crate) const EXPORT_SIZE: usize =
+            (10 * size_of::<W>()) + BLOCKSIZE + size_of::<u64>() + 1;
+
+        /// Serialize the state into `dest`.
+        pub(crate) fn _export(&self, dest: &mut [u8]) -> Result<(), UnknownCryptoError> {
+            if dest.len() != Self::EXPORT_SIZE {
+                return Err(UnknownCryptoError);
+            }
+
+            let (working_state, rest) = dest.split_at_mut(8 * W::size_of());
+            W::as_be_bytes(&self.working_state, working_state);
+            let (buffer, rest) = rest.split_at_mut(BLOCKSIZE);
+            buffer.copy_from_slice(&self.buffer);
+            let (leftover, rest) = rest.split_at_mut(size_of::<u64>());
+            leftover.copy_from_slice(&(self.leftover as u64).to_be_bytes());
+            let (message_len, rest) = rest.split_at_mut(2 * W::size_of());
+            W::as_be_bytes(&self.message_len, message_len);
+            rest[0] = u8::from(self.is_finalized);
+
+            Ok(())
+        }
+
+        /// Deserialize a state previously serialized with `_export()`.
+        ///
+        /// A state that has been finalized is rejected.
+        pub(crate) fn _import(src: &[u8]) -> Result<Self, UnknownCryptoError> {
+            if src.len() != Self::EXPORT_SIZE {
+                return Err(UnknownCryptoError);
+            }
+
+            let mut state = Self::_new();
+
+            let (working_state, rest) = src.split_at(8 * W::size_of());
+            W::from_be_bytes(working_state, &mut state.working_state);
+            let (buffer, rest) = rest.split_at(BLOCKSIZE);
+            state.buffer.copy_from_slice(buffer);
+            let (leftover, rest) = rest.split_at(size_of::<u64>());
+            // NOTE: `try_into()` cannot fail, the length is fixed by `split_at()`.
+            let leftover = u64::from_be_bytes(leftover.try_into().unwrap());
+            if leftover >= BLOCKSIZE as u64 {
+                return Err(UnknownCryptoError);
+            }
+            state.leftover = leftover as usize;
+            let (message_len, rest) = rest.split_at(2 * W::size_of());
+            W::from_be_bytes(message_len, &mut state.message_len);
+            if rest[0] != 0 {
+                // Either finalized or not a valid boolean.
+                return Err(UnknownCryptoError);
+            }
+
+            Ok(state)
+        }
+
+        /// Zeroize all data held by the state. This is what `Drop` does, so a state
+        /// that is dropped before being finalized leaves nothing behind.
+        pub(crate) fn _wipe(&mut self) {
+            self.working_state.iter_mut().zeroize();
+            self.buffer.iter_mut().zeroize();
+            self.message_len.iter_mut().zeroize();
+            self.leftover.zeroize();
+            self.is_finalized.zeroize();
+        }
+
         #[cfg(test)]
         /// Compare two Sha2 state objects to check if their fields
         /// are the same.
@@ -720,6 +783,9 @@ mod test_word {
     fn equiv_sizeof() {
         assert_eq!(WordU32::size_of(), size_of::<u32>());
         assert_eq!(WordU64::size_of(), size_of::<u64>());
+        // `State::EXPORT_SIZE` relies on the word types having no padding.
+        assert_eq!(WordU32::size_of(), size_of::<WordU32>());
+        assert_eq!(WordU64::size_of(), size_of::<WordU64>());
     }
 
     #[test]
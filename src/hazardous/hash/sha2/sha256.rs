@@ -195,6 +195,28 @@ impl Sha256 {
     }
 }
 
+impl crate::hazardous::hash::Hash for Sha256 {
+    const OUTSIZE: usize = SHA256_OUTSIZE;
+
+    type Digest = Digest;
+
+    fn new() -> Self {
+        Sha256::new()
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        Sha256::update(self, data)
+    }
+
+    fn finalize(&mut self) -> Result<Digest, UnknownCryptoError> {
+        Sha256::finalize(self)
+    }
+
+    fn reset(&mut self) {
+        Sha256::reset(self)
+    }
+}
+
 impl crate::hazardous::mac::hmac::HmacHashFunction for Sha256 {
     /// The blocksize of the hash function.
     const _BLOCKSIZE: usize = SHA256_BLOCKSIZE;
@@ -261,8 +283,7 @@ impl io::Write for Sha256 {
     /// variant when it returns an error. Additionally, this will always contain Orion's
     /// [`UnknownCryptoError`] type.
     fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
-        self.update(bytes)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.update(bytes).map_err(io::Error::other)?;
         Ok(bytes.len())
     }
 
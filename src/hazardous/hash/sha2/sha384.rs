@@ -175,6 +175,28 @@ impl Sha384 {
     }
 }
 
+impl crate::hazardous::hash::Hash for Sha384 {
+    const OUTSIZE: usize = SHA384_OUTSIZE;
+
+    type Digest = Digest;
+
+    fn new() -> Self {
+        Sha384::new()
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        Sha384::update(self, data)
+    }
+
+    fn finalize(&mut self) -> Result<Digest, UnknownCryptoError> {
+        Sha384::finalize(self)
+    }
+
+    fn reset(&mut self) {
+        Sha384::reset(self)
+    }
+}
+
 impl crate::hazardous::mac::hmac::HmacHashFunction for Sha384 {
     /// The blocksize of the hash function.
     const _BLOCKSIZE: usize = SHA384_BLOCKSIZE;
@@ -241,8 +263,7 @@ impl io::Write for Sha384 {
     /// variant when it returns an error. Additionally, this will always contain Orion's
     /// [`UnknownCryptoError`] type.
     fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
-        self.update(bytes)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.update(bytes).map_err(io::Error::other)?;
         Ok(bytes.len())
     }
 
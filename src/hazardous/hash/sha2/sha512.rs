@@ -34,7 +34,11 @@
 //! - More than 2*(2^64-1) __bits__ of data are hashed.
 //!
 //! # Security:
-//! - SHA512 is vulnerable to length extension attacks.
+//! - SHA512 is vulnerable to length extension attacks. A digest of a secret
+//!   key and a message, such as `SHA512(key || message)`, must therefore never
+//!   be used to authenticate the message. Use [HMAC-SHA512] for this, which
+//!   returns a [`Tag`] that is verified in constant time. A [`Digest`] cannot be
+//!   used where a [`Tag`] is expected.
 //!
 //! # Recommendation:
 //! - It is recommended to use [BLAKE2b] when possible.
@@ -58,6 +62,9 @@
 //! [`reset()`]: sha512::Sha512::reset
 //! [`finalize()`]: sha512::Sha512::finalize
 //! [BLAKE2b]: super::blake2::blake2b
+//! [HMAC-SHA512]: crate::hazardous::mac::hmac::sha512::HmacSha512
+//! [`Tag`]: crate::hazardous::mac::hmac::sha512::Tag
+//! [`Digest`]: sha512::Digest
 
 use crate::errors::UnknownCryptoError;
 
@@ -70,6 +77,21 @@ construct_public! {
     /// # Errors:
     /// An error will be returned if:
     /// - `slice` is not 64 bytes.
+    ///
+    /// # Note:
+    /// A `Digest` is not a MAC, and cannot be used in place of an HMAC [`Tag`]:
+    /// ```rust,compile_fail
+    /// use orion::hazardous::hash::sha2::sha512::Sha512;
+    /// use orion::hazardous::mac::hmac::sha512::{HmacSha512, SecretKey};
+    ///
+    /// let key = SecretKey::from_slice(&[0u8; 128])?;
+    /// let digest = Sha512::digest(b"data")?;
+    ///
+    /// let _ = HmacSha512::verify(&digest, &key, b"data");
+    /// # Ok::<(), orion::errors::UnknownCryptoError>(())
+    /// ```
+    ///
+    /// [`Tag`]: crate::hazardous::mac::hmac::sha512::Tag
     (Digest, test_digest, SHA512_OUTSIZE, SHA512_OUTSIZE)
 }
 
@@ -84,6 +106,9 @@ pub const SHA512_BLOCKSIZE: usize = 128;
 pub const SHA512_OUTSIZE: usize = 64;
 /// The number of constants for the hash function SHA512.
 const N_CONSTS: usize = 80;
+/// The size of a SHA512 state serialized with `_export()`.
+pub(crate) const SHA512_EXPORT_SIZE: usize =
+    State::<WordU64, V512, SHA512_BLOCKSIZE, SHA512_OUTSIZE, N_CONSTS>::EXPORT_SIZE;
 
 #[derive(Clone)]
 pub(crate) struct V512;
@@ -198,6 +223,28 @@ impl Sha512 {
     }
 }
 
+impl crate::hazardous::hash::Hash for Sha512 {
+    const OUTSIZE: usize = SHA512_OUTSIZE;
+
+    type Digest = Digest;
+
+    fn new() -> Self {
+        Sha512::new()
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        Sha512::update(self, data)
+    }
+
+    fn finalize(&mut self) -> Result<Digest, UnknownCryptoError> {
+        Sha512::finalize(self)
+    }
+
+    fn reset(&mut self) {
+        Sha512::reset(self)
+    }
+}
+
 impl crate::hazardous::mac::hmac::HmacHashFunction for Sha512 {
     /// The blocksize of the hash function.
     const _BLOCKSIZE: usize = SHA512_BLOCKSIZE;
@@ -264,8 +311,7 @@ impl io::Write for Sha512 {
     /// variant when it returns an error. Additionally, this will always contain Orion's
     /// [`UnknownCryptoError`] type.
     fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
-        self.update(bytes)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.update(bytes).map_err(io::Error::other)?;
         Ok(bytes.len())
     }
 
@@ -114,8 +114,7 @@ impl io::Write for Sha3_224 {
     /// variant when it returns an error. Additionally, this will always contain Orion's
     /// [`UnknownCryptoError`] type.
     fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
-        self.update(bytes)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.update(bytes).map_err(io::Error::other)?;
         Ok(bytes.len())
     }
 
@@ -1481,8 +1481,7 @@ mod private {
                 259, 49, 1009, 1012, 828, 416, 1015, 878, 802, 213, 230, 567, 392,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1490,7 +1489,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -1572,8 +1570,7 @@ mod private {
                 1719, 1171, 1881, 2031,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1581,7 +1578,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -1666,8 +1662,7 @@ mod private {
                 3021, 1483, 2941, 2676, 422, 1775,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1675,7 +1670,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -1760,8 +1754,7 @@ mod private {
                 1163, 1752, 3677, 1553, 2352, 3650, 1758, 1756, 3893, 3566,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1769,7 +1762,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -1851,8 +1843,7 @@ mod private {
                 4079, 1009, 184, 91, 3936, 1790, 3997, 719, 1012, 3540, 3910, 848, 980, 726,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1860,7 +1851,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -1944,8 +1934,7 @@ mod private {
                 2035, 2712, 3411, 1658,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -1953,7 +1942,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2038,8 +2026,7 @@ mod private {
                 2313, 430, 3057, 2824, 2721, 1792, 1944, 3026, 3907, 2236, 978, 2126,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2047,7 +2034,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2132,8 +2118,7 @@ mod private {
                 4010, 2728, 1454, 2225, 3648, 3476, 3811, 3592, 3339, 3548, 3973, 3979, 4061,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2141,7 +2126,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2224,8 +2208,7 @@ mod private {
                 402,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2233,7 +2216,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2317,8 +2299,7 @@ mod private {
                 1543, 2967,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2326,7 +2307,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2411,8 +2391,7 @@ mod private {
                 3171, 349, 1296, 1876, 2988, 1789, 3580, 1396,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2420,7 +2399,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
 
             let offset = 0;
@@ -2505,8 +2483,7 @@ mod private {
                 688, 1143, 599, 3050, 3856, 3094, 2281, 356, 3916, 3918, 3209, 349, 1317,
             ];
 
-            let mut idx = offset;
-            for expected in expected_ref_idx.iter() {
+            for (idx, expected) in (offset..).zip(expected_ref_idx.iter()) {
                 // Mimic offset..segment_length runs with idx
                 assert_eq!(
                     *expected,
@@ -2514,7 +2491,6 @@ mod private {
                     "Invalid at {}",
                     idx
                 );
-                idx += 1;
             }
         }
     }
@@ -27,11 +27,15 @@
 //!   then it's an empty string.
 //! - `dst_out`: Destination buffer for the derived key. The length of the
 //!   derived key is implied by the length of `okm_out`.
+//! - `outputs`: Pairs of `info` and destination buffer for [`expand_multi()`].
+//! - `length`: The length of the derived key returned by [`derive_key_vec()`].
 //!
 //! # Errors:
 //! An error will be returned if:
-//! - The length of `dst_out` is less than 1.
-//! - The length of `dst_out` is greater than 255 * SHA(256/384/512)_OUTSIZE.
+//! - The length of `dst_out`, or of any destination buffer in `outputs`, is less than 1.
+//! - The length of `dst_out`, or of any destination buffer in `outputs`, is greater
+//!   than 255 * SHA(256/384/512)_OUTSIZE.
+//! - `length` is less than 1 or greater than 255 * SHA(256/384/512)_OUTSIZE.
 //!
 //! # Security:
 //! - Salts should always be generated using a CSPRNG.
@@ -55,10 +59,17 @@
 //! # Ok::<(), orion::errors::UnknownCryptoError>(())
 //! ```
 //! [`secure_rand_bytes()`]: crate::util::secure_rand_bytes
+//! [`expand_multi()`]: crate::hazardous::kdf::hkdf::sha512::expand_multi
+//! [`derive_key_vec()`]: crate::hazardous::kdf::hkdf::sha512::derive_key_vec
 
 use crate::errors::UnknownCryptoError;
 use crate::hazardous::mac::hmac;
 use zeroize::Zeroize;
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
+use zeroize::Zeroizing;
+
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::{vec, vec::Vec};
 
 /// The HKDF extract step.
 ///
@@ -82,9 +93,22 @@ where
     Ok(dest)
 }
 
-/// The HKDF expand step.
-fn _expand<Hmac, const OUTSIZE: usize>(
-    prk: &[u8],
+/// Check that `dest` is a valid length of output for the expand step.
+fn _check_expand_len<Hmac>(dest: &[u8]) -> Result<(), UnknownCryptoError>
+where
+    Hmac: hmac::HmacFunction,
+{
+    if dest.is_empty() || dest.len() > 255 * Hmac::HASH_FUNC_OUTSIZE {
+        return Err(UnknownCryptoError);
+    }
+
+    Ok(())
+}
+
+/// The HKDF expand step, using a `ctx` that has been keyed with the PRK and is in its
+/// initial state. `ctx` is reset afterwards, so it can be used for further expansions.
+fn _expand_with_ctx<Hmac, const OUTSIZE: usize>(
+    ctx: &mut Hmac,
     info: Option<&[u8]>,
     dest: &mut [u8],
 ) -> Result<(), UnknownCryptoError>
@@ -92,13 +116,9 @@ where
     Hmac: hmac::HmacFunction,
 {
     debug_assert_eq!(OUTSIZE, Hmac::HASH_FUNC_OUTSIZE);
-    debug_assert_eq!(prk.len(), Hmac::HASH_FUNC_OUTSIZE);
-    if dest.is_empty() || dest.len() > 255 * Hmac::HASH_FUNC_OUTSIZE {
-        return Err(UnknownCryptoError);
-    }
+    _check_expand_len::<Hmac>(dest)?;
 
     let optional_info = info.unwrap_or(&[0u8; 0]);
-    let mut ctx = Hmac::_new(prk)?;
 
     // We require a temporary buffer in case the requested bytes
     // to derive are lower than the HMAC functions output size.
@@ -126,11 +146,49 @@ where
         };
     }
 
+    ctx._reset();
     tmp.iter_mut().zeroize();
 
     Ok(())
 }
 
+/// The HKDF expand step.
+fn _expand<Hmac, const OUTSIZE: usize>(
+    prk: &[u8],
+    info: Option<&[u8]>,
+    dest: &mut [u8],
+) -> Result<(), UnknownCryptoError>
+where
+    Hmac: hmac::HmacFunction,
+{
+    debug_assert_eq!(prk.len(), Hmac::HASH_FUNC_OUTSIZE);
+    _check_expand_len::<Hmac>(dest)?;
+
+    _expand_with_ctx::<Hmac, { OUTSIZE }>(&mut Hmac::_new(prk)?, info, dest)
+}
+
+/// The HKDF expand step for several outputs, keying HMAC with the PRK only once.
+/// All output lengths are checked before any output is written.
+fn _expand_multi<Hmac, const OUTSIZE: usize>(
+    prk: &[u8],
+    outputs: &mut [(Option<&[u8]>, &mut [u8])],
+) -> Result<(), UnknownCryptoError>
+where
+    Hmac: hmac::HmacFunction,
+{
+    debug_assert_eq!(prk.len(), Hmac::HASH_FUNC_OUTSIZE);
+    for (_, dest) in outputs.iter() {
+        _check_expand_len::<Hmac>(dest)?;
+    }
+
+    let mut ctx = Hmac::_new(prk)?;
+    for (info, dest) in outputs.iter_mut() {
+        _expand_with_ctx::<Hmac, { OUTSIZE }>(&mut ctx, *info, dest)?;
+    }
+
+    Ok(())
+}
+
 /// Combine `extract` and `expand` to return a derived key.
 ///
 /// NOTE: See comment about const param at _extract function.
@@ -146,6 +204,30 @@ where
     _expand::<Hmac, { OUTSIZE }>(&_extract::<Hmac, { OUTSIZE }>(salt, ikm)?, info, dest)
 }
 
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
+/// Combine `extract` and `expand` to return a derived key of `length` bytes.
+///
+/// NOTE: See comment about const param at _extract function.
+fn _derive_key_vec<Hmac, const OUTSIZE: usize>(
+    salt: &[u8],
+    ikm: &[u8],
+    info: Option<&[u8]>,
+    length: usize,
+) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError>
+where
+    Hmac: hmac::HmacFunction,
+{
+    // Checked before allocating, even though `_expand()` would also reject it.
+    if length == 0 || length > 255 * Hmac::HASH_FUNC_OUTSIZE {
+        return Err(UnknownCryptoError);
+    }
+
+    let mut dest = Zeroizing::new(vec![0u8; length]);
+    _derive_key::<Hmac, { OUTSIZE }>(salt, ikm, info, &mut dest)?;
+
+    Ok(dest)
+}
+
 /// HKDF-HMAC-SHA256 (HMAC-based Extract-and-Expand Key Derivation Function) as specified in the [RFC 5869](https://tools.ietf.org/html/rfc5869).
 pub mod sha256 {
     use super::*;
@@ -175,6 +257,20 @@ pub mod sha256 {
         )
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// The HKDF expand step for several outputs at once. Each entry of `outputs` is
+    /// filled as [`expand()`] would fill it with the given `info`, but HMAC is only
+    /// keyed with `prk` once.
+    pub fn expand_multi(
+        prk: &Tag,
+        outputs: &mut [(Option<&[u8]>, &mut [u8])],
+    ) -> Result<(), UnknownCryptoError> {
+        _expand_multi::<hmac::sha256::HmacSha256, { SHA256_OUTSIZE }>(
+            prk.unprotected_as_bytes(),
+            outputs,
+        )
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Combine `extract` and `expand` to return a derived key.
     pub fn derive_key(
@@ -186,6 +282,20 @@ pub mod sha256 {
         _derive_key::<hmac::sha256::HmacSha256, { SHA256_OUTSIZE }>(salt, ikm, info, dst_out)
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+    /// Combine `extract` and `expand` to return a derived key of `length` bytes.
+    /// The key is zeroized when dropped.
+    pub fn derive_key_vec(
+        salt: &[u8],
+        ikm: &[u8],
+        info: Option<&[u8]>,
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+        _derive_key_vec::<hmac::sha256::HmacSha256, { SHA256_OUTSIZE }>(salt, ikm, info, length)
+    }
+
     #[cfg(test)]
     #[cfg(feature = "safe_api")]
     // Mark safe_api because currently it only contains proptests.
@@ -251,6 +361,20 @@ pub mod sha384 {
         )
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// The HKDF expand step for several outputs at once. Each entry of `outputs` is
+    /// filled as [`expand()`] would fill it with the given `info`, but HMAC is only
+    /// keyed with `prk` once.
+    pub fn expand_multi(
+        prk: &Tag,
+        outputs: &mut [(Option<&[u8]>, &mut [u8])],
+    ) -> Result<(), UnknownCryptoError> {
+        _expand_multi::<hmac::sha384::HmacSha384, { SHA384_OUTSIZE }>(
+            prk.unprotected_as_bytes(),
+            outputs,
+        )
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Combine `extract` and `expand` to return a derived key.
     pub fn derive_key(
@@ -262,6 +386,20 @@ pub mod sha384 {
         _derive_key::<hmac::sha384::HmacSha384, { SHA384_OUTSIZE }>(salt, ikm, info, dst_out)
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+    /// Combine `extract` and `expand` to return a derived key of `length` bytes.
+    /// The key is zeroized when dropped.
+    pub fn derive_key_vec(
+        salt: &[u8],
+        ikm: &[u8],
+        info: Option<&[u8]>,
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+        _derive_key_vec::<hmac::sha384::HmacSha384, { SHA384_OUTSIZE }>(salt, ikm, info, length)
+    }
+
     #[cfg(test)]
     #[cfg(feature = "safe_api")]
     // Mark safe_api because currently it only contains proptests.
@@ -327,6 +465,20 @@ pub mod sha512 {
         )
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// The HKDF expand step for several outputs at once. Each entry of `outputs` is
+    /// filled as [`expand()`] would fill it with the given `info`, but HMAC is only
+    /// keyed with `prk` once.
+    pub fn expand_multi(
+        prk: &Tag,
+        outputs: &mut [(Option<&[u8]>, &mut [u8])],
+    ) -> Result<(), UnknownCryptoError> {
+        _expand_multi::<hmac::sha512::HmacSha512, { SHA512_OUTSIZE }>(
+            prk.unprotected_as_bytes(),
+            outputs,
+        )
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Combine `extract` and `expand` to return a derived key.
     pub fn derive_key(
@@ -338,6 +490,20 @@ pub mod sha512 {
         _derive_key::<hmac::sha512::HmacSha512, { SHA512_OUTSIZE }>(salt, ikm, info, dst_out)
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+    /// Combine `extract` and `expand` to return a derived key of `length` bytes.
+    /// The key is zeroized when dropped.
+    pub fn derive_key_vec(
+        salt: &[u8],
+        ikm: &[u8],
+        info: Option<&[u8]>,
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+        _derive_key_vec::<hmac::sha512::HmacSha512, { SHA512_OUTSIZE }>(salt, ikm, info, length)
+    }
+
     #[cfg(test)]
     #[cfg(feature = "safe_api")]
     // Mark safe_api because currently it only contains proptests.
@@ -418,6 +584,44 @@ mod public {
         assert!(sha512::derive_key(b"", b"", Some(b""), &mut okm_out).is_ok());
     }
 
+    #[test]
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    fn hkdf_derive_key_vec_same_as_derive_key() {
+        let ikm = b"0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b";
+        let salt = b"000102030405060708090a0b0c";
+        let info = b"f0f1f2f3f4f5f6f7f8f9";
+        let mut okm_out = [0u8; 255 * SHA512_OUTSIZE];
+
+        // A 32-byte key and a 16-byte IV in one blob.
+        for length in [1, 32 + 16, 255 * SHA256_OUTSIZE] {
+            sha256::derive_key(salt, ikm, Some(info), &mut okm_out[..length]).unwrap();
+            let okm = sha256::derive_key_vec(salt, ikm, Some(info), length).unwrap();
+            assert_eq!(&okm[..], &okm_out[..length]);
+        }
+        for length in [1, 32 + 16, 255 * SHA384_OUTSIZE] {
+            sha384::derive_key(salt, ikm, Some(info), &mut okm_out[..length]).unwrap();
+            let okm = sha384::derive_key_vec(salt, ikm, Some(info), length).unwrap();
+            assert_eq!(&okm[..], &okm_out[..length]);
+        }
+        for length in [1, 32 + 16, 255 * SHA512_OUTSIZE] {
+            sha512::derive_key(salt, ikm, Some(info), &mut okm_out[..length]).unwrap();
+            let okm = sha512::derive_key_vec(salt, ikm, Some(info), length).unwrap();
+            assert_eq!(&okm[..], &okm_out[..length]);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    fn hkdf_derive_key_vec_bad_length_err() {
+        assert!(sha256::derive_key_vec(b"", b"", None, 0).is_err());
+        assert!(sha256::derive_key_vec(b"", b"", None, 255 * SHA256_OUTSIZE + 1).is_err());
+        assert!(sha384::derive_key_vec(b"", b"", None, 0).is_err());
+        assert!(sha384::derive_key_vec(b"", b"", None, 255 * SHA384_OUTSIZE + 1).is_err());
+        assert!(sha512::derive_key_vec(b"", b"", None, 0).is_err());
+        assert!(sha512::derive_key_vec(b"", b"", None, 255 * SHA512_OUTSIZE + 1).is_err());
+        assert!(sha512::derive_key_vec(b"", b"", None, usize::MAX).is_err());
+    }
+
     #[test]
     fn hkdf_zero_length_err() {
         let mut okm_out = [0u8; 0];
@@ -519,4 +723,56 @@ mod public {
         sha512::derive_key(salt, ikm, Some(info), &mut okm_out_verify).unwrap();
         assert_ne!(okm_out[..], okm_out_verify[..]);
     }
+
+    #[test]
+    fn hkdf_expand_multi_same_as_expand() {
+        let prk256 = sha256::extract(b"salt", b"ikm").unwrap();
+        let prk384 = sha384::extract(b"salt", b"ikm").unwrap();
+        let prk512 = sha512::extract(b"salt", b"ikm").unwrap();
+
+        let infos: [Option<&[u8]>; 4] = [Some(b"encryption"), None, Some(b""), Some(b"mac")];
+        let lens = [32, 1, 255 * SHA256_OUTSIZE, 100];
+
+        let mut expected = [[0u8; 255 * SHA256_OUTSIZE]; 4];
+        let mut actual = [[0u8; 255 * SHA256_OUTSIZE]; 4];
+
+        macro_rules! check {
+            ($module:ident, $prk:expr) => {
+                for ((dest, info), len) in expected.iter_mut().zip(infos.iter()).zip(lens.iter()) {
+                    $module::expand(&$prk, *info, &mut dest[..*len]).unwrap();
+                }
+                let [a, b, c, d] = &mut actual;
+                let mut outputs = [
+                    (infos[0], &mut a[..lens[0]]),
+                    (infos[1], &mut b[..lens[1]]),
+                    (infos[2], &mut c[..lens[2]]),
+                    (infos[3], &mut d[..lens[3]]),
+                ];
+                $module::expand_multi(&$prk, &mut outputs).unwrap();
+                assert_eq!(actual, expected);
+            };
+        }
+
+        check!(sha256, prk256);
+        check!(sha384, prk384);
+        check!(sha512, prk512);
+
+        // No outputs is a no-op.
+        assert!(sha512::expand_multi(&prk512, &mut []).is_ok());
+    }
+
+    #[test]
+    fn hkdf_expand_multi_invalid_length_err() {
+        let prk = sha512::extract(b"salt", b"ikm").unwrap();
+        let mut first = [0u8; 32];
+        let mut empty = [0u8; 0];
+        let mut too_long = [0u8; 255 * SHA512_OUTSIZE + 1];
+
+        let mut outputs = [(None, &mut first[..]), (None, &mut empty[..])];
+        assert!(sha512::expand_multi(&prk, &mut outputs).is_err());
+        let mut outputs = [(None, &mut first[..]), (None, &mut too_long[..])];
+        assert!(sha512::expand_multi(&prk, &mut outputs).is_err());
+        // Nothing is written when any of the outputs is invalid.
+        assert_eq!(first, [0u8; 32]);
+    }
 }
@@ -25,7 +25,9 @@
 //! - `salt`: Salt value.
 //! - `iterations`: Iteration count.
 //! - `dst_out`: Destination buffer for the derived key. The length of the
-//!   derived key is implied by the length of `dst_out`.
+//!   derived key is implied by the length of `dst_out`. Nothing is allocated on the
+//!   heap: a single HMAC state on the stack is reused for all iterations, so PBKDF2
+//!   is also available in `no_std` without `alloc`.
 //! - `expected`: The expected derived key.
 //!
 //! # Errors:
@@ -98,6 +100,8 @@ where
 
     if iterations > 1 {
         for _ in 1..iterations {
+            // The key is only padded and hashed with ipad/opad once, in `_derive_key()`.
+            // Resetting copies the prepared state instead of re-initializing HMAC.
             hmac._reset();
             hmac._update(u_step)?;
             hmac._finalize(u_step)?;
@@ -188,7 +192,7 @@ pub mod sha256 {
         /// # Panics:
         /// A panic will occur if:
         /// - Failure to generate random bytes securely.
-        (Password, Sha256, sha256::SHA256_OUTSIZE, test_pbkdf2_password, sha256::SHA256_BLOCKSIZE)
+        (Password, Sha256, sha256::SHA256_OUTSIZE, test_pbkdf2_password, sha256::SHA256_BLOCKSIZE, padded)
     }
 
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
@@ -245,7 +249,7 @@ pub mod sha384 {
         /// # Panics:
         /// A panic will occur if:
         /// - Failure to generate random bytes securely.
-        (Password, Sha384, sha384::SHA384_OUTSIZE, test_pbkdf2_password, sha384::SHA384_BLOCKSIZE)
+        (Password, Sha384, sha384::SHA384_OUTSIZE, test_pbkdf2_password, sha384::SHA384_BLOCKSIZE, padded)
     }
 
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
@@ -302,7 +306,7 @@ pub mod sha512 {
         /// # Panics:
         /// A panic will occur if:
         /// - Failure to generate random bytes securely.
-        (Password, Sha512, sha512::SHA512_OUTSIZE, test_pbkdf2_password, sha512::SHA512_BLOCKSIZE)
+        (Password, Sha512, sha512::SHA512_OUTSIZE, test_pbkdf2_password, sha512::SHA512_BLOCKSIZE, padded)
     }
 
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
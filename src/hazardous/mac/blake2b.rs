@@ -69,6 +69,8 @@
 
 use crate::errors::UnknownCryptoError;
 use crate::hazardous::hash::blake2::blake2b_core::{self, BLAKE2B_KEYSIZE, BLAKE2B_OUTSIZE};
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::string::String;
 use core::ops::DerefMut;
 use zeroize::Zeroizing;
 
@@ -132,6 +134,15 @@ impl Blake2b {
         Tag::from_slice(&tmp[..self._state.size])
     }
 
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return a BLAKE2b tag as a lowercase hex string. This finalizes the state,
+    /// the same as [`Self::finalize()`].
+    pub fn finalize_hex(&mut self) -> Result<String, UnknownCryptoError> {
+        Ok(crate::util::to_hex(self.finalize()?.unprotected_as_bytes()))
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Verify a BLAKE2b tag in constant time.
     pub fn verify(
@@ -230,6 +241,33 @@ mod public {
         }
     }
 
+    #[cfg(feature = "safe_api")]
+    mod test_finalize_hex {
+        use crate::hazardous::mac::blake2b::{Blake2b, SecretKey};
+
+        #[test]
+        fn test_finalize_hex_matches_tag() {
+            let key = SecretKey::from_slice(&[1u8; 32]).unwrap();
+            let mut state = Blake2b::new(&key, 32).unwrap();
+            state.update(b"Some message.").unwrap();
+            let tag = state.finalize().unwrap();
+
+            state.reset(&key).unwrap();
+            state.update(b"Some message.").unwrap();
+            let hex = state.finalize_hex().unwrap();
+
+            let expected: String = tag
+                .unprotected_as_bytes()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            assert_eq!(hex, expected);
+            assert_eq!(hex.len(), tag.len() * 2);
+            // The state was finalized.
+            assert!(state.finalize_hex().is_err());
+        }
+    }
+
     mod test_new {
         use crate::hazardous::mac::blake2b::{Blake2b, SecretKey};
 
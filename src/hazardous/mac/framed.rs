@@ -0,0 +1,264 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! Length-prefixed records, authenticated with HMAC-SHA512.
+//!
+//! A record is `BE32(payload.len()) || payload || tag`, where `tag` is the
+//! 64-byte `HMAC(secret_key, BE32(payload.len()) || payload)`. Records can be
+//! concatenated, and [`read_record()`] returns the input following a record so
+//! that the next one can be read from it.
+//!
+//! # Parameters:
+//! - `secret_key`: The authentication key.
+//! - `payload`: The data of a record.
+//! - `input`: One or more records, as written by [`write_record()`].
+//! - `dst_out`: Destination buffer that the record is written to.
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - `payload` is longer than `u32::MAX` bytes.
+//! - `dst_out` is shorter than the record, which is [`RECORD_OVERHEAD`] bytes
+//!   longer than `payload`.
+//! - `input` is shorter than [`RECORD_OVERHEAD`] bytes.
+//! - The length prefix of the record is greater than what remains of `input`.
+//! - The tag of the record does not match.
+//!
+//! # Security:
+//! - The length prefix is authenticated along with the payload. However, records are
+//!   authenticated individually, so reordering, dropping or replaying whole records is
+//!   not detected. Include a sequence number in the payload if this matters.
+//! - The payload is only returned if its tag is valid. The tag is compared in
+//!   constant time.
+//! - The secret key should always be generated using a CSPRNG.
+//!   [`SecretKey::generate()`] can be used for this.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::mac::{framed, hmac::sha512::SecretKey};
+//!
+//! let key = SecretKey::generate();
+//!
+//! let mut stream = [0u8; 2 * framed::RECORD_OVERHEAD + 12];
+//! let first_len = framed::write_record(&key, b"Hello", &mut stream)?;
+//! framed::write_record(&key, b"world!", &mut stream[first_len..])?;
+//!
+//! let (first, rest) = framed::read_record(&key, &stream)?;
+//! let (second, _) = framed::read_record(&key, rest)?;
+//! assert_eq!(first, b"Hello");
+//! assert_eq!(second, b"world!");
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [`SecretKey::generate()`]: crate::hazardous::mac::hmac::sha512::SecretKey::generate
+//! [`read_record()`]: crate::hazardous::mac::framed::read_record
+//! [`write_record()`]: crate::hazardous::mac::framed::write_record
+//! [`RECORD_OVERHEAD`]: crate::hazardous::mac::framed::RECORD_OVERHEAD
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::hash::sha2::sha512::SHA512_OUTSIZE;
+use crate::hazardous::mac::hmac::sha512::{HmacSha512, SecretKey, Tag};
+
+/// The size of the length prefix of a record.
+const LEN_PREFIX_SIZE: usize = size_of::<u32>();
+
+/// The number of bytes a record adds to its payload.
+pub const RECORD_OVERHEAD: usize = LEN_PREFIX_SIZE + SHA512_OUTSIZE;
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Write an authenticated record of `payload` to the start of `dst_out`, returning the
+/// length of the record.
+pub fn write_record(
+    secret_key: &SecretKey,
+    payload: &[u8],
+    dst_out: &mut [u8],
+) -> Result<usize, UnknownCryptoError> {
+    let len_prefix = u32::try_from(payload.len())
+        .map_err(|_| UnknownCryptoError)?
+        .to_be_bytes();
+    let record_len = payload
+        .len()
+        .checked_add(RECORD_OVERHEAD)
+        .ok_or(UnknownCryptoError)?;
+    if dst_out.len() < record_len {
+        return Err(UnknownCryptoError);
+    }
+
+    let mut ctx = HmacSha512::new(secret_key);
+    ctx.update(len_prefix)?;
+    ctx.update(payload)?;
+    let tag = ctx.finalize()?;
+
+    let (prefix_out, rest) = dst_out.split_at_mut(LEN_PREFIX_SIZE);
+    let (payload_out, rest) = rest.split_at_mut(payload.len());
+    prefix_out.copy_from_slice(&len_prefix);
+    payload_out.copy_from_slice(payload);
+    rest[..SHA512_OUTSIZE].copy_from_slice(tag.unprotected_as_bytes());
+
+    Ok(record_len)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Read and verify the record at the start of `input`, returning its payload and the
+/// remaining input after the record.
+pub fn read_record<'a>(
+    secret_key: &SecretKey,
+    input: &'a [u8],
+) -> Result<(&'a [u8], &'a [u8]), UnknownCryptoError> {
+    if input.len() < RECORD_OVERHEAD {
+        return Err(UnknownCryptoError);
+    }
+
+    let (len_prefix, rest) = input.split_at(LEN_PREFIX_SIZE);
+    let mut prefix = [0u8; LEN_PREFIX_SIZE];
+    prefix.copy_from_slice(len_prefix);
+    // The length is not secret, so this check does not need to be constant time.
+    let payload_len =
+        usize::try_from(u32::from_be_bytes(prefix)).map_err(|_| UnknownCryptoError)?;
+    if payload_len > rest.len() - SHA512_OUTSIZE {
+        return Err(UnknownCryptoError);
+    }
+
+    let (payload, rest) = rest.split_at(payload_len);
+    let (tag, rest) = rest.split_at(SHA512_OUTSIZE);
+    let expected = Tag::from_slice(tag)?;
+
+    let mut ctx = HmacSha512::new(secret_key);
+    ctx.update(len_prefix)?;
+    ctx.update(payload)?;
+    ctx.finalize_verify(&expected, (LEN_PREFIX_SIZE + payload_len) as u64)?;
+
+    Ok((payload, rest))
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    const KEY: [u8; 128] = [1u8; 128];
+
+    fn record(payload: &[u8]) -> ([u8; 256 + RECORD_OVERHEAD], usize) {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let mut out = [0u8; 256 + RECORD_OVERHEAD];
+        let len = write_record(&sk, payload, &mut out).unwrap();
+
+        (out, len)
+    }
+
+    #[test]
+    fn test_valid_records() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        for payload_len in [0, 1, 64, 128, 256] {
+            let payload = &[7u8; 256][..payload_len];
+            let (out, len) = record(payload);
+            assert_eq!(len, payload_len + RECORD_OVERHEAD);
+
+            let (read, rest) = read_record(&sk, &out[..len]).unwrap();
+            assert_eq!(read, payload);
+            assert!(rest.is_empty());
+
+            let (read, rest) = read_record(&sk, &out).unwrap();
+            assert_eq!(read, payload);
+            assert_eq!(rest.len(), out.len() - len);
+        }
+    }
+
+    #[test]
+    fn test_consecutive_records() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let mut stream = [0u8; 3 * RECORD_OVERHEAD + 9];
+        let mut written = 0;
+        for payload in [&b"one"[..], b"", b"three!"] {
+            written += write_record(&sk, payload, &mut stream[written..]).unwrap();
+        }
+        assert_eq!(written, stream.len());
+
+        let (first, rest) = read_record(&sk, &stream).unwrap();
+        let (second, rest) = read_record(&sk, rest).unwrap();
+        let (third, rest) = read_record(&sk, rest).unwrap();
+        assert_eq!(first, b"one");
+        assert!(second.is_empty());
+        assert_eq!(third, b"three!");
+        assert!(rest.is_empty());
+        assert!(read_record(&sk, rest).is_err());
+    }
+
+    #[test]
+    fn test_truncated_records() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let (out, len) = record(b"Some message.");
+
+        for truncated_len in [0, 1, LEN_PREFIX_SIZE, RECORD_OVERHEAD - 1, len - 1] {
+            assert!(read_record(&sk, &out[..truncated_len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_declared_length_exceeds_input() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let (mut out, len) = record(b"Some message.");
+
+        out[..LEN_PREFIX_SIZE].copy_from_slice(&14u32.to_be_bytes());
+        assert!(read_record(&sk, &out[..len]).is_err());
+        out[..LEN_PREFIX_SIZE].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(read_record(&sk, &out).is_err());
+    }
+
+    #[test]
+    fn test_tampered_records() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let (out, len) = record(b"Some message.");
+
+        for idx in [
+            0,
+            LEN_PREFIX_SIZE - 1,
+            LEN_PREFIX_SIZE,
+            len - SHA512_OUTSIZE,
+            len - 1,
+        ] {
+            let mut tampered = out;
+            tampered[idx] ^= 1;
+            assert!(read_record(&sk, &tampered[..len]).is_err());
+        }
+
+        // A shorter declared length makes part of the payload look like the tag.
+        let mut tampered = out;
+        tampered[..LEN_PREFIX_SIZE].copy_from_slice(&12u32.to_be_bytes());
+        assert!(read_record(&sk, &tampered[..len]).is_err());
+
+        let other_sk = SecretKey::from_slice(&[2u8; 128]).unwrap();
+        assert!(read_record(&other_sk, &out[..len]).is_err());
+    }
+
+    #[test]
+    fn test_write_record_dst_too_short() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let mut out = [0u8; RECORD_OVERHEAD + 12];
+        assert!(write_record(&sk, b"Some message.", &mut out).is_err());
+        assert_eq!(
+            write_record(&sk, b"Some message", &mut out).unwrap(),
+            out.len()
+        );
+    }
+}
@@ -30,12 +30,31 @@
 //! - [`finalize()`] is called twice without a [`reset()`] in between.
 //! - [`update()`] is called after [`finalize()`] without a [`reset()`] in
 //!   between.
+//! - More than 2^64-1 bytes are passed to [`update()`] without a [`reset()`]
+//!   in between.
 //! - The HMAC does not match the expected when verifying.
+//! - The amount of data passed to [`update()`] does not match the expected
+//!   length given to [`finalize_verify()`].
+//! - A finalized state is imported with [`import_state()`].
+//! - [`update_ad()`] is called on a state not created with [`new_with_ad()`].
+//! - [`update_ad()`] is called after [`update()`] without a [`reset()`] in between.
+//! - `SecretKey::from_slice()` is called with a slice that is not the blocksize of the hash function.
+//!   Use `SecretKey::from_slice_padded()` for secret keys of other lengths.
+//! - [`verify_slices()`] is called with an `expected` tag that is not the output size of the hash function.
+//!   [`verify_slices_detailed()`] returns [`CryptoError::InvalidInput`] for this, and
+//!   [`CryptoError::Validation`] when the tag does not match.
 //!
 //! # Security:
 //! - The secret key should always be generated using a CSPRNG.
 //!   [`SecretKey::generate()`] can be used for this.
-//! - The minimum recommended size for a secret key is 64 bytes.
+//! - The minimum recommended size for a secret key is 64 bytes. Only
+//!   `SecretKey::from_slice_padded()` accepts shorter keys, padding them without an
+//!   error. [`SecretKey::is_weak()`] can be used to check for short keys built with it.
+//!   With the `log` feature, [`HmacSha512::new()`] and the others log a warning for them.
+//! - [`HmacSha512Hasher`] truncates the tag to 64 bits and must not be used
+//!   for authentication, only for keyed hashing in hash maps.
+//! - [`fingerprint()`] likewise truncates the tag to 64 bits, for keyed
+//!   content-addressing. Fingerprints must not be used for authentication.
 //!
 //! # Recommendation:
 //! - If you are unsure of whether to use HMAC or Poly1305, it is most often
@@ -59,10 +78,25 @@
 //! [`update()`]: hmac::sha512::HmacSha512::update
 //! [`reset()`]: hmac::sha512::HmacSha512::reset
 //! [`finalize()`]: hmac::sha512::HmacSha512::finalize
+//! [`finalize_verify()`]: hmac::sha512::HmacSha512::finalize_verify
+//! [`import_state()`]: hmac::sha512::HmacSha512::import_state
+//! [`verify_slices()`]: hmac::sha512::HmacSha512::verify_slices
+//! [`verify_slices_detailed()`]: hmac::sha512::HmacSha512::verify_slices_detailed
+//! [`CryptoError::InvalidInput`]: crate::errors::CryptoError::InvalidInput
+//! [`CryptoError::Validation`]: crate::errors::CryptoError::Validation
+//! [`update_ad()`]: hmac::sha512::HmacSha512::update_ad
+//! [`new_with_ad()`]: hmac::sha512::HmacSha512::new_with_ad
 //! [`SecretKey::generate()`]: hmac::sha512::SecretKey::generate
+//! [`SecretKey::is_weak()`]: hmac::sha512::SecretKey::is_weak
+//! [`HmacSha512::new()`]: hmac::sha512::HmacSha512::new
+//! [`HmacSha512Hasher`]: hmac::sha512::HmacSha512Hasher
+//! [`fingerprint()`]: hmac::sha512::fingerprint
 //! [Cryptographic Right Answers]: https://latacora.micro.blog/2018/04/03/cryptographic-right-answers.html
 
-use crate::errors::UnknownCryptoError;
+use crate::errors::{CryptoError, UnknownCryptoError};
+use crate::util::ZeroizeGuard;
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::string::String;
 use zeroize::Zeroize;
 
 /// A trait used to define a cryptographic hash function used by HMAC.
@@ -121,20 +155,40 @@ pub(crate) trait HmacFunction {
 const IPAD: u8 = 0x36;
 const OPAD: u8 = 0x5C;
 
+/// Salt of the HKDF-Extract that derives the key of states created with `_new_with_ad()`.
+const AD_KEY_SALT: &[u8] = b"orion-hmac-ad";
+
+/// Prefix of each associated data frame passed to `_update_ad()`.
+const AD_FRAME: u8 = 0x00;
+/// Marker separating the associated data frames from the message.
+const AD_END: u8 = 0x01;
+
+#[cfg(all(test, feature = "safe_api"))]
+std::thread_local! {
+    /// Panic in the middle of `_keyed_hashers()` for the current thread. Only used to test
+    /// that the padded key is wiped when unwinding.
+    static PANIC_IN_KEYED_HASHERS: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
 #[derive(Clone)]
 pub(crate) struct Hmac<S: HmacHashFunction, const BLOCKSIZE: usize> {
     working_hasher: S,
     opad_hasher: S,
     ipad_hasher: S,
+    bytes_processed: u64,
     is_finalized: bool,
+    /// Created with `_new_with_ad()`, so that `_update_ad()` can be used.
+    ad_mode: bool,
+    /// In `ad_mode`, but `AD_END` has not been processed yet.
+    ad_pending: bool,
 }
 
 impl<S: HmacHashFunction, const BLOCKSIZE: usize> core::fmt::Debug for Hmac<S, BLOCKSIZE> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "Hmac {{ working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], is_finalized: {:?} }}",
-            self.is_finalized
+            "Hmac {{ working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], bytes_processed: {:?}, is_finalized: {:?} }}",
+            self.bytes_processed, self.is_finalized
         )
     }
 }
@@ -145,12 +199,34 @@ impl<S: HmacHashFunction, const BLOCKSIZE: usize> Hmac<S, BLOCKSIZE> {
     #[allow(dead_code)]
     const HASH_FUNC_OUTSIZE: usize = S::_OUTSIZE;
 
-    /// Construct a state from a `secret_key`. The `secret_key` may be pre-padded or not.
+    /// Return the inner and outer hashers, having processed IPAD and OPAD xor `secret_key`
+    /// respectively. The `secret_key` may be pre-padded or not.
     ///
     /// Ref: https://brycx.github.io/2018/08/06/hmac-and-precomputation-optimization.html
-    fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
+    #[inline]
+    fn _keyed_hashers(secret_key: &[u8]) -> Result<(S, S), UnknownCryptoError> {
+        let mut pad = [0u8; BLOCKSIZE];
+        Self::_keyed_hashers_with_pad(secret_key, &mut pad)
+    }
+
+    /// Same as `_keyed_hashers()`, using `pad` as scratch space for the padded key.
+    /// `pad` is zeroized on every return path, including errors and panics.
+    ///
+    /// Timing: the only branch is on `secret_key.len()`, which is not secret. The XOR
+    /// loops and hash updates run over all `BLOCKSIZE` bytes of `pad`, whatever the
+    /// key bytes are. `HmacShaXXX::new()` always passes a key that `SecretKey` has
+    /// already padded to `BLOCKSIZE`, so it never takes the hashing branch. A key longer
+    /// than the blocksize is hashed once, in `SecretKey::from_slice_padded()`, which
+    /// likewise only depends on its length. See `test_hmac_sha512_new_timing` in
+    /// `tests/ct` for a timing test.
+    #[inline]
+    fn _keyed_hashers_with_pad(
+        secret_key: &[u8],
+        pad: &mut [u8; BLOCKSIZE],
+    ) -> Result<(S, S), UnknownCryptoError> {
         debug_assert_eq!(S::_BLOCKSIZE, BLOCKSIZE);
-        let mut ipad = [IPAD; BLOCKSIZE];
+        let mut ipad = ZeroizeGuard(pad);
+        ipad.fill(IPAD);
 
         if secret_key.len() > BLOCKSIZE {
             // SK is NOT pre-padded.
@@ -163,7 +239,7 @@ impl<S: HmacHashFunction, const BLOCKSIZE: usize> Hmac<S, BLOCKSIZE> {
             // SK has been pre-padded or SK.len() <= BLOCKSIZE.
             // Because 0x00 xor IPAD = IPAD, the existence of padding bytes (0x00)
             // within SK, during this operation, is inconsequential.
-            xor_slices!(secret_key, &mut ipad);
+            xor_slices!(secret_key, &mut ipad[..]);
         }
 
         let mut ih = S::_new();
@@ -174,33 +250,144 @@ impl<S: HmacHashFunction, const BLOCKSIZE: usize> Hmac<S, BLOCKSIZE> {
             *elem ^= IPAD ^ OPAD;
         }
 
+        #[cfg(all(test, feature = "safe_api"))]
+        if PANIC_IN_KEYED_HASHERS.with(|panic| panic.get()) {
+            panic!("injected panic in _keyed_hashers()");
+        }
+
         let mut oh = S::_new();
         oh._update(&ipad)?;
 
-        ipad.iter_mut().zeroize();
+        Ok((ih, oh))
+    }
+
+    /// Construct a state from a `secret_key`. The `secret_key` may be pre-padded or not.
+    fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
+        Self::_new_in_mode(secret_key, false)
+    }
+
+    /// Construct a state from a `secret_key` that authenticates associated data passed
+    /// to `_update_ad()`, before the message. The `secret_key` may be pre-padded or not.
+    fn _new_with_ad(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
+        Self::_new_in_mode(secret_key, true)
+    }
+
+    /// Derive the key of a state in `ad_mode` from `secret_key` into `dest`, which must
+    /// be the output size of the hash function. The `secret_key` may be pre-padded or not.
+    ///
+    /// This is HKDF-Extract, with `AD_KEY_SALT` as salt and the padded `secret_key` as
+    /// input keying material. Only standard HMAC is used, keyed with the salt, so the
+    /// result is unrelated to any tag made with `secret_key` itself. Using the padded
+    /// key means that `secret_key` with or without its padding gives the same result.
+    fn _ad_key(secret_key: &[u8], dest: &mut [u8]) -> Result<(), UnknownCryptoError> {
+        debug_assert_eq!(dest.len(), S::_OUTSIZE);
+        let mut padded_key = [0u8; BLOCKSIZE];
+        let mut padded_key = ZeroizeGuard(&mut padded_key);
+        if secret_key.len() > BLOCKSIZE {
+            S::_digest(secret_key, &mut padded_key[..S::_OUTSIZE])?;
+        } else {
+            padded_key[..secret_key.len()].copy_from_slice(secret_key);
+        }
+
+        let (mut ih, mut oh) = Self::_keyed_hashers(AD_KEY_SALT)?;
+        ih._update(&padded_key[..])?;
+        ih._finalize(dest)?;
+        oh._update(dest)?;
+        oh._finalize(dest)
+    }
+
+    /// Return the keyed hashers of a state in `ad_mode` or not, from `secret_key`.
+    fn _keyed_hashers_in_mode(
+        secret_key: &[u8],
+        ad_mode: bool,
+    ) -> Result<(S, S), UnknownCryptoError> {
+        if !ad_mode {
+            return Self::_keyed_hashers(secret_key);
+        }
+
+        let mut ad_key = [0u8; BLOCKSIZE];
+        let mut ad_key = ZeroizeGuard(&mut ad_key);
+        Self::_ad_key(secret_key, &mut ad_key[..S::_OUTSIZE])?;
+        Self::_keyed_hashers(&ad_key[..S::_OUTSIZE])
+    }
+
+    fn _new_in_mode(secret_key: &[u8], ad_mode: bool) -> Result<Self, UnknownCryptoError> {
+        let (ih, oh) = Self::_keyed_hashers_in_mode(secret_key, ad_mode)?;
 
         Ok(Self {
             working_hasher: ih.clone(),
             opad_hasher: oh,
             ipad_hasher: ih,
+            bytes_processed: 0,
             is_finalized: false,
+            ad_mode,
+            ad_pending: ad_mode,
         })
     }
 
+    /// Pass `data` to the inner hash function and count it.
+    fn _absorb(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        self.bytes_processed = u64::try_from(data.len())
+            .ok()
+            .and_then(|len| self.bytes_processed.checked_add(len))
+            .ok_or(UnknownCryptoError)?;
+
+        self.working_hasher._update(data)
+    }
+
+    /// Mark the end of the associated data, if it has not been marked yet. Only states
+    /// in `ad_mode` have associated data, which may also be none at all.
+    fn _end_ad(&mut self) -> Result<(), UnknownCryptoError> {
+        if self.ad_pending {
+            self.ad_pending = false;
+            self._absorb(&[AD_END])?;
+        }
+
+        Ok(())
+    }
+
     fn _update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
         if self.is_finalized {
-            Err(UnknownCryptoError)
-        } else {
-            self.working_hasher._update(data)
+            log_misuse!("HMAC: update() called on a finalized state without reset()");
+            return Err(UnknownCryptoError);
+        }
+
+        self._end_ad()?;
+        self._absorb(data)
+    }
+
+    /// Process `ad` as a length-prefixed frame of associated data. This must be called
+    /// on a state in `ad_mode`, before any message data is passed to `_update()`.
+    fn _update_ad(&mut self, ad: &[u8]) -> Result<(), UnknownCryptoError> {
+        if self.is_finalized {
+            log_misuse!("HMAC: update_ad() called on a finalized state without reset()");
+            return Err(UnknownCryptoError);
+        }
+        if !self.ad_mode {
+            log_misuse!("HMAC: update_ad() called on a state not created with new_with_ad()");
+            return Err(UnknownCryptoError);
+        }
+        if !self.ad_pending {
+            log_misuse!("HMAC: update_ad() called after update()");
+            return Err(UnknownCryptoError);
         }
+
+        let ad_len = u64::try_from(ad.len()).map_err(|_| UnknownCryptoError)?;
+        self._absorb(&[AD_FRAME])?;
+        self._absorb(&ad_len.to_le_bytes())?;
+        self._absorb(ad)?;
+
+        Ok(())
     }
 
     fn _finalize(&mut self, dest: &mut [u8]) -> Result<(), UnknownCryptoError> {
         debug_assert!(!dest.is_empty());
         if self.is_finalized {
+            log_misuse!("HMAC: finalize() called on a finalized state without reset()");
             return Err(UnknownCryptoError);
         }
 
+        self._end_ad()?;
         self.is_finalized = true;
         let mut outer_hasher = self.opad_hasher.clone();
         self.working_hasher._finalize(dest)?;
@@ -208,9 +395,42 @@ impl<S: HmacHashFunction, const BLOCKSIZE: usize> Hmac<S, BLOCKSIZE> {
         outer_hasher._finalize(dest)
     }
 
+    /// One-shot HMAC of `data`, which must be less than `BLOCKSIZE` bytes.
+    ///
+    /// This skips the streaming state entirely: no copy of the inner hasher is kept for
+    /// resetting and no finalization or length bookkeeping is done. The result is identical
+    /// to that of `_new()`, `_update()` and `_finalize()`.
+    #[inline]
+    fn _oneshot_small(
+        secret_key: &[u8],
+        data: &[u8],
+        dest: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        debug_assert!(data.len() < BLOCKSIZE);
+        debug_assert!(!dest.is_empty());
+        let (mut ih, mut oh) = Self::_keyed_hashers(secret_key)?;
+        ih._update(data)?;
+        ih._finalize(dest)?;
+        oh._update(dest)?;
+        oh._finalize(dest)
+    }
+
     fn _reset(&mut self) {
         self.working_hasher = self.ipad_hasher.clone();
+        self.bytes_processed = 0;
         self.is_finalized = false;
+        self.ad_pending = self.ad_mode;
+    }
+
+    /// Replace the key of the state with `secret_key` and reset it, keeping `ad_mode`.
+    /// The `secret_key` may be pre-padded or not.
+    fn _reset_with_key(&mut self, secret_key: &[u8]) -> Result<(), UnknownCryptoError> {
+        let (ih, oh) = Self::_keyed_hashers_in_mode(secret_key, self.ad_mode)?;
+        self.ipad_hasher = ih;
+        self.opad_hasher = oh;
+        self._reset();
+
+        Ok(())
     }
 
     #[cfg(test)]
@@ -221,7 +441,72 @@ impl<S: HmacHashFunction, const BLOCKSIZE: usize> Hmac<S, BLOCKSIZE> {
             .compare_state_to_other(&other.working_hasher);
         self.opad_hasher.compare_state_to_other(&other.opad_hasher);
         self.ipad_hasher.compare_state_to_other(&other.ipad_hasher);
+        assert_eq!(self.bytes_processed, other.bytes_processed);
         assert_eq!(self.is_finalized, other.is_finalized);
+        assert_eq!(self.ad_mode, other.ad_mode);
+        assert_eq!(self.ad_pending, other.ad_pending);
+    }
+}
+
+#[cfg(all(test, feature = "safe_api"))]
+mod test_keyed_hashers {
+    use super::*;
+    use crate::hazardous::hash::sha2::sha512::{Sha512, SHA512_BLOCKSIZE};
+
+    #[test]
+    fn test_pad_wiped_on_panic() {
+        struct Guard;
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                PANIC_IN_KEYED_HASHERS.with(|panic| panic.set(false));
+            }
+        }
+
+        let mut pad = [0u8; SHA512_BLOCKSIZE];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            PANIC_IN_KEYED_HASHERS.with(|panic| panic.set(true));
+            let _guard = Guard;
+            let _ = Hmac::<Sha512, SHA512_BLOCKSIZE>::_keyed_hashers_with_pad(&[1u8; 32], &mut pad);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(pad, [0u8; SHA512_BLOCKSIZE]);
+    }
+
+    #[test]
+    fn test_pad_wiped_on_return() {
+        let mut pad = [0u8; SHA512_BLOCKSIZE];
+        let (ih, oh) =
+            Hmac::<Sha512, SHA512_BLOCKSIZE>::_keyed_hashers_with_pad(&[1u8; 32], &mut pad)
+                .unwrap();
+        assert_eq!(pad, [0u8; SHA512_BLOCKSIZE]);
+
+        let (ih_expected, oh_expected) =
+            Hmac::<Sha512, SHA512_BLOCKSIZE>::_keyed_hashers(&[1u8; 32]).unwrap();
+        ih.compare_state_to_other(&ih_expected);
+        oh.compare_state_to_other(&oh_expected);
+    }
+
+    #[test]
+    fn test_ad_key_same_for_equivalent_keys() {
+        type H = Hmac<Sha512, SHA512_BLOCKSIZE>;
+        let mut expected = [0u8; 64];
+        H::_ad_key(&[1u8; 32], &mut expected).unwrap();
+
+        let mut padded = [0u8; SHA512_BLOCKSIZE];
+        padded[..32].copy_from_slice(&[1u8; 32]);
+        let mut actual = [0u8; 64];
+        H::_ad_key(&padded, &mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        // A key longer than the blocksize is used as its digest, as in plain HMAC.
+        let long_key = [2u8; SHA512_BLOCKSIZE + 1];
+        let mut digest = [0u8; 64];
+        Sha512::_digest(&long_key, &mut digest).unwrap();
+        H::_ad_key(&long_key, &mut expected).unwrap();
+        H::_ad_key(&digest, &mut actual).unwrap();
+        assert_eq!(actual, expected);
     }
 }
 
@@ -234,7 +519,9 @@ pub mod sha256 {
         /// A type to represent the `SecretKey` that HMAC uses for authentication.
         ///
         /// # Note:
-        /// `SecretKey` pads the secret key for use with HMAC to a length of 64, when initialized.
+        /// `from_slice()` requires a secret key of exactly 64 bytes. `from_slice_padded()` accepts
+        /// any length and pads (or hashes, if longer) the secret key for use with HMAC to a length
+        /// of 64, when initialized.
         ///
         /// Using `unprotected_as_bytes()` will return the secret key with padding.
         ///
@@ -252,10 +539,35 @@ pub mod sha256 {
         /// # Errors:
         /// An error will be returned if:
         /// - `slice` is not 32 bytes.
+        ///
+        /// # Note:
+        /// Tags of different MACs are distinct types, and one cannot be passed where
+        /// the other is expected:
+        /// ```rust,compile_fail
+        /// use orion::hazardous::mac::{hmac, poly1305};
+        ///
+        /// let hmac_tag = hmac::sha256::Tag::from([0u8; 32]);
+        /// let key = poly1305::OneTimeKey::from_slice(&[0u8; 32])?;
+        ///
+        /// let _ = poly1305::Poly1305::verify(&hmac_tag, &key, b"data");
+        /// # Ok::<(), orion::errors::UnknownCryptoError>(())
+        /// ```
         (Tag, test_tag, sha256::SHA256_OUTSIZE, sha256::SHA256_OUTSIZE)
     }
 
-    impl_from_trait!(Tag, sha256::SHA256_OUTSIZE);
+    impl_from_trait!(@fixed_bytes Tag, sha256::SHA256_OUTSIZE);
+
+    impl Tag {
+        /// Return the XOR of this tag and `other`, computed over all bytes.
+        ///
+        /// This is only meant for protocols that combine tags this way, such as for
+        /// aggregate verification. XORing tags is not a secure MAC on its own.
+        pub fn xor(&self, other: &Tag) -> Tag {
+            Tag {
+                bytes: self.bytes.xor(&other.bytes),
+            }
+        }
+    }
 
     use super::Hmac;
 
@@ -265,6 +577,8 @@ pub mod sha256 {
         _state: Hmac<Sha256, { sha256::SHA256_BLOCKSIZE }>,
     }
 
+    impl_extend_trait!(HmacSha256);
+
     impl HmacSha256 {
         fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
             Ok(Self {
@@ -275,19 +589,78 @@ pub mod sha256 {
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
         /// Initialize `HmacSha256` struct with a given key.
         pub fn new(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new() called with a secret key shorter than 64 bytes");
+            }
             // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
             Self::_new(secret_key.unprotected_as_bytes()).unwrap()
         }
 
-        /// Reset to `new()` state.
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Initialize `HmacSha256` struct with a given key, for authenticating associated data
+        /// with [`Self::update_ad()`] before the message.
+        ///
+        /// The state runs standard HMAC-SHA256 under a key derived from `secret_key`
+        /// with HKDF-Extract, using `"orion-hmac-ad"` as salt. Its tags are therefore
+        /// unrelated to those of [`Self::new()`] under the same key.
+        pub fn new_with_ad(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new_with_ad() called with a secret key shorter than 64 bytes");
+            }
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            Self {
+                _state: Hmac::<Sha256, { sha256::SHA256_BLOCKSIZE }>::_new_with_ad(
+                    secret_key.unprotected_as_bytes(),
+                )
+                .unwrap(),
+            }
+        }
+
+        /// Reset to the state returned by `new()` or `new_with_ad()`, whichever
+        /// created this state.
         pub fn reset(&mut self) {
             self._state._reset()
         }
 
+        /// Replace the key with `secret_key` and reset to the state `new()` or
+        /// `new_with_ad()`, whichever created this state, would return for it. Previous
+        /// key material in the state is overwritten.
+        pub fn reset_with_key(&mut self, secret_key: &SecretKey) {
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            self._state
+                ._reset_with_key(secret_key.unprotected_as_bytes())
+                .unwrap()
+        }
+
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
         /// Update state with `data`. This can be called multiple times.
-        pub fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
-            self._state._update(data)
+        ///
+        /// `data` can be anything that is viewable as bytes, such as `&str`, `Vec<u8>`
+        /// or a byte array.
+        pub fn update(&mut self, data: impl AsRef<[u8]>) -> Result<(), UnknownCryptoError> {
+            self._state._update(data.as_ref())
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Update state with associated data `ad`. This can be called multiple times,
+        /// but only on a state created with [`Self::new_with_ad()`] and before the first
+        /// call to [`Self::update()`].
+        ///
+        /// Each `ad` is authenticated as `0x00 || LE64(ad.len()) || ad`, and a single
+        /// `0x01` byte is authenticated after the last one, so the boundaries between
+        /// the associated data and the message cannot be shifted. The `0x01` byte is
+        /// also authenticated if this is never called.
+        pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), UnknownCryptoError> {
+            self._state._update_ad(ad)
+        }
+
+        /// Return the number of bytes passed to [`Self::update()`] since
+        /// [`Self::new()`] or the last [`Self::reset()`]. This includes the framing
+        /// added by [`Self::update_ad()`].
+        pub fn bytes_processed(&self) -> u64 {
+            self._state.bytes_processed
         }
 
         /// Return a HMAC-SHA256 tag.
@@ -307,12 +680,92 @@ pub mod sha256 {
             Ok(Tag::from(dest))
         }
 
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a tag, together with the number of bytes it authenticates.
+        pub fn finalize_with_len(&mut self) -> Result<(Tag, u64), UnknownCryptoError> {
+            let tag = self.finalize()?;
+
+            Ok((tag, self.bytes_processed()))
+        }
+
+        #[cfg(any(feature = "safe_api", feature = "alloc"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a HMAC-SHA256 tag as a lowercase hex string. This finalizes the state,
+        /// the same as [`Self::finalize()`].
+        pub fn finalize_hex(&mut self) -> Result<String, UnknownCryptoError> {
+            Ok(crate::util::to_hex(self.finalize()?.unprotected_as_bytes()))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a tag, together with the state re-keyed with `secret_key`, as
+        /// [`Self::new()`] or [`Self::new_with_ad()`], whichever created this state,
+        /// would return it.
+        ///
+        /// This is meant for authenticating the next message under a new key right
+        /// away. The state is reused, and the previous key material in it is overwritten.
+        pub fn finalize_and_init(
+            mut self,
+            secret_key: &SecretKey,
+        ) -> Result<(Tag, Self), UnknownCryptoError> {
+            let tag = self.finalize()?;
+            self.reset_with_key(secret_key);
+
+            Ok((tag, self))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Finalize the state and verify the result against `expected` in constant time.
+        ///
+        /// If [`Self::bytes_processed()`] differs from `expected_len`, an error is
+        /// returned immediately, without finalizing the state. The length of the
+        /// input is not secret, so this check does not need to be constant time.
+        pub fn finalize_verify(
+            &mut self,
+            expected: &Tag,
+            expected_len: u64,
+        ) -> Result<(), UnknownCryptoError> {
+            if self.bytes_processed() != expected_len {
+                return Err(UnknownCryptoError);
+            }
+
+            if &self.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
         /// One-shot function for generating an HMAC-SHA256 tag of `data`.
         pub fn hmac(secret_key: &SecretKey, data: &[u8]) -> Result<Tag, UnknownCryptoError> {
-            let mut ctx = Self::new(secret_key);
-            ctx.update(data)?;
-            ctx.finalize()
+            Ok(Tag::from(Self::hmac_into_array(secret_key, data)?))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// One-shot function for generating an HMAC-SHA256 tag of `data`, returned as an array.
+        ///
+        /// Only the stack is used, so this is also available in `no_std` context. The bytes
+        /// are the same as those of the `Tag` returned by [`Self::hmac()`].
+        pub fn hmac_into_array(
+            secret_key: &SecretKey,
+            data: &[u8],
+        ) -> Result<[u8; sha256::SHA256_OUTSIZE], UnknownCryptoError> {
+            let mut dest = [0u8; sha256::SHA256_OUTSIZE];
+
+            if data.len() < sha256::SHA256_BLOCKSIZE {
+                Hmac::<Sha256, { sha256::SHA256_BLOCKSIZE }>::_oneshot_small(
+                    secret_key.unprotected_as_bytes(),
+                    data,
+                    &mut dest,
+                )?;
+            } else {
+                let mut ctx = Self::new(secret_key);
+                ctx.update(data)?;
+                ctx._finalize_internal(&mut dest)?;
+            }
+
+            Ok(dest)
         }
 
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
@@ -328,6 +781,128 @@ pub mod sha256 {
                 Err(UnknownCryptoError)
             }
         }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA256 tag in constant time, accepting it if it is valid under
+        /// any of `secret_keys`. This is meant for key rotation, where tags made with
+        /// the previous key are accepted for a while.
+        ///
+        /// A tag is computed and compared for every key, without returning early, so the
+        /// time taken does not reveal which key matched. An error is returned if
+        /// `secret_keys` is empty.
+        pub fn verify_any(
+            expected: &Tag,
+            secret_keys: &[&SecretKey],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            use subtle::ConstantTimeEq;
+
+            let mut matched = subtle::Choice::from(0u8);
+            for secret_key in secret_keys {
+                let tag = Self::hmac(secret_key, data)?;
+                matched |= tag
+                    .unprotected_as_bytes()
+                    .ct_eq(expected.unprotected_as_bytes());
+            }
+
+            if bool::from(matched) {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA256 tag in constant time, given the tag and secret key as raw bytes.
+        ///
+        /// `secret_key` may be of any length and is padded, or hashed if longer than the
+        /// blocksize, as specified in RFC 2104. An error is returned if `expected` is not
+        /// 32 bytes.
+        pub fn verify_slices(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            // The length of a tag is not secret, so a truncated or otherwise malformed
+            // tag can be told apart from a mismatch. The error is the same either way.
+            if expected.len() != sha256::SHA256_OUTSIZE {
+                log_misuse!(
+                    "HMAC: verify_slices() called with an expected tag of the wrong length"
+                );
+                return Err(UnknownCryptoError);
+            }
+            let expected = Tag::from_slice(expected)?;
+            let secret_key = SecretKey::from_slice_padded(secret_key)?;
+
+            Self::verify(&expected, &secret_key, data)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA256 tag in constant time, given the tag and secret key as raw
+        /// bytes, telling a malformed tag apart from one that does not match.
+        ///
+        /// This is the same as [`Self::verify_slices()`], except for the error returned:
+        /// - [`CryptoError::InvalidInput`] if `expected` is not 32 bytes. The length
+        ///   of a tag is not secret.
+        /// - [`CryptoError::Validation`] if `expected` does not match. Its contents are
+        ///   still compared in constant time.
+        pub fn verify_slices_detailed(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), CryptoError> {
+            if expected.len() != sha256::SHA256_OUTSIZE {
+                return Err(CryptoError::InvalidInput);
+            }
+
+            Self::verify_slices(expected, secret_key, data).map_err(|_| CryptoError::Validation)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA256 tag in constant time, over the concatenation of `chunks`.
+        pub fn verify_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(
+            expected: &Tag,
+            secret_key: &SecretKey,
+            chunks: I,
+        ) -> Result<(), UnknownCryptoError> {
+            let mut ctx = Self::new(secret_key);
+            for chunk in chunks {
+                ctx.update(chunk)?;
+            }
+
+            if &ctx.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+    }
+
+    impl crate::hazardous::mac::sealed::Sealed for HmacSha256 {}
+
+    impl crate::hazardous::mac::Mac for HmacSha256 {
+        type Key = SecretKey;
+        type Tag = Tag;
+
+        fn new(key: &SecretKey) -> Self {
+            HmacSha256::new(key)
+        }
+
+        fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha256::update(self, data)
+        }
+
+        fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+            HmacSha256::finalize(self)
+        }
+
+        fn reset_with_key(&mut self, key: &SecretKey) {
+            HmacSha256::reset_with_key(self, key)
+        }
+
+        fn verify(expected: &Tag, key: &SecretKey, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha256::verify(expected, key, data)
+        }
     }
 
     impl HmacFunction for HmacSha256 {
@@ -369,7 +944,7 @@ pub mod sha256 {
             let secret_key = SecretKey::generate();
             let initial_state = HmacSha256::new(&secret_key);
             let debug = format!("{:?}", initial_state);
-            let expected = "HmacSha256 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], is_finalized: false } }";
+            let expected = "HmacSha256 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], bytes_processed: 0, is_finalized: false } }";
             assert_eq!(debug, expected);
         }
 
@@ -392,234 +967,597 @@ pub mod sha256 {
             }
         }
 
-        mod test_streaming_interface {
+        mod test_tag_xor {
             use super::*;
-            use crate::test_framework::incremental_interface::*;
 
-            const KEY: [u8; 32] = [0u8; 32];
+            #[test]
+            fn test_xor_is_involution() {
+                let a = Tag::from([1u8; sha256::SHA256_OUTSIZE]);
+                let b: [u8; sha256::SHA256_OUTSIZE] = core::array::from_fn(|i| i as u8);
+                let b = Tag::from(b);
+
+                assert_ne!(a.xor(&b), a);
+                assert_eq!(a.xor(&b).xor(&b), a);
+                assert_eq!(a.xor(&b), b.xor(&a));
+                assert_eq!(a.xor(&a), Tag::from([0u8; sha256::SHA256_OUTSIZE]));
+            }
+        }
 
-            impl TestableStreamingContext<Tag> for HmacSha256 {
-                fn reset(&mut self) -> Result<(), UnknownCryptoError> {
-                    self.reset();
-                    Ok(())
-                }
+        mod test_extend {
+            use super::*;
 
-                fn update(&mut self, input: &[u8]) -> Result<(), UnknownCryptoError> {
-                    self.update(input)
+            #[test]
+            fn test_extend_same_as_update() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+
+                for len in [0, 1, 63, 64, 65, 128, 300] {
+                    let mut expected = HmacSha256::new(&key);
+                    expected.update(&data[..len]).unwrap();
+
+                    let mut state = HmacSha256::new(&key);
+                    state.extend(data[..len].iter().copied());
+                    assert_eq!(state.bytes_processed(), len as u64);
+                    let mut state_ref = HmacSha256::new(&key);
+                    state_ref.extend(data[..len].iter());
+
+                    let expected = expected.finalize().unwrap();
+                    assert_eq!(state.finalize().unwrap(), expected);
+                    assert_eq!(state_ref.finalize().unwrap(), expected);
                 }
+            }
 
-                fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
-                    self.finalize()
-                }
+            #[test]
+            fn test_extend_after_finalize_ignored() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new(&key);
+                let _ = state.finalize().unwrap();
+                state.extend([0u8; 100]);
+                assert_eq!(state.bytes_processed(), 0);
+                assert!(state.finalize().is_err());
+            }
+        }
 
-                fn one_shot(input: &[u8]) -> Result<Tag, UnknownCryptoError> {
-                    HmacSha256::hmac(&SecretKey::from_slice(&KEY).unwrap(), input)
-                }
+        mod test_reset_with_key {
+            use super::*;
 
-                fn verify_result(expected: &Tag, input: &[u8]) -> Result<(), UnknownCryptoError> {
-                    // This will only run verification tests on differing input. They do not
-                    // include tests for different secret keys.
-                    HmacSha256::verify(expected, &SecretKey::from_slice(&KEY).unwrap(), input)
-                }
+            #[test]
+            fn test_reset_with_key_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut fresh = HmacSha256::new(&new_key);
+                fresh.update(data).unwrap();
+                let expected = fresh.finalize().unwrap();
+
+                let mut state = HmacSha256::new(&old_key);
+                state.update(data).unwrap();
+                let old_tag = state.finalize().unwrap();
+                assert_ne!(old_tag, expected);
+
+                // Also from a finalized state.
+                state.reset_with_key(&new_key);
+                assert_eq!(state.bytes_processed(), 0);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                // A subsequent reset() keeps the new key.
+                state.reset();
+                state.update(&data[..50]).unwrap();
+                state.reset_with_key(&new_key);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+                state.reset();
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+            }
+        }
 
-                fn compare_states(state_1: &HmacSha256, state_2: &HmacSha256) {
-                    state_1._state.compare_state_to_other(&state_2._state);
-                }
+        mod test_empty_input {
+            use super::*;
+
+            fn check_empty_input(key: &[u8], expected_hex: &str) {
+                let secret_key = SecretKey::from_slice_padded(key).unwrap();
+                let mut expected = [0u8; sha256::SHA256_OUTSIZE];
+                hex::decode_to_slice(expected_hex, &mut expected).unwrap();
+                let expected_tag = Tag::from_slice(&expected).unwrap();
+
+                assert_eq!(
+                    HmacSha256::hmac_into_array(&secret_key, &[]).unwrap(),
+                    expected
+                );
+                assert_eq!(HmacSha256::hmac(&secret_key, &[]).unwrap(), expected_tag);
+                assert!(HmacSha256::verify(&expected_tag, &secret_key, &[]).is_ok());
+                assert!(HmacSha256::verify(&expected_tag, &secret_key, &[0u8]).is_err());
+
+                // Streaming, with no or only empty updates.
+                let mut state = HmacSha256::new(&secret_key);
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+                state.reset();
+                state.update([]).unwrap();
+                state.update([]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected_tag);
             }
 
             #[test]
-            fn default_consistency_tests() {
-                let initial_state = HmacSha256::new(&SecretKey::from_slice(&KEY).unwrap());
-
-                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha256>::new(
-                    initial_state,
-                    sha256::SHA256_BLOCKSIZE,
+            fn test_empty_input_kat() {
+                // Computed with Python's hmac module.
+                check_empty_input(
+                    b"",
+                    "b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad",
+                );
+                check_empty_input(
+                    b"key",
+                    "5d5d139563c95b5967b9bd9a8c9b233a9dedb45072794cd232dc1b74832607d0",
                 );
-                test_runner.run_all_tests();
             }
+        }
 
-            #[quickcheck]
-            #[cfg(feature = "safe_api")]
-            /// Related bug: https://github.com/orion-rs/orion/issues/46
-            /// Test different streaming state usage patterns.
-            fn prop_input_to_consistency(data: Vec<u8>) -> bool {
-                let initial_state = HmacSha256::new(&SecretKey::from_slice(&KEY).unwrap());
+        mod test_finalize_and_init {
+            use super::*;
 
-                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha256>::new(
-                    initial_state,
-                    sha256::SHA256_BLOCKSIZE,
+            #[test]
+            fn test_finalize_and_init_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut state = HmacSha256::new(&old_key);
+                state.update(data).unwrap();
+                let (tag, mut state) = state.finalize_and_init(&new_key).unwrap();
+                assert_eq!(tag, HmacSha256::hmac(&old_key, &data).unwrap());
+                state
+                    ._state
+                    .compare_state_to_other(&HmacSha256::new(&new_key)._state);
+
+                state.update(data).unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha256::hmac(&new_key, &data).unwrap()
                 );
-                test_runner.run_all_tests_property(&data);
-                true
+
+                // An already finalized state cannot be finalized again.
+                assert!(state.finalize_and_init(&old_key).is_err());
             }
         }
-    }
-}
 
-/// HMAC-SHA384 (Hash-based Message Authentication Code) as specified in the [RFC 2104](https://tools.ietf.org/html/rfc2104).
-pub mod sha384 {
-    use super::*;
-    use crate::hazardous::hash::sha2::sha384::{self, Sha384};
+        mod test_finalize_with_len {
+            use super::*;
 
-    construct_hmac_key! {
-        /// A type to represent the `SecretKey` that HMAC uses for authentication.
-        ///
-        /// # Note:
-        /// `SecretKey` pads the secret key for use with HMAC to a length of 128, when initialized.
-        ///
-        /// Using `unprotected_as_bytes()` will return the secret key with padding.
-        ///
-        /// `len()` will return the length with padding (always 128).
-        ///
-        /// # Panics:
-        /// A panic will occur if:
-        /// - Failure to generate random bytes securely.
-        (SecretKey, Sha384, sha384::SHA384_OUTSIZE, test_hmac_key, sha384::SHA384_BLOCKSIZE)
-    }
+            #[test]
+            fn test_len_across_chunks() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
+
+                let mut state = HmacSha256::new(&key);
+                let mut expected_len = 0u64;
+                for chunk in data.chunks(7) {
+                    state.update(chunk).unwrap();
+                    expected_len += chunk.len() as u64;
+                }
+                state.update([]).unwrap();
+
+                let mut other = HmacSha256::new(&key);
+                other.update(data).unwrap();
+                let (tag, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, expected_len);
+                assert_eq!(len, 300);
+                assert_eq!(tag, other.finalize().unwrap());
+                assert!(state.finalize_with_len().is_err());
+
+                state.reset();
+                let (_, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, 0);
+            }
+        }
 
-    construct_tag! {
-        /// A type to represent the `Tag` that HMAC returns.
-        ///
-        /// # Errors:
-        /// An error will be returned if:
-        /// - `slice` is not 48 bytes.
-        (Tag, test_tag, sha384::SHA384_OUTSIZE, sha384::SHA384_OUTSIZE)
-    }
+        mod test_verify_chunks {
+            use super::*;
 
-    impl_from_trait!(Tag, sha384::SHA384_OUTSIZE);
+            #[test]
+            fn test_verify_chunks_same_as_verify() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut data = [0u8; 300];
+                for (idx, byte) in data.iter_mut().enumerate() {
+                    *byte = idx as u8;
+                }
+                let tag = HmacSha256::hmac(&key, &data).unwrap();
+                assert!(HmacSha256::verify(&tag, &key, &data).is_ok());
 
-    use super::Hmac;
+                for chunk_size in [1, 7, 64, 128, 299, 300, 301] {
+                    assert!(HmacSha256::verify_chunks(&tag, &key, data.chunks(chunk_size)).is_ok());
+                }
+                assert!(
+                    HmacSha256::verify_chunks(&tag, &key, [&data[..10], &[], &data[10..]]).is_ok()
+                );
 
-    #[derive(Clone, Debug)]
-    /// HMAC-SHA384 streaming state.
-    pub struct HmacSha384 {
-        _state: Hmac<Sha384, { sha384::SHA384_BLOCKSIZE }>,
-    }
+                // Missing, reordered and extra data.
+                assert!(HmacSha256::verify_chunks(&tag, &key, data.chunks(7).skip(1)).is_err());
+                assert!(HmacSha256::verify_chunks(&tag, &key, data.chunks(150).rev()).is_err());
+                assert!(HmacSha256::verify_chunks(&tag, &key, [&data[..], &[0u8]]).is_err());
 
-    impl HmacSha384 {
-        fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
-            Ok(Self {
-                _state: Hmac::<Sha384, { sha384::SHA384_BLOCKSIZE }>::_new(secret_key)?,
-            })
+                let empty_tag = HmacSha256::hmac(&key, &[]).unwrap();
+                assert!(HmacSha256::verify_chunks(&empty_tag, &key, core::iter::empty()).is_ok());
+            }
         }
 
-        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Initialize `HmacSha384` struct with a given key.
-        pub fn new(secret_key: &SecretKey) -> Self {
-            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
-            Self::_new(secret_key.unprotected_as_bytes()).unwrap()
-        }
+        mod test_hmac_into_array {
+            use super::*;
 
-        /// Reset to `new()` state.
-        pub fn reset(&mut self) {
-            self._state._reset()
-        }
+            #[test]
+            // Does not depend on `safe_api`, so this also runs in `no_std` context.
+            fn test_hmac_into_array_same_as_hmac() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
 
-        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Update state with `data`. This can be called multiple times.
-        pub fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
-            self._state._update(data)
-        }
+                for len in [0, 1, 64 - 1, 64, 64 + 1, 300] {
+                    let tag = HmacSha256::hmac(&key, &data[..len]).unwrap();
+                    let array = HmacSha256::hmac_into_array(&key, &data[..len]).unwrap();
 
-        /// Return a HMAC-SHA384 tag.
-        pub(crate) fn _finalize_internal(
-            &mut self,
-            dest: &mut [u8],
-        ) -> Result<(), UnknownCryptoError> {
-            self._state._finalize(dest)
+                    assert_eq!(tag.unprotected_as_bytes(), &array[..]);
+                }
+            }
         }
 
-        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Return a HMAC-SHA384 tag.
-        pub fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
-            let mut dest = [0u8; sha384::SHA384_OUTSIZE];
-            self._finalize_internal(&mut dest)?;
+        #[cfg(feature = "safe_api")]
+        mod test_finalize_hex {
+            use super::*;
 
-            Ok(Tag::from(dest))
-        }
+            #[test]
+            fn test_finalize_hex_matches_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new(&key);
+                state.update(b"Some message.").unwrap();
+                let tag = state.finalize().unwrap();
 
-        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// One-shot function for generating an HMAC-SHA384 tag of `data`.
-        pub fn hmac(secret_key: &SecretKey, data: &[u8]) -> Result<Tag, UnknownCryptoError> {
-            let mut ctx = Self::new(secret_key);
-            ctx.update(data)?;
-            ctx.finalize()
+                state.reset();
+                state.update(b"Some message.").unwrap();
+                let hex = state.finalize_hex().unwrap();
+
+                let expected: String = tag
+                    .unprotected_as_bytes()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                assert_eq!(hex, expected);
+                assert_eq!(hex.len(), tag.len() * 2);
+                // The state was finalized.
+                assert!(state.finalize_hex().is_err());
+            }
         }
 
-        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Verify a HMAC-SHA384 tag in constant time.
-        pub fn verify(
-            expected: &Tag,
-            secret_key: &SecretKey,
-            data: &[u8],
-        ) -> Result<(), UnknownCryptoError> {
-            if &Self::hmac(secret_key, data)? == expected {
-                Ok(())
-            } else {
-                Err(UnknownCryptoError)
+        #[cfg(feature = "safe_api")]
+        mod test_update_as_ref {
+            use super::*;
+
+            #[test]
+            fn test_update_str_vec_array() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let expected = HmacSha256::hmac(&key, &[b'a'; 16]).unwrap();
+
+                let mut state = HmacSha256::new(&key);
+                state.update("aaaaaaaa").unwrap();
+                state.update(vec![b'a'; 4]).unwrap();
+                state.update([b'a'; 4]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                let data = vec![b'a'; 16];
+                state.reset();
+                state.update(&data).unwrap();
+                state.update(&data[..0]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
             }
         }
-    }
 
-    impl HmacFunction for HmacSha384 {
-        /// The output size of the internal hash function used.
-        const HASH_FUNC_OUTSIZE: usize = sha384::SHA384_OUTSIZE;
+        mod test_update_ad {
+            use super::*;
 
-        /// Create a new instance of the HMAC function, using a `secret_key` that may or may not be padded.
-        fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
-            Self::_new(secret_key)
-        }
+            fn tag_of(ad: &[&[u8]], message: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new_with_ad(&key);
+                for frame in ad {
+                    state.update_ad(frame).unwrap();
+                }
+                state.update(message).unwrap();
+                state.finalize().unwrap()
+            }
 
-        /// Update the internal state with `data`.
-        fn _update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
-            self._state._update(data)
-        }
+            /// Standard HMAC of `encoded`, under the key that `new_with_ad()` derives.
+            fn raw_tag_of(encoded: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let ad_key = crate::hazardous::kdf::hkdf::sha256::extract(
+                    b"orion-hmac-ad",
+                    key.unprotected_as_bytes(),
+                )
+                .unwrap();
+                let ad_key = SecretKey::from_slice_padded(ad_key.unprotected_as_bytes()).unwrap();
+                HmacSha256::hmac(&ad_key, encoded).unwrap()
+            }
 
-        /// Finalize the MAC and put the final tag into `dest`.
-        ///
-        /// NOTE: `dest` may be less than the complete output size of the hash function
-        /// (Self::HASH_FUNC_OUTSIZE). If that is the case, `dest.len()` bytes will be copied,
-        /// but `dest` should NEVER be empty.
-        fn _finalize(&mut self, dest: &mut [u8]) -> Result<(), UnknownCryptoError> {
-            self._state._finalize(dest)
+            #[test]
+            fn test_encoding() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut encoded = [0u8; 1 + 8 + 2 + 1 + 3];
+                encoded[9..11].copy_from_slice(b"ad");
+                encoded[1..9].copy_from_slice(&2u64.to_le_bytes());
+                encoded[11] = 1;
+                encoded[12..].copy_from_slice(b"msg");
+
+                assert_eq!(tag_of(&[b"ad"], b"msg"), raw_tag_of(&encoded));
+                assert_eq!(tag_of(&[], b"msg"), raw_tag_of(&encoded[11..]));
+
+                // Associated data without a message still ends with the marker.
+                let mut state = HmacSha256::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                assert_eq!(state.finalize().unwrap(), raw_tag_of(&encoded[..12]));
+                assert_eq!(state.bytes_processed(), 12);
+
+                // Never a plain HMAC tag under the same key, whatever it is computed over.
+                assert_ne!(
+                    tag_of(&[b"ad"], b"msg"),
+                    HmacSha256::hmac(&key, &encoded).unwrap()
+                );
+                assert_ne!(tag_of(&[], b"msg"), HmacSha256::hmac(&key, b"msg").unwrap());
+                assert_ne!(
+                    tag_of(&[], b"msg"),
+                    HmacSha256::hmac(&key, &encoded[11..]).unwrap()
+                );
+                assert_ne!(tag_of(&[], b""), HmacSha256::hmac(&key, b"").unwrap());
+                assert_ne!(tag_of(&[], b""), HmacSha256::hmac(&key, &[1u8]).unwrap());
+            }
+
+            #[test]
+            fn test_boundaries_not_ambiguous() {
+                let mut frame_of_b = [0u8; 1 + 8 + 1];
+                frame_of_b[1..9].copy_from_slice(&1u64.to_le_bytes());
+                frame_of_b[9] = b'b';
+
+                assert_ne!(tag_of(&[b"a", b"b"], b""), tag_of(&[b"a"], &frame_of_b));
+                assert_ne!(tag_of(&[b"ab"], b""), tag_of(&[b"a"], b"b"));
+                assert_ne!(tag_of(&[b""], b"msg"), tag_of(&[], b"msg"));
+                assert_ne!(tag_of(&[b"", b""], b""), tag_of(&[b""], b""));
+                assert_ne!(tag_of(&[b"ad"], b"msg"), tag_of(&[b"msg"], b"ad"));
+            }
+
+            #[test]
+            fn test_update_ad_without_ad_mode_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                assert!(state.update_ad(b"").is_err());
+
+                state.reset_with_key(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                state.update(b"msg").unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha256::hmac(&key, b"msg").unwrap()
+                );
+            }
+
+            #[test]
+            fn test_finalize_and_init_keeps_mode() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                let (_, mut state) = state.finalize_and_init(&key).unwrap();
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                let (_, mut state) = HmacSha256::new(&key).finalize_and_init(&key).unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+            }
+
+            #[test]
+            fn test_update_ad_after_update_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha256::new_with_ad(&key);
+                state.update_ad(b"first").unwrap();
+                state.update_ad(b"second").unwrap();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"third").is_err());
+                state.update(b"more msg").unwrap();
+                let _ = state.finalize().unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update_ad(b"ad").unwrap();
+                let mut ad_first = state.clone();
+                ad_first.update(b"msg").unwrap();
+                assert_eq!(ad_first.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                // Resetting with a key keeps the associated data mode.
+                state.reset_with_key(&key);
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+            }
         }
 
-        /// Reset the state.
-        fn _reset(&mut self) {
-            self._state._reset()
+        mod test_verify_any {
+            use super::*;
+
+            #[test]
+            fn test_verify_any_second_key() {
+                let data = b"Some message.";
+                let previous = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let current = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let other = SecretKey::from_slice_padded(&[3u8; 32]).unwrap();
+                let tag = HmacSha256::hmac(&current, data).unwrap();
+
+                assert!(HmacSha256::verify(&tag, &previous, data).is_err());
+                assert!(HmacSha256::verify_any(&tag, &[&previous, &current], data).is_ok());
+                assert!(HmacSha256::verify_any(&tag, &[&current, &previous], data).is_ok());
+                assert!(HmacSha256::verify_any(&tag, &[&current], data).is_ok());
+                assert!(HmacSha256::verify_any(&tag, &[&previous, &other], data).is_err());
+                assert!(
+                    HmacSha256::verify_any(&tag, &[&previous, &current], b"Other message.")
+                        .is_err()
+                );
+                assert!(HmacSha256::verify_any(&tag, &[], data).is_err());
+            }
         }
-    }
 
-    #[cfg(test)]
-    mod public {
-        use super::*;
+        mod test_verify_slices {
+            use super::*;
 
-        #[test]
-        #[cfg(feature = "safe_api")]
-        fn test_debug_impl() {
-            let secret_key = SecretKey::generate();
-            let initial_state = HmacSha384::new(&secret_key);
-            let debug = format!("{:?}", initial_state);
-            let expected = "HmacSha384 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], is_finalized: false } }";
-            assert_eq!(debug, expected);
+            #[test]
+            fn test_verify_slices_same_as_verify() {
+                let data = b"Some message.";
+                for key_len in [0, 1, 32, 64, 64 + 1, 300] {
+                    let raw_key = &[1u8; 300][..key_len];
+                    let key = SecretKey::from_slice_padded(raw_key).unwrap();
+                    let tag = HmacSha256::hmac(&key, data).unwrap();
+
+                    assert!(
+                        HmacSha256::verify_slices(tag.unprotected_as_bytes(), raw_key, data)
+                            .is_ok()
+                    );
+                    assert!(HmacSha256::verify(&tag, &key, data).is_ok());
+                }
+            }
+
+            #[test]
+            fn test_verify_slices_tampered() {
+                let raw_key = [1u8; 300];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha256::hmac(&key, data).unwrap();
+
+                let mut bad_tag = [0u8; 32];
+                bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+                bad_tag[0] ^= 1;
+                assert!(HmacSha256::verify_slices(&bad_tag, &raw_key, data).is_err());
+                assert!(
+                    HmacSha256::verify_slices(tag.unprotected_as_bytes(), &raw_key[1..], data)
+                        .is_err()
+                );
+                assert!(HmacSha256::verify_slices(
+                    tag.unprotected_as_bytes(),
+                    &raw_key,
+                    b"Some message!"
+                )
+                .is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_wrong_tag_length() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha256::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert!(HmacSha256::verify_slices(&[], &raw_key, data).is_err());
+                assert!(HmacSha256::verify_slices(&tag[..32 - 1], &raw_key, data).is_err());
+                let mut long_tag = [0u8; 32 + 1];
+                long_tag[..32].copy_from_slice(tag);
+                assert!(HmacSha256::verify_slices(&long_tag, &raw_key, data).is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_detailed() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha256::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert_eq!(
+                    HmacSha256::verify_slices_detailed(tag, &raw_key, data),
+                    Ok(())
+                );
+                assert_eq!(
+                    HmacSha256::verify_slices_detailed(&[], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+                assert_eq!(
+                    HmacSha256::verify_slices_detailed(&tag[..32 - 1], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+
+                let mut bad_tag = [0u8; 32];
+                bad_tag.copy_from_slice(tag);
+                bad_tag[32 - 1] ^= 1;
+                assert_eq!(
+                    HmacSha256::verify_slices_detailed(&bad_tag, &raw_key, data),
+                    Err(CryptoError::Validation)
+                );
+                assert_eq!(
+                    HmacSha256::verify_slices_detailed(tag, &raw_key, b"Other message."),
+                    Err(CryptoError::Validation)
+                );
+            }
         }
 
-        #[cfg(feature = "safe_api")]
-        mod test_verify {
+        mod test_finalize_verify {
             use super::*;
 
-            #[quickcheck]
-            #[cfg(feature = "safe_api")]
-            /// When using a different key, verify() should always yield an error.
-            /// NOTE: Using different and same input data is tested with TestableStreamingContext.
-            fn prop_verify_diff_key_false(data: Vec<u8>) -> bool {
-                let sk = SecretKey::generate();
-                let mut state = HmacSha384::new(&sk);
-                state.update(&data[..]).unwrap();
-                let tag = state.finalize().unwrap();
-                let bad_sk = SecretKey::generate();
+            #[test]
+            fn test_finalize_verify_ok() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha256::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha256::new(&key);
+                state.update(&data[..40]).unwrap();
+                state.update(&data[40..]).unwrap();
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+                // The state has been finalized.
+                assert!(state.finalize_verify(&expected, 100).is_err());
+            }
 
-                HmacSha384::verify(&tag, &bad_sk, &data[..]).is_err()
+            #[test]
+            fn test_finalize_verify_length_mismatch() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha256::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha256::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&expected, 99).is_err());
+                assert!(state.finalize_verify(&expected, 101).is_err());
+                // Rejecting on length does not finalize the state.
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+            }
+
+            #[test]
+            fn test_finalize_verify_bad_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let bad = HmacSha256::hmac(&key, &data[..99]).unwrap();
+
+                let mut state = HmacSha256::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&bad, 100).is_err());
+            }
+        }
+
+        mod test_oneshot_small {
+            use super::*;
+
+            #[test]
+            /// The small-message path of `hmac()` must agree with the streaming interface,
+            /// on both sides of the blocksize boundary.
+            fn test_oneshot_equals_streaming() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let data = [255u8; sha256::SHA256_BLOCKSIZE * 2];
+
+                for len in 0..=data.len() {
+                    let mut state = HmacSha256::new(&sk);
+                    state.update(&data[..len]).unwrap();
+
+                    assert_eq!(
+                        HmacSha256::hmac(&sk, &data[..len]).unwrap(),
+                        state.finalize().unwrap()
+                    );
+                }
             }
         }
 
@@ -629,7 +1567,7 @@ pub mod sha384 {
 
             const KEY: [u8; 32] = [0u8; 32];
 
-            impl TestableStreamingContext<Tag> for HmacSha384 {
+            impl TestableStreamingContext<Tag> for HmacSha256 {
                 fn reset(&mut self) -> Result<(), UnknownCryptoError> {
                     self.reset();
                     Ok(())
@@ -644,27 +1582,31 @@ pub mod sha384 {
                 }
 
                 fn one_shot(input: &[u8]) -> Result<Tag, UnknownCryptoError> {
-                    HmacSha384::hmac(&SecretKey::from_slice(&KEY).unwrap(), input)
+                    HmacSha256::hmac(&SecretKey::from_slice_padded(&KEY).unwrap(), input)
                 }
 
                 fn verify_result(expected: &Tag, input: &[u8]) -> Result<(), UnknownCryptoError> {
                     // This will only run verification tests on differing input. They do not
                     // include tests for different secret keys.
-                    HmacSha384::verify(expected, &SecretKey::from_slice(&KEY).unwrap(), input)
+                    HmacSha256::verify(
+                        expected,
+                        &SecretKey::from_slice_padded(&KEY).unwrap(),
+                        input,
+                    )
                 }
 
-                fn compare_states(state_1: &HmacSha384, state_2: &HmacSha384) {
+                fn compare_states(state_1: &HmacSha256, state_2: &HmacSha256) {
                     state_1._state.compare_state_to_other(&state_2._state);
                 }
             }
 
             #[test]
             fn default_consistency_tests() {
-                let initial_state = HmacSha384::new(&SecretKey::from_slice(&KEY).unwrap());
+                let initial_state = HmacSha256::new(&SecretKey::from_slice_padded(&KEY).unwrap());
 
-                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha384>::new(
+                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha256>::new(
                     initial_state,
-                    sha384::SHA384_BLOCKSIZE,
+                    sha256::SHA256_BLOCKSIZE,
                 );
                 test_runner.run_all_tests();
             }
@@ -674,11 +1616,11 @@ pub mod sha384 {
             /// Related bug: https://github.com/orion-rs/orion/issues/46
             /// Test different streaming state usage patterns.
             fn prop_input_to_consistency(data: Vec<u8>) -> bool {
-                let initial_state = HmacSha384::new(&SecretKey::from_slice(&KEY).unwrap());
+                let initial_state = HmacSha256::new(&SecretKey::from_slice_padded(&KEY).unwrap());
 
-                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha384>::new(
+                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha256>::new(
                     initial_state,
-                    sha384::SHA384_BLOCKSIZE,
+                    sha256::SHA256_BLOCKSIZE,
                 );
                 test_runner.run_all_tests_property(&data);
                 true
@@ -687,16 +1629,18 @@ pub mod sha384 {
     }
 }
 
-/// HMAC-SHA512 (Hash-based Message Authentication Code) as specified in the [RFC 2104](https://tools.ietf.org/html/rfc2104).
-pub mod sha512 {
+/// HMAC-SHA384 (Hash-based Message Authentication Code) as specified in the [RFC 2104](https://tools.ietf.org/html/rfc2104).
+pub mod sha384 {
     use super::*;
-    use crate::hazardous::hash::sha2::sha512::{self, Sha512};
+    use crate::hazardous::hash::sha2::sha384::{self, Sha384};
 
     construct_hmac_key! {
         /// A type to represent the `SecretKey` that HMAC uses for authentication.
         ///
         /// # Note:
-        /// `SecretKey` pads the secret key for use with HMAC to a length of 128, when initialized.
+        /// `from_slice()` requires a secret key of exactly 128 bytes. `from_slice_padded()` accepts
+        /// any length and pads (or hashes, if longer) the secret key for use with HMAC to a length
+        /// of 128, when initialized.
         ///
         /// Using `unprotected_as_bytes()` will return the secret key with padding.
         ///
@@ -705,7 +1649,7 @@ pub mod sha512 {
         /// # Panics:
         /// A panic will occur if:
         /// - Failure to generate random bytes securely.
-        (SecretKey, Sha512, sha512::SHA512_OUTSIZE, test_hmac_key, sha512::SHA512_BLOCKSIZE)
+        (SecretKey, Sha384, sha384::SHA384_OUTSIZE, test_hmac_key, sha384::SHA384_BLOCKSIZE)
     }
 
     construct_tag! {
@@ -713,46 +1657,119 @@ pub mod sha512 {
         ///
         /// # Errors:
         /// An error will be returned if:
-        /// - `slice` is not 64 bytes.
-        (Tag, test_tag, sha512::SHA512_OUTSIZE, sha512::SHA512_OUTSIZE)
+        /// - `slice` is not 48 bytes.
+        (Tag, test_tag, sha384::SHA384_OUTSIZE, sha384::SHA384_OUTSIZE)
     }
 
-    impl_from_trait!(Tag, sha512::SHA512_OUTSIZE);
+    impl_from_trait!(@fixed_bytes Tag, sha384::SHA384_OUTSIZE);
+
+    impl Tag {
+        /// Return the XOR of this tag and `other`, computed over all bytes.
+        ///
+        /// This is only meant for protocols that combine tags this way, such as for
+        /// aggregate verification. XORing tags is not a secure MAC on its own.
+        pub fn xor(&self, other: &Tag) -> Tag {
+            Tag {
+                bytes: self.bytes.xor(&other.bytes),
+            }
+        }
+    }
 
     use super::Hmac;
 
     #[derive(Clone, Debug)]
-    /// HMAC-SHA512 streaming state.
-    pub struct HmacSha512 {
-        _state: Hmac<Sha512, { sha512::SHA512_BLOCKSIZE }>,
+    /// HMAC-SHA384 streaming state.
+    pub struct HmacSha384 {
+        _state: Hmac<Sha384, { sha384::SHA384_BLOCKSIZE }>,
     }
 
-    impl HmacSha512 {
+    impl_extend_trait!(HmacSha384);
+
+    impl HmacSha384 {
         fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
             Ok(Self {
-                _state: Hmac::<Sha512, { sha512::SHA512_BLOCKSIZE }>::_new(secret_key)?,
+                _state: Hmac::<Sha384, { sha384::SHA384_BLOCKSIZE }>::_new(secret_key)?,
             })
         }
 
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Initialize `HmacSha512` struct with a given key.
+        /// Initialize `HmacSha384` struct with a given key.
         pub fn new(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new() called with a secret key shorter than 64 bytes");
+            }
             // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
             Self::_new(secret_key.unprotected_as_bytes()).unwrap()
         }
 
-        /// Reset to `new()` state.
-        pub fn reset(&mut self) {
-            self._state._reset()
-        }
-
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Update state with `data`. This can be called multiple times.
-        pub fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
-            self._state._update(data)
+        /// Initialize `HmacSha384` struct with a given key, for authenticating associated data
+        /// with [`Self::update_ad()`] before the message.
+        ///
+        /// The state runs standard HMAC-SHA384 under a key derived from `secret_key`
+        /// with HKDF-Extract, using `"orion-hmac-ad"` as salt. Its tags are therefore
+        /// unrelated to those of [`Self::new()`] under the same key.
+        pub fn new_with_ad(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new_with_ad() called with a secret key shorter than 64 bytes");
+            }
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            Self {
+                _state: Hmac::<Sha384, { sha384::SHA384_BLOCKSIZE }>::_new_with_ad(
+                    secret_key.unprotected_as_bytes(),
+                )
+                .unwrap(),
+            }
         }
 
-        /// Return a HMAC-SHA512 tag.
+        /// Reset to the state returned by `new()` or `new_with_ad()`, whichever
+        /// created this state.
+        pub fn reset(&mut self) {
+            self._state._reset()
+        }
+
+        /// Replace the key with `secret_key` and reset to the state `new()` or
+        /// `new_with_ad()`, whichever created this state, would return for it. Previous
+        /// key material in the state is overwritten.
+        pub fn reset_with_key(&mut self, secret_key: &SecretKey) {
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            self._state
+                ._reset_with_key(secret_key.unprotected_as_bytes())
+                .unwrap()
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Update state with `data`. This can be called multiple times.
+        ///
+        /// `data` can be anything that is viewable as bytes, such as `&str`, `Vec<u8>`
+        /// or a byte array.
+        pub fn update(&mut self, data: impl AsRef<[u8]>) -> Result<(), UnknownCryptoError> {
+            self._state._update(data.as_ref())
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Update state with associated data `ad`. This can be called multiple times,
+        /// but only on a state created with [`Self::new_with_ad()`] and before the first
+        /// call to [`Self::update()`].
+        ///
+        /// Each `ad` is authenticated as `0x00 || LE64(ad.len()) || ad`, and a single
+        /// `0x01` byte is authenticated after the last one, so the boundaries between
+        /// the associated data and the message cannot be shifted. The `0x01` byte is
+        /// also authenticated if this is never called.
+        pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), UnknownCryptoError> {
+            self._state._update_ad(ad)
+        }
+
+        /// Return the number of bytes passed to [`Self::update()`] since
+        /// [`Self::new()`] or the last [`Self::reset()`]. This includes the framing
+        /// added by [`Self::update_ad()`].
+        pub fn bytes_processed(&self) -> u64 {
+            self._state.bytes_processed
+        }
+
+        /// Return a HMAC-SHA384 tag.
         pub(crate) fn _finalize_internal(
             &mut self,
             dest: &mut [u8],
@@ -761,24 +1778,104 @@ pub mod sha512 {
         }
 
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Return a HMAC-SHA512 tag.
+        /// Return a HMAC-SHA384 tag.
         pub fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
-            let mut dest = [0u8; sha512::SHA512_OUTSIZE];
+            let mut dest = [0u8; sha384::SHA384_OUTSIZE];
             self._finalize_internal(&mut dest)?;
 
             Ok(Tag::from(dest))
         }
 
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// One-shot function for generating an HMAC-SHA512 tag of `data`.
+        /// Return a tag, together with the number of bytes it authenticates.
+        pub fn finalize_with_len(&mut self) -> Result<(Tag, u64), UnknownCryptoError> {
+            let tag = self.finalize()?;
+
+            Ok((tag, self.bytes_processed()))
+        }
+
+        #[cfg(any(feature = "safe_api", feature = "alloc"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a HMAC-SHA384 tag as a lowercase hex string. This finalizes the state,
+        /// the same as [`Self::finalize()`].
+        pub fn finalize_hex(&mut self) -> Result<String, UnknownCryptoError> {
+            Ok(crate::util::to_hex(self.finalize()?.unprotected_as_bytes()))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a tag, together with the state re-keyed with `secret_key`, as
+        /// [`Self::new()`] or [`Self::new_with_ad()`], whichever created this state,
+        /// would return it.
+        ///
+        /// This is meant for authenticating the next message under a new key right
+        /// away. The state is reused, and the previous key material in it is overwritten.
+        pub fn finalize_and_init(
+            mut self,
+            secret_key: &SecretKey,
+        ) -> Result<(Tag, Self), UnknownCryptoError> {
+            let tag = self.finalize()?;
+            self.reset_with_key(secret_key);
+
+            Ok((tag, self))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Finalize the state and verify the result against `expected` in constant time.
+        ///
+        /// If [`Self::bytes_processed()`] differs from `expected_len`, an error is
+        /// returned immediately, without finalizing the state. The length of the
+        /// input is not secret, so this check does not need to be constant time.
+        pub fn finalize_verify(
+            &mut self,
+            expected: &Tag,
+            expected_len: u64,
+        ) -> Result<(), UnknownCryptoError> {
+            if self.bytes_processed() != expected_len {
+                return Err(UnknownCryptoError);
+            }
+
+            if &self.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// One-shot function for generating an HMAC-SHA384 tag of `data`.
         pub fn hmac(secret_key: &SecretKey, data: &[u8]) -> Result<Tag, UnknownCryptoError> {
-            let mut ctx = Self::new(secret_key);
-            ctx.update(data)?;
-            ctx.finalize()
+            Ok(Tag::from(Self::hmac_into_array(secret_key, data)?))
         }
 
         #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-        /// Verify a HMAC-SHA512 tag in constant time.
+        /// One-shot function for generating an HMAC-SHA384 tag of `data`, returned as an array.
+        ///
+        /// Only the stack is used, so this is also available in `no_std` context. The bytes
+        /// are the same as those of the `Tag` returned by [`Self::hmac()`].
+        pub fn hmac_into_array(
+            secret_key: &SecretKey,
+            data: &[u8],
+        ) -> Result<[u8; sha384::SHA384_OUTSIZE], UnknownCryptoError> {
+            let mut dest = [0u8; sha384::SHA384_OUTSIZE];
+
+            if data.len() < sha384::SHA384_BLOCKSIZE {
+                Hmac::<Sha384, { sha384::SHA384_BLOCKSIZE }>::_oneshot_small(
+                    secret_key.unprotected_as_bytes(),
+                    data,
+                    &mut dest,
+                )?;
+            } else {
+                let mut ctx = Self::new(secret_key);
+                ctx.update(data)?;
+                ctx._finalize_internal(&mut dest)?;
+            }
+
+            Ok(dest)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA384 tag in constant time.
         pub fn verify(
             expected: &Tag,
             secret_key: &SecretKey,
@@ -790,11 +1887,133 @@ pub mod sha512 {
                 Err(UnknownCryptoError)
             }
         }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA384 tag in constant time, accepting it if it is valid under
+        /// any of `secret_keys`. This is meant for key rotation, where tags made with
+        /// the previous key are accepted for a while.
+        ///
+        /// A tag is computed and compared for every key, without returning early, so the
+        /// time taken does not reveal which key matched. An error is returned if
+        /// `secret_keys` is empty.
+        pub fn verify_any(
+            expected: &Tag,
+            secret_keys: &[&SecretKey],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            use subtle::ConstantTimeEq;
+
+            let mut matched = subtle::Choice::from(0u8);
+            for secret_key in secret_keys {
+                let tag = Self::hmac(secret_key, data)?;
+                matched |= tag
+                    .unprotected_as_bytes()
+                    .ct_eq(expected.unprotected_as_bytes());
+            }
+
+            if bool::from(matched) {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA384 tag in constant time, given the tag and secret key as raw bytes.
+        ///
+        /// `secret_key` may be of any length and is padded, or hashed if longer than the
+        /// blocksize, as specified in RFC 2104. An error is returned if `expected` is not
+        /// 48 bytes.
+        pub fn verify_slices(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            // The length of a tag is not secret, so a truncated or otherwise malformed
+            // tag can be told apart from a mismatch. The error is the same either way.
+            if expected.len() != sha384::SHA384_OUTSIZE {
+                log_misuse!(
+                    "HMAC: verify_slices() called with an expected tag of the wrong length"
+                );
+                return Err(UnknownCryptoError);
+            }
+            let expected = Tag::from_slice(expected)?;
+            let secret_key = SecretKey::from_slice_padded(secret_key)?;
+
+            Self::verify(&expected, &secret_key, data)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA384 tag in constant time, given the tag and secret key as raw
+        /// bytes, telling a malformed tag apart from one that does not match.
+        ///
+        /// This is the same as [`Self::verify_slices()`], except for the error returned:
+        /// - [`CryptoError::InvalidInput`] if `expected` is not 48 bytes. The length
+        ///   of a tag is not secret.
+        /// - [`CryptoError::Validation`] if `expected` does not match. Its contents are
+        ///   still compared in constant time.
+        pub fn verify_slices_detailed(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), CryptoError> {
+            if expected.len() != sha384::SHA384_OUTSIZE {
+                return Err(CryptoError::InvalidInput);
+            }
+
+            Self::verify_slices(expected, secret_key, data).map_err(|_| CryptoError::Validation)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA384 tag in constant time, over the concatenation of `chunks`.
+        pub fn verify_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(
+            expected: &Tag,
+            secret_key: &SecretKey,
+            chunks: I,
+        ) -> Result<(), UnknownCryptoError> {
+            let mut ctx = Self::new(secret_key);
+            for chunk in chunks {
+                ctx.update(chunk)?;
+            }
+
+            if &ctx.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
     }
 
-    impl HmacFunction for HmacSha512 {
+    impl crate::hazardous::mac::sealed::Sealed for HmacSha384 {}
+
+    impl crate::hazardous::mac::Mac for HmacSha384 {
+        type Key = SecretKey;
+        type Tag = Tag;
+
+        fn new(key: &SecretKey) -> Self {
+            HmacSha384::new(key)
+        }
+
+        fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha384::update(self, data)
+        }
+
+        fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+            HmacSha384::finalize(self)
+        }
+
+        fn reset_with_key(&mut self, key: &SecretKey) {
+            HmacSha384::reset_with_key(self, key)
+        }
+
+        fn verify(expected: &Tag, key: &SecretKey, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha384::verify(expected, key, data)
+        }
+    }
+
+    impl HmacFunction for HmacSha384 {
         /// The output size of the internal hash function used.
-        const HASH_FUNC_OUTSIZE: usize = sha512::SHA512_OUTSIZE;
+        const HASH_FUNC_OUTSIZE: usize = sha384::SHA384_OUTSIZE;
 
         /// Create a new instance of the HMAC function, using a `secret_key` that may or may not be padded.
         fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
@@ -829,9 +2048,9 @@ pub mod sha512 {
         #[cfg(feature = "safe_api")]
         fn test_debug_impl() {
             let secret_key = SecretKey::generate();
-            let initial_state = HmacSha512::new(&secret_key);
+            let initial_state = HmacSha384::new(&secret_key);
             let debug = format!("{:?}", initial_state);
-            let expected = "HmacSha512 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], is_finalized: false } }";
+            let expected = "HmacSha384 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], bytes_processed: 0, is_finalized: false } }";
             assert_eq!(debug, expected);
         }
 
@@ -845,12 +2064,2255 @@ pub mod sha512 {
             /// NOTE: Using different and same input data is tested with TestableStreamingContext.
             fn prop_verify_diff_key_false(data: Vec<u8>) -> bool {
                 let sk = SecretKey::generate();
-                let mut state = HmacSha512::new(&sk);
+                let mut state = HmacSha384::new(&sk);
                 state.update(&data[..]).unwrap();
                 let tag = state.finalize().unwrap();
                 let bad_sk = SecretKey::generate();
 
-                HmacSha512::verify(&tag, &bad_sk, &data[..]).is_err()
+                HmacSha384::verify(&tag, &bad_sk, &data[..]).is_err()
+            }
+        }
+
+        mod test_tag_xor {
+            use super::*;
+
+            #[test]
+            fn test_xor_is_involution() {
+                let a = Tag::from([1u8; sha384::SHA384_OUTSIZE]);
+                let b: [u8; sha384::SHA384_OUTSIZE] = core::array::from_fn(|i| i as u8);
+                let b = Tag::from(b);
+
+                assert_ne!(a.xor(&b), a);
+                assert_eq!(a.xor(&b).xor(&b), a);
+                assert_eq!(a.xor(&b), b.xor(&a));
+                assert_eq!(a.xor(&a), Tag::from([0u8; sha384::SHA384_OUTSIZE]));
+            }
+        }
+
+        mod test_extend {
+            use super::*;
+
+            #[test]
+            fn test_extend_same_as_update() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+
+                for len in [0, 1, 63, 64, 65, 128, 300] {
+                    let mut expected = HmacSha384::new(&key);
+                    expected.update(&data[..len]).unwrap();
+
+                    let mut state = HmacSha384::new(&key);
+                    state.extend(data[..len].iter().copied());
+                    assert_eq!(state.bytes_processed(), len as u64);
+                    let mut state_ref = HmacSha384::new(&key);
+                    state_ref.extend(data[..len].iter());
+
+                    let expected = expected.finalize().unwrap();
+                    assert_eq!(state.finalize().unwrap(), expected);
+                    assert_eq!(state_ref.finalize().unwrap(), expected);
+                }
+            }
+
+            #[test]
+            fn test_extend_after_finalize_ignored() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new(&key);
+                let _ = state.finalize().unwrap();
+                state.extend([0u8; 100]);
+                assert_eq!(state.bytes_processed(), 0);
+                assert!(state.finalize().is_err());
+            }
+        }
+
+        mod test_reset_with_key {
+            use super::*;
+
+            #[test]
+            fn test_reset_with_key_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut fresh = HmacSha384::new(&new_key);
+                fresh.update(data).unwrap();
+                let expected = fresh.finalize().unwrap();
+
+                let mut state = HmacSha384::new(&old_key);
+                state.update(data).unwrap();
+                let old_tag = state.finalize().unwrap();
+                assert_ne!(old_tag, expected);
+
+                // Also from a finalized state.
+                state.reset_with_key(&new_key);
+                assert_eq!(state.bytes_processed(), 0);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                // A subsequent reset() keeps the new key.
+                state.reset();
+                state.update(&data[..50]).unwrap();
+                state.reset_with_key(&new_key);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+                state.reset();
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+            }
+        }
+
+        mod test_empty_input {
+            use super::*;
+
+            fn check_empty_input(key: &[u8], expected_hex: &str) {
+                let secret_key = SecretKey::from_slice_padded(key).unwrap();
+                let mut expected = [0u8; sha384::SHA384_OUTSIZE];
+                hex::decode_to_slice(expected_hex, &mut expected).unwrap();
+                let expected_tag = Tag::from_slice(&expected).unwrap();
+
+                assert_eq!(
+                    HmacSha384::hmac_into_array(&secret_key, &[]).unwrap(),
+                    expected
+                );
+                assert_eq!(HmacSha384::hmac(&secret_key, &[]).unwrap(), expected_tag);
+                assert!(HmacSha384::verify(&expected_tag, &secret_key, &[]).is_ok());
+                assert!(HmacSha384::verify(&expected_tag, &secret_key, &[0u8]).is_err());
+
+                // Streaming, with no or only empty updates.
+                let mut state = HmacSha384::new(&secret_key);
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+                state.reset();
+                state.update([]).unwrap();
+                state.update([]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+            }
+
+            #[test]
+            fn test_empty_input_kat() {
+                // Computed with Python's hmac module.
+                check_empty_input(
+                    b"",
+                    "6c1f2ee938fad2e24bd91298474382ca218c75db3d83e114b3d4367776d14d3551289e75e8209cd4b792302840234adc",
+                );
+                check_empty_input(
+                    b"key",
+                    "99f44bb4e73c9d0ef26533596c8d8a32a5f8c10a9b997d30d89a7e35ba1ccf200b985f72431202b891fe350da410e43f",
+                );
+            }
+        }
+
+        mod test_finalize_and_init {
+            use super::*;
+
+            #[test]
+            fn test_finalize_and_init_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut state = HmacSha384::new(&old_key);
+                state.update(data).unwrap();
+                let (tag, mut state) = state.finalize_and_init(&new_key).unwrap();
+                assert_eq!(tag, HmacSha384::hmac(&old_key, &data).unwrap());
+                state
+                    ._state
+                    .compare_state_to_other(&HmacSha384::new(&new_key)._state);
+
+                state.update(data).unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha384::hmac(&new_key, &data).unwrap()
+                );
+
+                // An already finalized state cannot be finalized again.
+                assert!(state.finalize_and_init(&old_key).is_err());
+            }
+        }
+
+        mod test_finalize_with_len {
+            use super::*;
+
+            #[test]
+            fn test_len_across_chunks() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
+
+                let mut state = HmacSha384::new(&key);
+                let mut expected_len = 0u64;
+                for chunk in data.chunks(7) {
+                    state.update(chunk).unwrap();
+                    expected_len += chunk.len() as u64;
+                }
+                state.update([]).unwrap();
+
+                let mut other = HmacSha384::new(&key);
+                other.update(data).unwrap();
+                let (tag, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, expected_len);
+                assert_eq!(len, 300);
+                assert_eq!(tag, other.finalize().unwrap());
+                assert!(state.finalize_with_len().is_err());
+
+                state.reset();
+                let (_, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, 0);
+            }
+        }
+
+        mod test_verify_chunks {
+            use super::*;
+
+            #[test]
+            fn test_verify_chunks_same_as_verify() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut data = [0u8; 300];
+                for (idx, byte) in data.iter_mut().enumerate() {
+                    *byte = idx as u8;
+                }
+                let tag = HmacSha384::hmac(&key, &data).unwrap();
+                assert!(HmacSha384::verify(&tag, &key, &data).is_ok());
+
+                for chunk_size in [1, 7, 64, 128, 299, 300, 301] {
+                    assert!(HmacSha384::verify_chunks(&tag, &key, data.chunks(chunk_size)).is_ok());
+                }
+                assert!(
+                    HmacSha384::verify_chunks(&tag, &key, [&data[..10], &[], &data[10..]]).is_ok()
+                );
+
+                // Missing, reordered and extra data.
+                assert!(HmacSha384::verify_chunks(&tag, &key, data.chunks(7).skip(1)).is_err());
+                assert!(HmacSha384::verify_chunks(&tag, &key, data.chunks(150).rev()).is_err());
+                assert!(HmacSha384::verify_chunks(&tag, &key, [&data[..], &[0u8]]).is_err());
+
+                let empty_tag = HmacSha384::hmac(&key, &[]).unwrap();
+                assert!(HmacSha384::verify_chunks(&empty_tag, &key, core::iter::empty()).is_ok());
+            }
+        }
+
+        mod test_hmac_into_array {
+            use super::*;
+
+            #[test]
+            // Does not depend on `safe_api`, so this also runs in `no_std` context.
+            fn test_hmac_into_array_same_as_hmac() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
+
+                for len in [0, 1, 128 - 1, 128, 128 + 1, 300] {
+                    let tag = HmacSha384::hmac(&key, &data[..len]).unwrap();
+                    let array = HmacSha384::hmac_into_array(&key, &data[..len]).unwrap();
+
+                    assert_eq!(tag.unprotected_as_bytes(), &array[..]);
+                }
+            }
+        }
+
+        #[cfg(feature = "safe_api")]
+        mod test_finalize_hex {
+            use super::*;
+
+            #[test]
+            fn test_finalize_hex_matches_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new(&key);
+                state.update(b"Some message.").unwrap();
+                let tag = state.finalize().unwrap();
+
+                state.reset();
+                state.update(b"Some message.").unwrap();
+                let hex = state.finalize_hex().unwrap();
+
+                let expected: String = tag
+                    .unprotected_as_bytes()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                assert_eq!(hex, expected);
+                assert_eq!(hex.len(), tag.len() * 2);
+                // The state was finalized.
+                assert!(state.finalize_hex().is_err());
+            }
+        }
+
+        #[cfg(feature = "safe_api")]
+        mod test_update_as_ref {
+            use super::*;
+
+            #[test]
+            fn test_update_str_vec_array() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let expected = HmacSha384::hmac(&key, &[b'a'; 16]).unwrap();
+
+                let mut state = HmacSha384::new(&key);
+                state.update("aaaaaaaa").unwrap();
+                state.update(vec![b'a'; 4]).unwrap();
+                state.update([b'a'; 4]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                let data = vec![b'a'; 16];
+                state.reset();
+                state.update(&data).unwrap();
+                state.update(&data[..0]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+            }
+        }
+
+        mod test_update_ad {
+            use super::*;
+
+            fn tag_of(ad: &[&[u8]], message: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new_with_ad(&key);
+                for frame in ad {
+                    state.update_ad(frame).unwrap();
+                }
+                state.update(message).unwrap();
+                state.finalize().unwrap()
+            }
+
+            /// Standard HMAC of `encoded`, under the key that `new_with_ad()` derives.
+            fn raw_tag_of(encoded: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let ad_key = crate::hazardous::kdf::hkdf::sha384::extract(
+                    b"orion-hmac-ad",
+                    key.unprotected_as_bytes(),
+                )
+                .unwrap();
+                let ad_key = SecretKey::from_slice_padded(ad_key.unprotected_as_bytes()).unwrap();
+                HmacSha384::hmac(&ad_key, encoded).unwrap()
+            }
+
+            #[test]
+            fn test_encoding() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut encoded = [0u8; 1 + 8 + 2 + 1 + 3];
+                encoded[9..11].copy_from_slice(b"ad");
+                encoded[1..9].copy_from_slice(&2u64.to_le_bytes());
+                encoded[11] = 1;
+                encoded[12..].copy_from_slice(b"msg");
+
+                assert_eq!(tag_of(&[b"ad"], b"msg"), raw_tag_of(&encoded));
+                assert_eq!(tag_of(&[], b"msg"), raw_tag_of(&encoded[11..]));
+
+                // Associated data without a message still ends with the marker.
+                let mut state = HmacSha384::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                assert_eq!(state.finalize().unwrap(), raw_tag_of(&encoded[..12]));
+                assert_eq!(state.bytes_processed(), 12);
+
+                // Never a plain HMAC tag under the same key, whatever it is computed over.
+                assert_ne!(
+                    tag_of(&[b"ad"], b"msg"),
+                    HmacSha384::hmac(&key, &encoded).unwrap()
+                );
+                assert_ne!(tag_of(&[], b"msg"), HmacSha384::hmac(&key, b"msg").unwrap());
+                assert_ne!(
+                    tag_of(&[], b"msg"),
+                    HmacSha384::hmac(&key, &encoded[11..]).unwrap()
+                );
+                assert_ne!(tag_of(&[], b""), HmacSha384::hmac(&key, b"").unwrap());
+                assert_ne!(tag_of(&[], b""), HmacSha384::hmac(&key, &[1u8]).unwrap());
+            }
+
+            #[test]
+            fn test_boundaries_not_ambiguous() {
+                let mut frame_of_b = [0u8; 1 + 8 + 1];
+                frame_of_b[1..9].copy_from_slice(&1u64.to_le_bytes());
+                frame_of_b[9] = b'b';
+
+                assert_ne!(tag_of(&[b"a", b"b"], b""), tag_of(&[b"a"], &frame_of_b));
+                assert_ne!(tag_of(&[b"ab"], b""), tag_of(&[b"a"], b"b"));
+                assert_ne!(tag_of(&[b""], b"msg"), tag_of(&[], b"msg"));
+                assert_ne!(tag_of(&[b"", b""], b""), tag_of(&[b""], b""));
+                assert_ne!(tag_of(&[b"ad"], b"msg"), tag_of(&[b"msg"], b"ad"));
+            }
+
+            #[test]
+            fn test_update_ad_without_ad_mode_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                assert!(state.update_ad(b"").is_err());
+
+                state.reset_with_key(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                state.update(b"msg").unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha384::hmac(&key, b"msg").unwrap()
+                );
+            }
+
+            #[test]
+            fn test_finalize_and_init_keeps_mode() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                let (_, mut state) = state.finalize_and_init(&key).unwrap();
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                let (_, mut state) = HmacSha384::new(&key).finalize_and_init(&key).unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+            }
+
+            #[test]
+            fn test_update_ad_after_update_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha384::new_with_ad(&key);
+                state.update_ad(b"first").unwrap();
+                state.update_ad(b"second").unwrap();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"third").is_err());
+                state.update(b"more msg").unwrap();
+                let _ = state.finalize().unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update_ad(b"ad").unwrap();
+                let mut ad_first = state.clone();
+                ad_first.update(b"msg").unwrap();
+                assert_eq!(ad_first.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                // Resetting with a key keeps the associated data mode.
+                state.reset_with_key(&key);
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+            }
+        }
+
+        mod test_verify_any {
+            use super::*;
+
+            #[test]
+            fn test_verify_any_second_key() {
+                let data = b"Some message.";
+                let previous = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let current = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let other = SecretKey::from_slice_padded(&[3u8; 32]).unwrap();
+                let tag = HmacSha384::hmac(&current, data).unwrap();
+
+                assert!(HmacSha384::verify(&tag, &previous, data).is_err());
+                assert!(HmacSha384::verify_any(&tag, &[&previous, &current], data).is_ok());
+                assert!(HmacSha384::verify_any(&tag, &[&current, &previous], data).is_ok());
+                assert!(HmacSha384::verify_any(&tag, &[&current], data).is_ok());
+                assert!(HmacSha384::verify_any(&tag, &[&previous, &other], data).is_err());
+                assert!(
+                    HmacSha384::verify_any(&tag, &[&previous, &current], b"Other message.")
+                        .is_err()
+                );
+                assert!(HmacSha384::verify_any(&tag, &[], data).is_err());
+            }
+        }
+
+        mod test_verify_slices {
+            use super::*;
+
+            #[test]
+            fn test_verify_slices_same_as_verify() {
+                let data = b"Some message.";
+                for key_len in [0, 1, 32, 128, 128 + 1, 300] {
+                    let raw_key = &[1u8; 300][..key_len];
+                    let key = SecretKey::from_slice_padded(raw_key).unwrap();
+                    let tag = HmacSha384::hmac(&key, data).unwrap();
+
+                    assert!(
+                        HmacSha384::verify_slices(tag.unprotected_as_bytes(), raw_key, data)
+                            .is_ok()
+                    );
+                    assert!(HmacSha384::verify(&tag, &key, data).is_ok());
+                }
+            }
+
+            #[test]
+            fn test_verify_slices_tampered() {
+                let raw_key = [1u8; 300];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha384::hmac(&key, data).unwrap();
+
+                let mut bad_tag = [0u8; 48];
+                bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+                bad_tag[0] ^= 1;
+                assert!(HmacSha384::verify_slices(&bad_tag, &raw_key, data).is_err());
+                assert!(
+                    HmacSha384::verify_slices(tag.unprotected_as_bytes(), &raw_key[1..], data)
+                        .is_err()
+                );
+                assert!(HmacSha384::verify_slices(
+                    tag.unprotected_as_bytes(),
+                    &raw_key,
+                    b"Some message!"
+                )
+                .is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_wrong_tag_length() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha384::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert!(HmacSha384::verify_slices(&[], &raw_key, data).is_err());
+                assert!(HmacSha384::verify_slices(&tag[..48 - 1], &raw_key, data).is_err());
+                let mut long_tag = [0u8; 48 + 1];
+                long_tag[..48].copy_from_slice(tag);
+                assert!(HmacSha384::verify_slices(&long_tag, &raw_key, data).is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_detailed() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha384::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert_eq!(
+                    HmacSha384::verify_slices_detailed(tag, &raw_key, data),
+                    Ok(())
+                );
+                assert_eq!(
+                    HmacSha384::verify_slices_detailed(&[], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+                assert_eq!(
+                    HmacSha384::verify_slices_detailed(&tag[..48 - 1], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+
+                let mut bad_tag = [0u8; 48];
+                bad_tag.copy_from_slice(tag);
+                bad_tag[48 - 1] ^= 1;
+                assert_eq!(
+                    HmacSha384::verify_slices_detailed(&bad_tag, &raw_key, data),
+                    Err(CryptoError::Validation)
+                );
+                assert_eq!(
+                    HmacSha384::verify_slices_detailed(tag, &raw_key, b"Other message."),
+                    Err(CryptoError::Validation)
+                );
+            }
+        }
+
+        mod test_finalize_verify {
+            use super::*;
+
+            #[test]
+            fn test_finalize_verify_ok() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha384::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha384::new(&key);
+                state.update(&data[..40]).unwrap();
+                state.update(&data[40..]).unwrap();
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+                // The state has been finalized.
+                assert!(state.finalize_verify(&expected, 100).is_err());
+            }
+
+            #[test]
+            fn test_finalize_verify_length_mismatch() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha384::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha384::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&expected, 99).is_err());
+                assert!(state.finalize_verify(&expected, 101).is_err());
+                // Rejecting on length does not finalize the state.
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+            }
+
+            #[test]
+            fn test_finalize_verify_bad_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let bad = HmacSha384::hmac(&key, &data[..99]).unwrap();
+
+                let mut state = HmacSha384::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&bad, 100).is_err());
+            }
+        }
+
+        mod test_oneshot_small {
+            use super::*;
+
+            #[test]
+            /// The small-message path of `hmac()` must agree with the streaming interface,
+            /// on both sides of the blocksize boundary.
+            fn test_oneshot_equals_streaming() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 128]).unwrap();
+                let data = [255u8; sha384::SHA384_BLOCKSIZE * 2];
+
+                for len in 0..=data.len() {
+                    let mut state = HmacSha384::new(&sk);
+                    state.update(&data[..len]).unwrap();
+
+                    assert_eq!(
+                        HmacSha384::hmac(&sk, &data[..len]).unwrap(),
+                        state.finalize().unwrap()
+                    );
+                }
+            }
+        }
+
+        mod test_streaming_interface {
+            use super::*;
+            use crate::test_framework::incremental_interface::*;
+
+            const KEY: [u8; 32] = [0u8; 32];
+
+            impl TestableStreamingContext<Tag> for HmacSha384 {
+                fn reset(&mut self) -> Result<(), UnknownCryptoError> {
+                    self.reset();
+                    Ok(())
+                }
+
+                fn update(&mut self, input: &[u8]) -> Result<(), UnknownCryptoError> {
+                    self.update(input)
+                }
+
+                fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+                    self.finalize()
+                }
+
+                fn one_shot(input: &[u8]) -> Result<Tag, UnknownCryptoError> {
+                    HmacSha384::hmac(&SecretKey::from_slice_padded(&KEY).unwrap(), input)
+                }
+
+                fn verify_result(expected: &Tag, input: &[u8]) -> Result<(), UnknownCryptoError> {
+                    // This will only run verification tests on differing input. They do not
+                    // include tests for different secret keys.
+                    HmacSha384::verify(
+                        expected,
+                        &SecretKey::from_slice_padded(&KEY).unwrap(),
+                        input,
+                    )
+                }
+
+                fn compare_states(state_1: &HmacSha384, state_2: &HmacSha384) {
+                    state_1._state.compare_state_to_other(&state_2._state);
+                }
+            }
+
+            #[test]
+            fn default_consistency_tests() {
+                let initial_state = HmacSha384::new(&SecretKey::from_slice_padded(&KEY).unwrap());
+
+                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha384>::new(
+                    initial_state,
+                    sha384::SHA384_BLOCKSIZE,
+                );
+                test_runner.run_all_tests();
+            }
+
+            #[quickcheck]
+            #[cfg(feature = "safe_api")]
+            /// Related bug: https://github.com/orion-rs/orion/issues/46
+            /// Test different streaming state usage patterns.
+            fn prop_input_to_consistency(data: Vec<u8>) -> bool {
+                let initial_state = HmacSha384::new(&SecretKey::from_slice_padded(&KEY).unwrap());
+
+                let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha384>::new(
+                    initial_state,
+                    sha384::SHA384_BLOCKSIZE,
+                );
+                test_runner.run_all_tests_property(&data);
+                true
+            }
+        }
+    }
+}
+
+/// HMAC-SHA512 (Hash-based Message Authentication Code) as specified in the [RFC 2104](https://tools.ietf.org/html/rfc2104).
+pub mod sha512 {
+    use super::*;
+    use crate::hazardous::hash::sha2::sha512::{self, Sha512};
+
+    construct_hmac_key! {
+        /// A type to represent the `SecretKey` that HMAC uses for authentication.
+        ///
+        /// # Note:
+        /// `from_slice()` requires a secret key of exactly 128 bytes. `from_slice_padded()` accepts
+        /// any length and pads (or hashes, if longer) the secret key for use with HMAC to a length
+        /// of 128, when initialized.
+        ///
+        /// Using `unprotected_as_bytes()` will return the secret key with padding.
+        ///
+        /// `len()` will return the length with padding (always 128).
+        ///
+        /// # Panics:
+        /// A panic will occur if:
+        /// - Failure to generate random bytes securely.
+        (SecretKey, Sha512, sha512::SHA512_OUTSIZE, test_hmac_key, sha512::SHA512_BLOCKSIZE)
+    }
+
+    construct_tag! {
+        /// A type to represent the `Tag` that HMAC returns.
+        ///
+        /// # Errors:
+        /// An error will be returned if:
+        /// - `slice` is not 64 bytes.
+        (Tag, test_tag, sha512::SHA512_OUTSIZE, sha512::SHA512_OUTSIZE)
+    }
+
+    impl_from_trait!(@fixed_bytes Tag, sha512::SHA512_OUTSIZE);
+
+    impl Tag {
+        /// Return the XOR of this tag and `other`, computed over all bytes.
+        ///
+        /// This is only meant for protocols that combine tags this way, such as for
+        /// aggregate verification. XORing tags is not a secure MAC on its own.
+        pub fn xor(&self, other: &Tag) -> Tag {
+            Tag {
+                bytes: self.bytes.xor(&other.bytes),
+            }
+        }
+    }
+
+    /// The size of a single exported SHA512 state.
+    const SHA512_STATESIZE: usize = sha512::SHA512_EXPORT_SIZE;
+
+    /// The size of an exported HMAC-SHA512 state.
+    pub const HMAC_SHA512_STATESIZE: usize = (3 * SHA512_STATESIZE) + 8 + 1;
+
+    construct_secret_key! {
+        /// A type to represent an exported `HmacSha512` streaming state.
+        ///
+        /// # Note:
+        /// The exported state contains values derived from the secret key. It must be
+        /// protected in the same way as the `SecretKey` itself.
+        ///
+        /// # Errors:
+        /// An error will be returned if:
+        /// - `slice` is not 660 bytes.
+        (ExportedState, test_exported_state, HMAC_SHA512_STATESIZE, HMAC_SHA512_STATESIZE)
+    }
+
+    impl_from_trait!(ExportedState, HMAC_SHA512_STATESIZE);
+
+    use super::Hmac;
+    use crate::hazardous::hash::sha2::sha2_core::State;
+
+    #[derive(Clone, Debug)]
+    /// HMAC-SHA512 streaming state.
+    pub struct HmacSha512 {
+        _state: Hmac<Sha512, { sha512::SHA512_BLOCKSIZE }>,
+    }
+
+    impl_extend_trait!(HmacSha512);
+
+    impl HmacSha512 {
+        fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
+            Ok(Self {
+                _state: Hmac::<Sha512, { sha512::SHA512_BLOCKSIZE }>::_new(secret_key)?,
+            })
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Initialize `HmacSha512` struct with a given key.
+        pub fn new(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new() called with a secret key shorter than 64 bytes");
+            }
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            Self::_new(secret_key.unprotected_as_bytes()).unwrap()
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Initialize `HmacSha512` struct with a given key, for authenticating associated data
+        /// with [`Self::update_ad()`] before the message.
+        ///
+        /// The state runs standard HMAC-SHA512 under a key derived from `secret_key`
+        /// with HKDF-Extract, using `"orion-hmac-ad"` as salt. Its tags are therefore
+        /// unrelated to those of [`Self::new()`] under the same key.
+        pub fn new_with_ad(secret_key: &SecretKey) -> Self {
+            #[cfg(feature = "log")]
+            if secret_key.is_weak() {
+                log_misuse!("HMAC: new_with_ad() called with a secret key shorter than 64 bytes");
+            }
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            Self {
+                _state: Hmac::<Sha512, { sha512::SHA512_BLOCKSIZE }>::_new_with_ad(
+                    secret_key.unprotected_as_bytes(),
+                )
+                .unwrap(),
+            }
+        }
+
+        /// Reset to the state returned by `new()` or `new_with_ad()`, whichever
+        /// created this state.
+        pub fn reset(&mut self) {
+            self._state._reset()
+        }
+
+        /// Replace the key with `secret_key` and reset to the state `new()` or
+        /// `new_with_ad()`, whichever created this state, would return for it. Previous
+        /// key material in the state is overwritten.
+        pub fn reset_with_key(&mut self, secret_key: &SecretKey) {
+            // NOTE: `secret_key` has been pre-padded so .unwrap() is OK.
+            self._state
+                ._reset_with_key(secret_key.unprotected_as_bytes())
+                .unwrap()
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Update state with `data`. This can be called multiple times.
+        ///
+        /// `data` can be anything that is viewable as bytes, such as `&str`, `Vec<u8>`
+        /// or a byte array.
+        pub fn update(&mut self, data: impl AsRef<[u8]>) -> Result<(), UnknownCryptoError> {
+            self._state._update(data.as_ref())
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Update state with associated data `ad`. This can be called multiple times,
+        /// but only on a state created with [`Self::new_with_ad()`] and before the first
+        /// call to [`Self::update()`].
+        ///
+        /// Each `ad` is authenticated as `0x00 || LE64(ad.len()) || ad`, and a single
+        /// `0x01` byte is authenticated after the last one, so the boundaries between
+        /// the associated data and the message cannot be shifted. The `0x01` byte is
+        /// also authenticated if this is never called.
+        pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), UnknownCryptoError> {
+            self._state._update_ad(ad)
+        }
+
+        /// Return the number of bytes passed to [`Self::update()`] since
+        /// [`Self::new()`] or the last [`Self::reset()`]. This includes the framing
+        /// added by [`Self::update_ad()`].
+        pub fn bytes_processed(&self) -> u64 {
+            self._state.bytes_processed
+        }
+
+        /// Return a HMAC-SHA512 tag.
+        pub(crate) fn _finalize_internal(
+            &mut self,
+            dest: &mut [u8],
+        ) -> Result<(), UnknownCryptoError> {
+            self._state._finalize(dest)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a HMAC-SHA512 tag.
+        pub fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+            let mut dest = [0u8; sha512::SHA512_OUTSIZE];
+            self._finalize_internal(&mut dest)?;
+
+            Ok(Tag::from(dest))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a tag, together with the number of bytes it authenticates.
+        pub fn finalize_with_len(&mut self) -> Result<(Tag, u64), UnknownCryptoError> {
+            let tag = self.finalize()?;
+
+            Ok((tag, self.bytes_processed()))
+        }
+
+        #[cfg(any(feature = "safe_api", feature = "alloc"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a HMAC-SHA512 tag as a lowercase hex string. This finalizes the state,
+        /// the same as [`Self::finalize()`].
+        pub fn finalize_hex(&mut self) -> Result<String, UnknownCryptoError> {
+            Ok(crate::util::to_hex(self.finalize()?.unprotected_as_bytes()))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Return a tag, together with the state re-keyed with `secret_key`, as
+        /// [`Self::new()`] or [`Self::new_with_ad()`], whichever created this state,
+        /// would return it.
+        ///
+        /// This is meant for authenticating the next message under a new key right
+        /// away. The state is reused, and the previous key material in it is overwritten.
+        pub fn finalize_and_init(
+            mut self,
+            secret_key: &SecretKey,
+        ) -> Result<(Tag, Self), UnknownCryptoError> {
+            let tag = self.finalize()?;
+            self.reset_with_key(secret_key);
+
+            Ok((tag, self))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Finalize the state and verify the result against `expected` in constant time.
+        ///
+        /// If [`Self::bytes_processed()`] differs from `expected_len`, an error is
+        /// returned immediately, without finalizing the state. The length of the
+        /// input is not secret, so this check does not need to be constant time.
+        pub fn finalize_verify(
+            &mut self,
+            expected: &Tag,
+            expected_len: u64,
+        ) -> Result<(), UnknownCryptoError> {
+            if self.bytes_processed() != expected_len {
+                return Err(UnknownCryptoError);
+            }
+
+            if &self.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// One-shot function for generating an HMAC-SHA512 tag of `data`.
+        pub fn hmac(secret_key: &SecretKey, data: &[u8]) -> Result<Tag, UnknownCryptoError> {
+            Ok(Tag::from(Self::hmac_into_array(secret_key, data)?))
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// One-shot function for generating an HMAC-SHA512 tag of `data`, returned as an array.
+        ///
+        /// Only the stack is used, so this is also available in `no_std` context. The bytes
+        /// are the same as those of the `Tag` returned by [`Self::hmac()`].
+        pub fn hmac_into_array(
+            secret_key: &SecretKey,
+            data: &[u8],
+        ) -> Result<[u8; sha512::SHA512_OUTSIZE], UnknownCryptoError> {
+            let mut dest = [0u8; sha512::SHA512_OUTSIZE];
+
+            if data.len() < sha512::SHA512_BLOCKSIZE {
+                Hmac::<Sha512, { sha512::SHA512_BLOCKSIZE }>::_oneshot_small(
+                    secret_key.unprotected_as_bytes(),
+                    data,
+                    &mut dest,
+                )?;
+            } else {
+                let mut ctx = Self::new(secret_key);
+                ctx.update(data)?;
+                ctx._finalize_internal(&mut dest)?;
+            }
+
+            Ok(dest)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA512 tag in constant time.
+        pub fn verify(
+            expected: &Tag,
+            secret_key: &SecretKey,
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            if &Self::hmac(secret_key, data)? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA512 tag in constant time, accepting it if it is valid under
+        /// any of `secret_keys`. This is meant for key rotation, where tags made with
+        /// the previous key are accepted for a while.
+        ///
+        /// A tag is computed and compared for every key, without returning early, so the
+        /// time taken does not reveal which key matched. An error is returned if
+        /// `secret_keys` is empty.
+        pub fn verify_any(
+            expected: &Tag,
+            secret_keys: &[&SecretKey],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            use subtle::ConstantTimeEq;
+
+            let mut matched = subtle::Choice::from(0u8);
+            for secret_key in secret_keys {
+                let tag = Self::hmac(secret_key, data)?;
+                matched |= tag
+                    .unprotected_as_bytes()
+                    .ct_eq(expected.unprotected_as_bytes());
+            }
+
+            if bool::from(matched) {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA512 tag in constant time, given the tag and secret key as raw bytes.
+        ///
+        /// `secret_key` may be of any length and is padded, or hashed if longer than the
+        /// blocksize, as specified in RFC 2104. An error is returned if `expected` is not
+        /// 64 bytes.
+        pub fn verify_slices(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), UnknownCryptoError> {
+            // The length of a tag is not secret, so a truncated or otherwise malformed
+            // tag can be told apart from a mismatch. The error is the same either way.
+            if expected.len() != sha512::SHA512_OUTSIZE {
+                log_misuse!(
+                    "HMAC: verify_slices() called with an expected tag of the wrong length"
+                );
+                return Err(UnknownCryptoError);
+            }
+            let expected = Tag::from_slice(expected)?;
+            let secret_key = SecretKey::from_slice_padded(secret_key)?;
+
+            Self::verify(&expected, &secret_key, data)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA512 tag in constant time, given the tag and secret key as raw
+        /// bytes, telling a malformed tag apart from one that does not match.
+        ///
+        /// This is the same as [`Self::verify_slices()`], except for the error returned:
+        /// - [`CryptoError::InvalidInput`] if `expected` is not 64 bytes. The length
+        ///   of a tag is not secret.
+        /// - [`CryptoError::Validation`] if `expected` does not match. Its contents are
+        ///   still compared in constant time.
+        pub fn verify_slices_detailed(
+            expected: &[u8],
+            secret_key: &[u8],
+            data: &[u8],
+        ) -> Result<(), CryptoError> {
+            if expected.len() != sha512::SHA512_OUTSIZE {
+                return Err(CryptoError::InvalidInput);
+            }
+
+            Self::verify_slices(expected, secret_key, data).map_err(|_| CryptoError::Validation)
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Generate an HMAC-SHA512 tag of a precomputed SHA512 `digest`.
+        ///
+        /// The tag is `HMAC(secret_key, SHA512(data))`, so the (possibly large) message
+        /// only has to be hashed once, and the digest can be both signed and verified.
+        ///
+        /// # Security:
+        /// This is a different MAC than [`Self::hmac()`] over `data`, and the two tags
+        /// are not interchangeable. Its security also relies on the collision resistance
+        /// of SHA512, which plain HMAC does not. A key should only be used for one of the two.
+        pub fn sign_prehashed(
+            secret_key: &SecretKey,
+            digest: &sha512::Digest,
+        ) -> Result<Tag, UnknownCryptoError> {
+            Self::hmac(secret_key, digest.as_ref())
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify, in constant time, a tag made with [`Self::sign_prehashed()`] of a
+        /// precomputed SHA512 `digest`.
+        pub fn verify_prehashed(
+            expected: &Tag,
+            secret_key: &SecretKey,
+            digest: &sha512::Digest,
+        ) -> Result<(), UnknownCryptoError> {
+            Self::verify(expected, secret_key, digest.as_ref())
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Verify a HMAC-SHA512 tag in constant time, over the concatenation of `chunks`.
+        pub fn verify_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(
+            expected: &Tag,
+            secret_key: &SecretKey,
+            chunks: I,
+        ) -> Result<(), UnknownCryptoError> {
+            let mut ctx = Self::new(secret_key);
+            for chunk in chunks {
+                ctx.update(chunk)?;
+            }
+
+            if &ctx.finalize()? == expected {
+                Ok(())
+            } else {
+                Err(UnknownCryptoError)
+            }
+        }
+
+        /// Export the streaming state, so that it can later be resumed with [`Self::import_state()`].
+        pub fn export_state(&self) -> ExportedState {
+            let mut value = [0u8; HMAC_SHA512_STATESIZE];
+            let (hashers, rest) = value.split_at_mut(3 * SHA512_STATESIZE);
+            let (bytes_processed, is_finalized) = rest.split_at_mut(8);
+            let states = [
+                &self._state.working_hasher,
+                &self._state.ipad_hasher,
+                &self._state.opad_hasher,
+            ];
+            for (hasher, dest) in states
+                .iter()
+                .zip(hashers.chunks_exact_mut(SHA512_STATESIZE))
+            {
+                // NOTE: `dest` is always SHA512_STATESIZE bytes so .unwrap() is OK.
+                hasher._state._export(dest).unwrap();
+            }
+            bytes_processed.copy_from_slice(&self._state.bytes_processed.to_be_bytes());
+            is_finalized[0] = u8::from(self._state.is_finalized)
+                | (u8::from(self._state.ad_pending) << 1)
+                | (u8::from(self._state.ad_mode) << 2);
+
+            let exported = ExportedState::from(value);
+            value.zeroize();
+
+            exported
+        }
+
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Resume a streaming state previously exported with [`Self::export_state()`].
+        ///
+        /// # Errors:
+        /// An error will be returned if:
+        /// - `state` was exported after [`Self::finalize()`] without a [`Self::reset()`] in between.
+        /// - `state` is otherwise malformed.
+        pub fn import_state(state: &ExportedState) -> Result<Self, UnknownCryptoError> {
+            let (hashers, rest) = state.unprotected_as_bytes().split_at(3 * SHA512_STATESIZE);
+            let (bytes_processed, is_finalized) = rest.split_at(8);
+            // NOTE: `bytes_processed` is always 8 bytes so .unwrap() is OK.
+            let bytes_processed = u64::from_be_bytes(bytes_processed.try_into().unwrap());
+            // Associated data can only be pending in the associated data mode, and once
+            // it is no longer pending, at least the byte marking its end has been processed.
+            let (ad_mode, ad_pending) = match is_finalized[0] {
+                0 => (false, false),
+                4 if bytes_processed > 0 => (true, false),
+                6 => (true, true),
+                _ => return Err(UnknownCryptoError),
+            };
+
+            let mut states = hashers.chunks_exact(SHA512_STATESIZE);
+            // NOTE: `hashers` always holds exactly three states so .unwrap() is OK.
+            let working_hasher = Sha512 {
+                _state: State::_import(states.next().unwrap())?,
+            };
+            let ipad_hasher = Sha512 {
+                _state: State::_import(states.next().unwrap())?,
+            };
+            let opad_hasher = Sha512 {
+                _state: State::_import(states.next().unwrap())?,
+            };
+
+            Ok(Self {
+                _state: Hmac {
+                    working_hasher,
+                    opad_hasher,
+                    ipad_hasher,
+                    bytes_processed,
+                    is_finalized: false,
+                    ad_mode,
+                    ad_pending,
+                },
+            })
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    /// An adapter implementing [`core::hash::Hasher`] over HMAC-SHA512, for keyed hashing
+    /// in hash maps where resistance against hash-flooding matters.
+    ///
+    /// __WARNING:__ [`finish()`](core::hash::Hasher::finish) returns only the first 8 bytes of the
+    /// HMAC-SHA512 tag. A 64-bit output does not provide the security guarantees of a MAC and
+    /// must never be used for authentication.
+    pub struct HmacSha512Hasher {
+        state: HmacSha512,
+    }
+
+    impl core::hash::Hasher for HmacSha512Hasher {
+        fn write(&mut self, bytes: &[u8]) {
+            // NOTE: `state` is never finalized, finish() works on a copy, so .unwrap() is OK.
+            self.state.update(bytes).unwrap();
+        }
+
+        fn finish(&self) -> u64 {
+            // NOTE: The copy has not been finalized before, so .unwrap() is OK.
+            let tag = self.state.clone().finalize().unwrap();
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&tag.unprotected_as_bytes()[..8]);
+
+            u64::from_le_bytes(value)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    /// A [`core::hash::BuildHasher`] creating [`HmacSha512Hasher`]s with the same secret key.
+    ///
+    /// __WARNING:__ See [`HmacSha512Hasher`] about the truncated output.
+    pub struct HmacSha512BuildHasher {
+        initial_state: HmacSha512,
+    }
+
+    impl HmacSha512BuildHasher {
+        /// Initialize a `HmacSha512BuildHasher` with a given key.
+        pub fn new(secret_key: &SecretKey) -> Self {
+            Self {
+                initial_state: HmacSha512::new(secret_key),
+            }
+        }
+    }
+
+    impl core::hash::BuildHasher for HmacSha512BuildHasher {
+        type Hasher = HmacSha512Hasher;
+
+        fn build_hasher(&self) -> Self::Hasher {
+            HmacSha512Hasher {
+                state: self.initial_state.clone(),
+            }
+        }
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return a short, keyed fingerprint of `data`: the first 8 bytes of its HMAC-SHA512
+    /// tag, as a big-endian `u64`. Meant for content-addressing and deduplication, where
+    /// an attacker who does not know `secret_key` should not be able to produce collisions
+    /// on purpose.
+    ///
+    /// __WARNING:__ A fingerprint is truncated to 64 bits and is compared as a plain `u64`,
+    /// which is not constant-time. It must never be used for authentication, use
+    /// [`HmacSha512::verify()`] for that.
+    pub fn fingerprint(secret_key: &SecretKey, data: &[u8]) -> Result<u64, UnknownCryptoError> {
+        let tag = HmacSha512::hmac(secret_key, data)?;
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&tag.unprotected_as_bytes()[..8]);
+
+        Ok(u64::from_be_bytes(value))
+    }
+
+    impl crate::hazardous::mac::sealed::Sealed for HmacSha512 {}
+
+    impl crate::hazardous::mac::Mac for HmacSha512 {
+        type Key = SecretKey;
+        type Tag = Tag;
+
+        fn new(key: &SecretKey) -> Self {
+            HmacSha512::new(key)
+        }
+
+        fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha512::update(self, data)
+        }
+
+        fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+            HmacSha512::finalize(self)
+        }
+
+        fn reset_with_key(&mut self, key: &SecretKey) {
+            HmacSha512::reset_with_key(self, key)
+        }
+
+        fn verify(expected: &Tag, key: &SecretKey, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            HmacSha512::verify(expected, key, data)
+        }
+    }
+
+    impl HmacFunction for HmacSha512 {
+        /// The output size of the internal hash function used.
+        const HASH_FUNC_OUTSIZE: usize = sha512::SHA512_OUTSIZE;
+
+        /// Create a new instance of the HMAC function, using a `secret_key` that may or may not be padded.
+        fn _new(secret_key: &[u8]) -> Result<Self, UnknownCryptoError> {
+            Self::_new(secret_key)
+        }
+
+        /// Update the internal state with `data`.
+        fn _update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            self._state._update(data)
+        }
+
+        /// Finalize the MAC and put the final tag into `dest`.
+        ///
+        /// NOTE: `dest` may be less than the complete output size of the hash function
+        /// (Self::HASH_FUNC_OUTSIZE). If that is the case, `dest.len()` bytes will be copied,
+        /// but `dest` should NEVER be empty.
+        fn _finalize(&mut self, dest: &mut [u8]) -> Result<(), UnknownCryptoError> {
+            self._state._finalize(dest)
+        }
+
+        /// Reset the state.
+        fn _reset(&mut self) {
+            self._state._reset()
+        }
+    }
+
+    #[cfg(test)]
+    mod public {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "safe_api")]
+        fn test_debug_impl() {
+            let secret_key = SecretKey::generate();
+            let initial_state = HmacSha512::new(&secret_key);
+            let debug = format!("{:?}", initial_state);
+            let expected = "HmacSha512 { _state: Hmac { working_hasher: [***OMITTED***], opad_hasher: [***OMITTED***], ipad_hasher: [***OMITTED***], bytes_processed: 0, is_finalized: false } }";
+            assert_eq!(debug, expected);
+        }
+
+        #[cfg(feature = "safe_api")]
+        mod test_verify {
+            use super::*;
+
+            #[quickcheck]
+            #[cfg(feature = "safe_api")]
+            /// When using a different key, verify() should always yield an error.
+            /// NOTE: Using different and same input data is tested with TestableStreamingContext.
+            fn prop_verify_diff_key_false(data: Vec<u8>) -> bool {
+                let sk = SecretKey::generate();
+                let mut state = HmacSha512::new(&sk);
+                state.update(&data[..]).unwrap();
+                let tag = state.finalize().unwrap();
+                let bad_sk = SecretKey::generate();
+
+                HmacSha512::verify(&tag, &bad_sk, &data[..]).is_err()
+            }
+        }
+
+        mod test_bytes_processed {
+            use super::*;
+
+            #[test]
+            fn test_counts_updates_and_resets() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                assert_eq!(state.bytes_processed(), 0);
+
+                state.update([0u8; 7]).unwrap();
+                state.update([]).unwrap();
+                state.update([0u8; 128]).unwrap();
+                state.update([0u8; 300]).unwrap();
+                assert_eq!(state.bytes_processed(), 435);
+
+                let _ = state.finalize().unwrap();
+                assert_eq!(state.bytes_processed(), 435);
+
+                state.reset();
+                assert_eq!(state.bytes_processed(), 0);
+            }
+
+            #[test]
+            fn test_err_on_overflow() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                state._state.bytes_processed = u64::MAX - 1;
+
+                assert!(state.update([0u8; 1]).is_ok());
+                assert!(state.update([0u8; 1]).is_err());
+                assert_eq!(state.bytes_processed(), u64::MAX);
+            }
+
+            #[test]
+            fn test_count_survives_export_import() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                state.update([0u8; 200]).unwrap();
+
+                let imported = HmacSha512::import_state(&state.export_state()).unwrap();
+                assert_eq!(imported.bytes_processed(), 200);
+            }
+        }
+
+        mod test_fingerprint {
+            use super::*;
+
+            #[test]
+            fn test_fingerprint_is_truncated_tag() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let tag = HmacSha512::hmac(&sk, b"Some content.").unwrap();
+                let mut expected = [0u8; 8];
+                expected.copy_from_slice(&tag.unprotected_as_bytes()[..8]);
+
+                assert_eq!(
+                    fingerprint(&sk, b"Some content.").unwrap(),
+                    u64::from_be_bytes(expected)
+                );
+            }
+
+            #[test]
+            fn test_deterministic_and_key_dependent() {
+                let sk_1 = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let sk_2 = SecretKey::from_slice_padded(&[2u8; 64]).unwrap();
+
+                assert_eq!(
+                    fingerprint(&sk_1, b"Some content.").unwrap(),
+                    fingerprint(&sk_1, b"Some content.").unwrap()
+                );
+                assert_ne!(
+                    fingerprint(&sk_1, b"Some content.").unwrap(),
+                    fingerprint(&sk_2, b"Some content.").unwrap()
+                );
+                assert_ne!(
+                    fingerprint(&sk_1, b"Some content.").unwrap(),
+                    fingerprint(&sk_1, b"Other content.").unwrap()
+                );
+            }
+        }
+
+        mod test_hasher {
+            use super::*;
+            use core::hash::{BuildHasher, Hasher};
+
+            #[test]
+            fn test_finish_is_truncated_tag() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut hasher = HmacSha512BuildHasher::new(&sk).build_hasher();
+                hasher.write(b"Some");
+                hasher.write(b" message.");
+
+                let tag = HmacSha512::hmac(&sk, b"Some message.").unwrap();
+                let mut expected = [0u8; 8];
+                expected.copy_from_slice(&tag.unprotected_as_bytes()[..8]);
+                assert_eq!(hasher.finish(), u64::from_le_bytes(expected));
+                // finish() does not modify the state.
+                assert_eq!(hasher.finish(), u64::from_le_bytes(expected));
+
+                hasher.write(b"More");
+                let tag = HmacSha512::hmac(&sk, b"Some message.More").unwrap();
+                expected.copy_from_slice(&tag.unprotected_as_bytes()[..8]);
+                assert_eq!(hasher.finish(), u64::from_le_bytes(expected));
+            }
+
+            #[test]
+            fn test_diff_key_diff_hash() {
+                let build_1 =
+                    HmacSha512BuildHasher::new(&SecretKey::from_slice_padded(&[1u8; 64]).unwrap());
+                let build_2 =
+                    HmacSha512BuildHasher::new(&SecretKey::from_slice_padded(&[2u8; 64]).unwrap());
+
+                assert_eq!(build_1.hash_one(b"key"), build_1.hash_one(b"key"));
+                assert_ne!(build_1.hash_one(b"key"), build_2.hash_one(b"key"));
+            }
+
+            #[test]
+            #[cfg(feature = "safe_api")]
+            fn test_hashmap() {
+                let mut map = std::collections::HashMap::with_hasher(HmacSha512BuildHasher::new(
+                    &SecretKey::generate(),
+                ));
+                for idx in 0..100u32 {
+                    map.insert(idx, idx * 2);
+                }
+
+                assert_eq!(map.len(), 100);
+                for idx in 0..100u32 {
+                    assert_eq!(map.get(&idx), Some(&(idx * 2)));
+                }
+                assert_eq!(map.get(&100), None);
+            }
+        }
+
+        mod test_export_import {
+            use super::*;
+
+            #[test]
+            #[cfg(feature = "safe_api")]
+            fn test_roundtrip_multi_megabyte() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                // 4 MiB input that is not evenly divisible by the blocksize.
+                let data = vec![255u8; (4 * 1024 * 1024) + 13];
+                let (first, second) = data.split_at((2 * 1024 * 1024) + 7);
+
+                let mut state = HmacSha512::new(&sk);
+                state.update(first).unwrap();
+                let exported = state.export_state();
+                drop(state);
+
+                let mut resumed = HmacSha512::import_state(&exported).unwrap();
+                resumed.update(second).unwrap();
+
+                assert_eq!(
+                    resumed.finalize().unwrap(),
+                    HmacSha512::hmac(&sk, &data).unwrap()
+                );
+            }
+
+            #[test]
+            fn test_roundtrip_preserves_state() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                state.update([0u8; 200]).unwrap();
+
+                let resumed = HmacSha512::import_state(&state.export_state()).unwrap();
+                state._state.compare_state_to_other(&resumed._state);
+            }
+
+            #[test]
+            fn test_reset_after_import() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                state.update(b"Some message.").unwrap();
+
+                let mut resumed = HmacSha512::import_state(&state.export_state()).unwrap();
+                resumed.reset();
+                resumed.update(b"Other message.").unwrap();
+
+                assert_eq!(
+                    resumed.finalize().unwrap(),
+                    HmacSha512::hmac(&sk, b"Other message.").unwrap()
+                );
+            }
+
+            #[test]
+            fn test_import_err_on_finalized() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new(&sk);
+                state.update(b"Some message.").unwrap();
+                let _ = state.finalize().unwrap();
+
+                assert!(HmacSha512::import_state(&state.export_state()).is_err());
+
+                state.reset();
+                assert!(HmacSha512::import_state(&state.export_state()).is_ok());
+            }
+
+            #[test]
+            fn test_export_import_pending_ad() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let mut state = HmacSha512::new_with_ad(&sk);
+                state.update_ad(b"ad").unwrap();
+
+                let mut imported = HmacSha512::import_state(&state.export_state()).unwrap();
+                state._state.compare_state_to_other(&imported._state);
+                imported.update_ad(b"more ad").unwrap();
+                state.update_ad(b"more ad").unwrap();
+                imported.update(b"msg").unwrap();
+                state.update(b"msg").unwrap();
+
+                let mut imported = HmacSha512::import_state(&imported.export_state()).unwrap();
+                assert!(imported.update_ad(b"ad").is_err());
+                assert_eq!(imported.finalize().unwrap(), state.finalize().unwrap());
+
+                // A fresh state in the associated data mode can be imported, and is not
+                // imported as a plain HMAC state.
+                let fresh = HmacSha512::new_with_ad(&sk);
+                let imported = HmacSha512::import_state(&fresh.export_state()).unwrap();
+                fresh._state.compare_state_to_other(&imported._state);
+
+                // Pending associated data outside the associated data mode, or the mode
+                // without the end of the associated data processed, is malformed.
+                let mut bad = [0u8; HMAC_SHA512_STATESIZE];
+                bad.copy_from_slice(fresh.export_state().unprotected_as_bytes());
+                for flags in [2u8, 3, 4, 5, 7, 8] {
+                    bad[HMAC_SHA512_STATESIZE - 1] = flags;
+                    assert!(HmacSha512::import_state(&ExportedState::from(bad)).is_err());
+                }
+            }
+
+            #[test]
+            fn test_import_err_on_malformed() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 64]).unwrap();
+                let state = HmacSha512::new(&sk);
+                let exported = state.export_state();
+
+                // Finalization flag of the HMAC state is not a valid boolean.
+                let mut bad = [0u8; HMAC_SHA512_STATESIZE];
+                bad.copy_from_slice(exported.unprotected_as_bytes());
+                bad[HMAC_SHA512_STATESIZE - 1] = 2;
+                assert!(HmacSha512::import_state(&ExportedState::from(bad)).is_err());
+
+                // Finalization flag of the inner working hasher is set.
+                bad.copy_from_slice(exported.unprotected_as_bytes());
+                bad[SHA512_STATESIZE - 1] = 1;
+                assert!(HmacSha512::import_state(&ExportedState::from(bad)).is_err());
+
+                // Leftover of the inner working hasher is not less than the blocksize.
+                bad.copy_from_slice(exported.unprotected_as_bytes());
+                let leftover_pos = (8 * 8) + sha512::SHA512_BLOCKSIZE;
+                bad[leftover_pos..leftover_pos + 8]
+                    .copy_from_slice(&(sha512::SHA512_BLOCKSIZE as u64).to_be_bytes());
+                assert!(HmacSha512::import_state(&ExportedState::from(bad)).is_err());
+            }
+        }
+
+        mod test_drop_mid_stream {
+            use super::*;
+
+            #[test]
+            fn test_hashers_wiped_mid_stream() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new(&key);
+                // Leaves a partial block in the buffer.
+                state.update([1u8; 200]).unwrap();
+
+                // HmacSha512 has no Drop of its own. All key-dependent data is in the
+                // three hashers, whose Drop calls _wipe().
+                let hmac = &mut state._state;
+                for hasher in [
+                    &mut hmac.working_hasher,
+                    &mut hmac.ipad_hasher,
+                    &mut hmac.opad_hasher,
+                ] {
+                    assert!(hasher
+                        ._state
+                        .working_state
+                        .iter()
+                        .any(|w| *w != Default::default()));
+                    hasher._state._wipe();
+                    assert!(hasher
+                        ._state
+                        .working_state
+                        .iter()
+                        .all(|w| *w == Default::default()));
+                    assert!(hasher
+                        ._state
+                        .message_len
+                        .iter()
+                        .all(|w| *w == Default::default()));
+                    assert_eq!(hasher._state.buffer, [0u8; sha512::SHA512_BLOCKSIZE]);
+                    assert_eq!(hasher._state.leftover, 0);
+                }
+            }
+        }
+
+        mod test_tag_xor {
+            use super::*;
+
+            #[test]
+            fn test_xor_is_involution() {
+                let a = Tag::from([1u8; sha512::SHA512_OUTSIZE]);
+                let b: [u8; sha512::SHA512_OUTSIZE] = core::array::from_fn(|i| i as u8);
+                let b = Tag::from(b);
+
+                assert_ne!(a.xor(&b), a);
+                assert_eq!(a.xor(&b).xor(&b), a);
+                assert_eq!(a.xor(&b), b.xor(&a));
+                assert_eq!(a.xor(&a), Tag::from([0u8; sha512::SHA512_OUTSIZE]));
+            }
+        }
+
+        mod test_extend {
+            use super::*;
+
+            #[test]
+            fn test_extend_same_as_update() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+
+                for len in [0, 1, 63, 64, 65, 128, 300] {
+                    let mut expected = HmacSha512::new(&key);
+                    expected.update(&data[..len]).unwrap();
+
+                    let mut state = HmacSha512::new(&key);
+                    state.extend(data[..len].iter().copied());
+                    assert_eq!(state.bytes_processed(), len as u64);
+                    let mut state_ref = HmacSha512::new(&key);
+                    state_ref.extend(data[..len].iter());
+
+                    let expected = expected.finalize().unwrap();
+                    assert_eq!(state.finalize().unwrap(), expected);
+                    assert_eq!(state_ref.finalize().unwrap(), expected);
+                }
+            }
+
+            #[test]
+            fn test_extend_after_finalize_ignored() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new(&key);
+                let _ = state.finalize().unwrap();
+                state.extend([0u8; 100]);
+                assert_eq!(state.bytes_processed(), 0);
+                assert!(state.finalize().is_err());
+            }
+        }
+
+        mod test_reset_with_key {
+            use super::*;
+
+            #[test]
+            fn test_reset_with_key_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut fresh = HmacSha512::new(&new_key);
+                fresh.update(data).unwrap();
+                let expected = fresh.finalize().unwrap();
+
+                let mut state = HmacSha512::new(&old_key);
+                state.update(data).unwrap();
+                let old_tag = state.finalize().unwrap();
+                assert_ne!(old_tag, expected);
+
+                // Also from a finalized state.
+                state.reset_with_key(&new_key);
+                assert_eq!(state.bytes_processed(), 0);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                // A subsequent reset() keeps the new key.
+                state.reset();
+                state.update(&data[..50]).unwrap();
+                state.reset_with_key(&new_key);
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+                state.reset();
+                state.update(data).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+            }
+        }
+
+        mod test_empty_input {
+            use super::*;
+
+            fn check_empty_input(key: &[u8], expected_hex: &str) {
+                let secret_key = SecretKey::from_slice_padded(key).unwrap();
+                let mut expected = [0u8; sha512::SHA512_OUTSIZE];
+                hex::decode_to_slice(expected_hex, &mut expected).unwrap();
+                let expected_tag = Tag::from_slice(&expected).unwrap();
+
+                assert_eq!(
+                    HmacSha512::hmac_into_array(&secret_key, &[]).unwrap(),
+                    expected
+                );
+                assert_eq!(HmacSha512::hmac(&secret_key, &[]).unwrap(), expected_tag);
+                assert!(HmacSha512::verify(&expected_tag, &secret_key, &[]).is_ok());
+                assert!(HmacSha512::verify(&expected_tag, &secret_key, &[0u8]).is_err());
+
+                // Streaming, with no or only empty updates.
+                let mut state = HmacSha512::new(&secret_key);
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+                state.reset();
+                state.update([]).unwrap();
+                state.update([]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected_tag);
+            }
+
+            #[test]
+            fn test_empty_input_kat() {
+                // Computed with Python's hmac module.
+                check_empty_input(
+                    b"",
+                    "b936cee86c9f87aa5d3c6f2e84cb5a4239a5fe50480a6ec66b70ab5b1f4ac6730c6c515421b327ec1d69402e53dfb49ad7381eb067b338fd7b0cb22247225d47",
+                );
+                check_empty_input(
+                    b"key",
+                    "84fa5aa0279bbc473267d05a53ea03310a987cecc4c1535ff29b6d76b8f1444a728df3aadb89d4a9a6709e1998f373566e8f824a8ca93b1821f0b69bc2a2f65e",
+                );
+            }
+        }
+
+        mod test_finalize_and_init {
+            use super::*;
+
+            #[test]
+            fn test_finalize_and_init_same_as_new() {
+                let old_key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let new_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let data = [0u8; 200];
+
+                let mut state = HmacSha512::new(&old_key);
+                state.update(data).unwrap();
+                let (tag, mut state) = state.finalize_and_init(&new_key).unwrap();
+                assert_eq!(tag, HmacSha512::hmac(&old_key, &data).unwrap());
+                state
+                    ._state
+                    .compare_state_to_other(&HmacSha512::new(&new_key)._state);
+
+                state.update(data).unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha512::hmac(&new_key, &data).unwrap()
+                );
+
+                // An already finalized state cannot be finalized again.
+                assert!(state.finalize_and_init(&old_key).is_err());
+            }
+        }
+
+        mod test_finalize_with_len {
+            use super::*;
+
+            #[test]
+            fn test_len_across_chunks() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
+
+                let mut state = HmacSha512::new(&key);
+                let mut expected_len = 0u64;
+                for chunk in data.chunks(7) {
+                    state.update(chunk).unwrap();
+                    expected_len += chunk.len() as u64;
+                }
+                state.update([]).unwrap();
+
+                let mut other = HmacSha512::new(&key);
+                other.update(data).unwrap();
+                let (tag, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, expected_len);
+                assert_eq!(len, 300);
+                assert_eq!(tag, other.finalize().unwrap());
+                assert!(state.finalize_with_len().is_err());
+
+                state.reset();
+                let (_, len) = state.finalize_with_len().unwrap();
+                assert_eq!(len, 0);
+            }
+        }
+
+        mod test_verify_chunks {
+            use super::*;
+
+            #[test]
+            fn test_verify_chunks_same_as_verify() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut data = [0u8; 300];
+                for (idx, byte) in data.iter_mut().enumerate() {
+                    *byte = idx as u8;
+                }
+                let tag = HmacSha512::hmac(&key, &data).unwrap();
+                assert!(HmacSha512::verify(&tag, &key, &data).is_ok());
+
+                for chunk_size in [1, 7, 64, 128, 299, 300, 301] {
+                    assert!(HmacSha512::verify_chunks(&tag, &key, data.chunks(chunk_size)).is_ok());
+                }
+                assert!(
+                    HmacSha512::verify_chunks(&tag, &key, [&data[..10], &[], &data[10..]]).is_ok()
+                );
+
+                // Missing, reordered and extra data.
+                assert!(HmacSha512::verify_chunks(&tag, &key, data.chunks(7).skip(1)).is_err());
+                assert!(HmacSha512::verify_chunks(&tag, &key, data.chunks(150).rev()).is_err());
+                assert!(HmacSha512::verify_chunks(&tag, &key, [&data[..], &[0u8]]).is_err());
+
+                let empty_tag = HmacSha512::hmac(&key, &[]).unwrap();
+                assert!(HmacSha512::verify_chunks(&empty_tag, &key, core::iter::empty()).is_ok());
+            }
+        }
+
+        mod test_hmac_into_array {
+            use super::*;
+
+            #[test]
+            // Does not depend on `safe_api`, so this also runs in `no_std` context.
+            fn test_hmac_into_array_same_as_hmac() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 300];
+
+                for len in [0, 1, 128 - 1, 128, 128 + 1, 300] {
+                    let tag = HmacSha512::hmac(&key, &data[..len]).unwrap();
+                    let array = HmacSha512::hmac_into_array(&key, &data[..len]).unwrap();
+
+                    assert_eq!(tag.unprotected_as_bytes(), &array[..]);
+                }
+            }
+        }
+
+        #[cfg(feature = "safe_api")]
+        mod test_finalize_hex {
+            use super::*;
+
+            #[test]
+            fn test_finalize_hex_matches_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new(&key);
+                state.update(b"Some message.").unwrap();
+                let tag = state.finalize().unwrap();
+
+                state.reset();
+                state.update(b"Some message.").unwrap();
+                let hex = state.finalize_hex().unwrap();
+
+                let expected: String = tag
+                    .unprotected_as_bytes()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                assert_eq!(hex, expected);
+                assert_eq!(hex.len(), tag.len() * 2);
+                // The state was finalized.
+                assert!(state.finalize_hex().is_err());
+            }
+        }
+
+        #[cfg(feature = "safe_api")]
+        mod test_update_as_ref {
+            use super::*;
+
+            #[test]
+            fn test_update_str_vec_array() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let expected = HmacSha512::hmac(&key, &[b'a'; 16]).unwrap();
+
+                let mut state = HmacSha512::new(&key);
+                state.update("aaaaaaaa").unwrap();
+                state.update(vec![b'a'; 4]).unwrap();
+                state.update([b'a'; 4]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+
+                let data = vec![b'a'; 16];
+                state.reset();
+                state.update(&data).unwrap();
+                state.update(&data[..0]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+            }
+        }
+
+        mod test_update_ad {
+            use super::*;
+
+            fn tag_of(ad: &[&[u8]], message: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new_with_ad(&key);
+                for frame in ad {
+                    state.update_ad(frame).unwrap();
+                }
+                state.update(message).unwrap();
+                state.finalize().unwrap()
+            }
+
+            /// Standard HMAC of `encoded`, under the key that `new_with_ad()` derives.
+            fn raw_tag_of(encoded: &[u8]) -> Tag {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let ad_key = crate::hazardous::kdf::hkdf::sha512::extract(
+                    b"orion-hmac-ad",
+                    key.unprotected_as_bytes(),
+                )
+                .unwrap();
+                let ad_key = SecretKey::from_slice_padded(ad_key.unprotected_as_bytes()).unwrap();
+                HmacSha512::hmac(&ad_key, encoded).unwrap()
+            }
+
+            #[test]
+            fn test_encoding() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut encoded = [0u8; 1 + 8 + 2 + 1 + 3];
+                encoded[9..11].copy_from_slice(b"ad");
+                encoded[1..9].copy_from_slice(&2u64.to_le_bytes());
+                encoded[11] = 1;
+                encoded[12..].copy_from_slice(b"msg");
+
+                assert_eq!(tag_of(&[b"ad"], b"msg"), raw_tag_of(&encoded));
+                assert_eq!(tag_of(&[], b"msg"), raw_tag_of(&encoded[11..]));
+
+                // Associated data without a message still ends with the marker.
+                let mut state = HmacSha512::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                assert_eq!(state.finalize().unwrap(), raw_tag_of(&encoded[..12]));
+                assert_eq!(state.bytes_processed(), 12);
+
+                // Never a plain HMAC tag under the same key, whatever it is computed over.
+                assert_ne!(
+                    tag_of(&[b"ad"], b"msg"),
+                    HmacSha512::hmac(&key, &encoded).unwrap()
+                );
+                assert_ne!(tag_of(&[], b"msg"), HmacSha512::hmac(&key, b"msg").unwrap());
+                assert_ne!(
+                    tag_of(&[], b"msg"),
+                    HmacSha512::hmac(&key, &encoded[11..]).unwrap()
+                );
+                assert_ne!(tag_of(&[], b""), HmacSha512::hmac(&key, b"").unwrap());
+                assert_ne!(tag_of(&[], b""), HmacSha512::hmac(&key, &[1u8]).unwrap());
+            }
+
+            #[test]
+            fn test_boundaries_not_ambiguous() {
+                let mut frame_of_b = [0u8; 1 + 8 + 1];
+                frame_of_b[1..9].copy_from_slice(&1u64.to_le_bytes());
+                frame_of_b[9] = b'b';
+
+                assert_ne!(tag_of(&[b"a", b"b"], b""), tag_of(&[b"a"], &frame_of_b));
+                assert_ne!(tag_of(&[b"ab"], b""), tag_of(&[b"a"], b"b"));
+                assert_ne!(tag_of(&[b""], b"msg"), tag_of(&[], b"msg"));
+                assert_ne!(tag_of(&[b"", b""], b""), tag_of(&[b""], b""));
+                assert_ne!(tag_of(&[b"ad"], b"msg"), tag_of(&[b"msg"], b"ad"));
+            }
+
+            #[test]
+            fn test_update_ad_without_ad_mode_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                assert!(state.update_ad(b"").is_err());
+
+                state.reset_with_key(&key);
+                assert!(state.update_ad(b"ad").is_err());
+                state.update(b"msg").unwrap();
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    HmacSha512::hmac(&key, b"msg").unwrap()
+                );
+            }
+
+            #[test]
+            fn test_finalize_and_init_keeps_mode() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new_with_ad(&key);
+                state.update_ad(b"ad").unwrap();
+                let (_, mut state) = state.finalize_and_init(&key).unwrap();
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                let (_, mut state) = HmacSha512::new(&key).finalize_and_init(&key).unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+            }
+
+            #[test]
+            fn test_update_ad_after_update_err() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let mut state = HmacSha512::new_with_ad(&key);
+                state.update_ad(b"first").unwrap();
+                state.update_ad(b"second").unwrap();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"third").is_err());
+                state.update(b"more msg").unwrap();
+                let _ = state.finalize().unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update(b"msg").unwrap();
+                assert!(state.update_ad(b"ad").is_err());
+
+                state.reset();
+                state.update_ad(b"ad").unwrap();
+                let mut ad_first = state.clone();
+                ad_first.update(b"msg").unwrap();
+                assert_eq!(ad_first.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+
+                // Resetting with a key keeps the associated data mode.
+                state.reset_with_key(&key);
+                state.update_ad(b"ad").unwrap();
+                state.update(b"msg").unwrap();
+                assert_eq!(state.finalize().unwrap(), tag_of(&[b"ad"], b"msg"));
+            }
+        }
+
+        mod test_verify_any {
+            use super::*;
+
+            #[test]
+            fn test_verify_any_second_key() {
+                let data = b"Some message.";
+                let previous = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let current = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                let other = SecretKey::from_slice_padded(&[3u8; 32]).unwrap();
+                let tag = HmacSha512::hmac(&current, data).unwrap();
+
+                assert!(HmacSha512::verify(&tag, &previous, data).is_err());
+                assert!(HmacSha512::verify_any(&tag, &[&previous, &current], data).is_ok());
+                assert!(HmacSha512::verify_any(&tag, &[&current, &previous], data).is_ok());
+                assert!(HmacSha512::verify_any(&tag, &[&current], data).is_ok());
+                assert!(HmacSha512::verify_any(&tag, &[&previous, &other], data).is_err());
+                assert!(
+                    HmacSha512::verify_any(&tag, &[&previous, &current], b"Other message.")
+                        .is_err()
+                );
+                assert!(HmacSha512::verify_any(&tag, &[], data).is_err());
+            }
+        }
+
+        mod test_tag_array {
+            use super::*;
+
+            #[test]
+            fn test_into_array_and_back() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let tag = HmacSha512::hmac(&key, b"Some message.").unwrap();
+                let expected = HmacSha512::hmac_into_array(&key, b"Some message.").unwrap();
+
+                let bytes: [u8; sha512::SHA512_OUTSIZE] = tag.clone().into();
+                assert_eq!(bytes, expected);
+                assert_eq!(Tag::from(bytes), tag);
+            }
+        }
+
+        mod test_prehashed {
+            use super::*;
+
+            #[test]
+            fn test_sign_prehashed_reference() {
+                // HMAC-SHA512(b"key", SHA512(data)), computed with Python's hmac and hashlib.
+                let data = b"The quick brown fox jumps over the lazy dog";
+                let expected = "102b635e0d6f591933f61a5a166dbfa499aa5d995b066017c4effe02a42bb223\
+                                90df3ec0858e462cd14a5c76eda4841f6ae20024c108ac73842108ad477a98e7";
+                let mut expected_tag = [0u8; 64];
+                hex::decode_to_slice(expected, &mut expected_tag).unwrap();
+
+                let key = SecretKey::from_slice_padded(b"key").unwrap();
+                let digest = Sha512::digest(data).unwrap();
+                let tag = HmacSha512::sign_prehashed(&key, &digest).unwrap();
+
+                assert_eq!(tag, &expected_tag[..]);
+                assert_eq!(tag, HmacSha512::hmac(&key, digest.as_ref()).unwrap());
+                assert_ne!(tag, HmacSha512::hmac(&key, data).unwrap());
+            }
+
+            #[test]
+            fn test_verify_prehashed() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let digest = Sha512::digest(b"Some message.").unwrap();
+                let tag = HmacSha512::sign_prehashed(&key, &digest).unwrap();
+
+                assert!(HmacSha512::verify_prehashed(&tag, &key, &digest).is_ok());
+                assert!(HmacSha512::verify(&tag, &key, b"Some message.").is_err());
+
+                let other_digest = Sha512::digest(b"Other message.").unwrap();
+                assert!(HmacSha512::verify_prehashed(&tag, &key, &other_digest).is_err());
+                let other_key = SecretKey::from_slice_padded(&[2u8; 32]).unwrap();
+                assert!(HmacSha512::verify_prehashed(&tag, &other_key, &digest).is_err());
+            }
+        }
+
+        mod test_verify_slices {
+            use super::*;
+
+            #[test]
+            fn test_verify_slices_same_as_verify() {
+                let data = b"Some message.";
+                for key_len in [0, 1, 32, 128, 128 + 1, 300] {
+                    let raw_key = &[1u8; 300][..key_len];
+                    let key = SecretKey::from_slice_padded(raw_key).unwrap();
+                    let tag = HmacSha512::hmac(&key, data).unwrap();
+
+                    assert!(
+                        HmacSha512::verify_slices(tag.unprotected_as_bytes(), raw_key, data)
+                            .is_ok()
+                    );
+                    assert!(HmacSha512::verify(&tag, &key, data).is_ok());
+                }
+            }
+
+            #[test]
+            fn test_verify_slices_tampered() {
+                let raw_key = [1u8; 300];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha512::hmac(&key, data).unwrap();
+
+                let mut bad_tag = [0u8; 64];
+                bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+                bad_tag[0] ^= 1;
+                assert!(HmacSha512::verify_slices(&bad_tag, &raw_key, data).is_err());
+                assert!(
+                    HmacSha512::verify_slices(tag.unprotected_as_bytes(), &raw_key[1..], data)
+                        .is_err()
+                );
+                assert!(HmacSha512::verify_slices(
+                    tag.unprotected_as_bytes(),
+                    &raw_key,
+                    b"Some message!"
+                )
+                .is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_wrong_tag_length() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha512::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert!(HmacSha512::verify_slices(&[], &raw_key, data).is_err());
+                assert!(HmacSha512::verify_slices(&tag[..64 - 1], &raw_key, data).is_err());
+                let mut long_tag = [0u8; 64 + 1];
+                long_tag[..64].copy_from_slice(tag);
+                assert!(HmacSha512::verify_slices(&long_tag, &raw_key, data).is_err());
+            }
+
+            #[test]
+            fn test_verify_slices_detailed() {
+                let raw_key = [1u8; 32];
+                let data = b"Some message.";
+                let key = SecretKey::from_slice_padded(&raw_key).unwrap();
+                let tag = HmacSha512::hmac(&key, data).unwrap();
+                let tag = tag.unprotected_as_bytes();
+
+                assert_eq!(
+                    HmacSha512::verify_slices_detailed(tag, &raw_key, data),
+                    Ok(())
+                );
+                assert_eq!(
+                    HmacSha512::verify_slices_detailed(&[], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+                assert_eq!(
+                    HmacSha512::verify_slices_detailed(&tag[..64 - 1], &raw_key, data),
+                    Err(CryptoError::InvalidInput)
+                );
+
+                let mut bad_tag = [0u8; 64];
+                bad_tag.copy_from_slice(tag);
+                bad_tag[64 - 1] ^= 1;
+                assert_eq!(
+                    HmacSha512::verify_slices_detailed(&bad_tag, &raw_key, data),
+                    Err(CryptoError::Validation)
+                );
+                assert_eq!(
+                    HmacSha512::verify_slices_detailed(tag, &raw_key, b"Other message."),
+                    Err(CryptoError::Validation)
+                );
+            }
+        }
+
+        mod test_finalize_verify {
+            use super::*;
+
+            #[test]
+            fn test_finalize_verify_ok() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha512::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha512::new(&key);
+                state.update(&data[..40]).unwrap();
+                state.update(&data[40..]).unwrap();
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+                // The state has been finalized.
+                assert!(state.finalize_verify(&expected, 100).is_err());
+            }
+
+            #[test]
+            fn test_finalize_verify_length_mismatch() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let expected = HmacSha512::hmac(&key, &data).unwrap();
+
+                let mut state = HmacSha512::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&expected, 99).is_err());
+                assert!(state.finalize_verify(&expected, 101).is_err());
+                // Rejecting on length does not finalize the state.
+                assert!(state.finalize_verify(&expected, 100).is_ok());
+            }
+
+            #[test]
+            fn test_finalize_verify_bad_tag() {
+                let key = SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+                let data = [0u8; 100];
+                let bad = HmacSha512::hmac(&key, &data[..99]).unwrap();
+
+                let mut state = HmacSha512::new(&key);
+                state.update(data).unwrap();
+                assert!(state.finalize_verify(&bad, 100).is_err());
+            }
+        }
+
+        mod test_oneshot_small {
+            use super::*;
+
+            #[test]
+            /// The small-message path of `hmac()` must agree with the streaming interface,
+            /// on both sides of the blocksize boundary.
+            fn test_oneshot_equals_streaming() {
+                let sk = SecretKey::from_slice_padded(&[1u8; 128]).unwrap();
+                let data = [255u8; sha512::SHA512_BLOCKSIZE * 2];
+
+                for len in 0..=data.len() {
+                    let mut state = HmacSha512::new(&sk);
+                    state.update(&data[..len]).unwrap();
+
+                    assert_eq!(
+                        HmacSha512::hmac(&sk, &data[..len]).unwrap(),
+                        state.finalize().unwrap()
+                    );
+                }
             }
         }
 
@@ -875,13 +4337,17 @@ pub mod sha512 {
                 }
 
                 fn one_shot(input: &[u8]) -> Result<Tag, UnknownCryptoError> {
-                    HmacSha512::hmac(&SecretKey::from_slice(&KEY).unwrap(), input)
+                    HmacSha512::hmac(&SecretKey::from_slice_padded(&KEY).unwrap(), input)
                 }
 
                 fn verify_result(expected: &Tag, input: &[u8]) -> Result<(), UnknownCryptoError> {
                     // This will only run verification tests on differing input. They do not
                     // include tests for different secret keys.
-                    HmacSha512::verify(expected, &SecretKey::from_slice(&KEY).unwrap(), input)
+                    HmacSha512::verify(
+                        expected,
+                        &SecretKey::from_slice_padded(&KEY).unwrap(),
+                        input,
+                    )
                 }
 
                 fn compare_states(state_1: &HmacSha512, state_2: &HmacSha512) {
@@ -891,7 +4357,7 @@ pub mod sha512 {
 
             #[test]
             fn default_consistency_tests() {
-                let initial_state = HmacSha512::new(&SecretKey::from_slice(&KEY).unwrap());
+                let initial_state = HmacSha512::new(&SecretKey::from_slice_padded(&KEY).unwrap());
 
                 let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha512>::new(
                     initial_state,
@@ -905,7 +4371,7 @@ pub mod sha512 {
             /// Related bug: https://github.com/orion-rs/orion/issues/46
             /// Test different streaming state usage patterns.
             fn prop_input_to_consistency(data: Vec<u8>) -> bool {
-                let initial_state = HmacSha512::new(&SecretKey::from_slice(&KEY).unwrap());
+                let initial_state = HmacSha512::new(&SecretKey::from_slice_padded(&KEY).unwrap());
 
                 let test_runner = StreamingContextConsistencyTester::<Tag, HmacSha512>::new(
                     initial_state,
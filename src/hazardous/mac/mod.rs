@@ -28,3 +28,166 @@ pub mod poly1305;
 
 /// BLAKE2b as specified in the [RFC 7693](https://tools.ietf.org/html/rfc7693).
 pub mod blake2b;
+
+/// Length-prefixed records authenticated with HMAC-SHA512.
+pub mod framed;
+
+#[cfg(feature = "experimental")]
+/// Tree mode of HMAC-SHA512 for large inputs. __WARNING:__ Experimental feature.
+pub mod tree;
+
+#[cfg(feature = "experimental")]
+/// Non-standard, 32-byte tags derived from Poly1305. __WARNING:__ Experimental feature.
+pub mod poly1305_wide;
+
+use crate::errors::UnknownCryptoError;
+
+pub(crate) mod sealed {
+    /// Implemented only by the MACs in this crate, so that [`super::Mac::verify()`]
+    /// is always backed by a tag type that compares in constant time.
+    pub trait Sealed {}
+}
+
+/// A streaming MAC, for code that is generic over the MAC primitive.
+///
+/// This trait is sealed and cannot be implemented outside of this crate:
+/// ```rust,compile_fail
+/// use orion::errors::UnknownCryptoError;
+/// use orion::hazardous::mac::{poly1305, Mac};
+///
+/// struct MyMac;
+///
+/// impl Mac for MyMac {
+///     type Key = poly1305::OneTimeKey;
+///     type Tag = poly1305::Tag;
+///
+///     fn new(_key: &Self::Key) -> Self {
+///         MyMac
+///     }
+///
+///     fn update(&mut self, _data: &[u8]) -> Result<(), UnknownCryptoError> {
+///         Ok(())
+///     }
+///
+///     fn finalize(&mut self) -> Result<Self::Tag, UnknownCryptoError> {
+///         Err(UnknownCryptoError)
+///     }
+///
+///     fn reset_with_key(&mut self, _key: &Self::Key) {}
+/// }
+/// ```
+///
+/// # Example:
+/// ```rust
+/// use orion::errors::UnknownCryptoError;
+/// use orion::hazardous::mac::{hmac, poly1305, Mac};
+///
+/// fn authenticate<M: Mac>(key: &M::Key, chunks: &[&[u8]]) -> Result<M::Tag, UnknownCryptoError> {
+///     let mut state = M::new(key);
+///     for chunk in chunks {
+///         state.update(chunk)?;
+///     }
+///
+///     state.finalize()
+/// }
+///
+/// let hmac_key = hmac::sha512::SecretKey::from_slice_padded(&[1u8; 32])?;
+/// let tag = authenticate::<hmac::sha512::HmacSha512>(&hmac_key, &[b"Some ", b"message."])?;
+/// assert!(hmac::sha512::HmacSha512::verify(&tag, &hmac_key, b"Some message.").is_ok());
+///
+/// let poly_key = poly1305::OneTimeKey::from_slice(&[1u8; 32])?;
+/// let tag = authenticate::<poly1305::Poly1305>(&poly_key, &[b"Some ", b"message."])?;
+/// assert!(poly1305::Poly1305::verify(&tag, &poly_key, b"Some message.").is_ok());
+/// # Ok::<(), UnknownCryptoError>(())
+/// ```
+pub trait Mac: sealed::Sealed + Sized {
+    /// The key type used to initialize the state.
+    type Key;
+
+    /// The tag type returned when finalizing. Comparison is in constant time.
+    type Tag: PartialEq;
+
+    /// Initialize a new state with `key`.
+    fn new(key: &Self::Key) -> Self;
+
+    /// Update state with `data`. This can be called multiple times.
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError>;
+
+    /// Return the tag of all data passed to [`Self::update()`].
+    fn finalize(&mut self) -> Result<Self::Tag, UnknownCryptoError>;
+
+    /// Reset to [`Self::new()`] state, with `key`.
+    ///
+    /// There is no keyless reset, because a Poly1305 one-time key must not be reused.
+    fn reset_with_key(&mut self, key: &Self::Key);
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Verify a tag of `data` in constant time.
+    fn verify(
+        expected: &Self::Tag,
+        key: &Self::Key,
+        data: &[u8],
+    ) -> Result<(), UnknownCryptoError> {
+        let mut state = Self::new(key);
+        state.update(data)?;
+
+        if &state.finalize()? == expected {
+            Ok(())
+        } else {
+            Err(UnknownCryptoError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_mac_trait {
+    use super::*;
+
+    /// Run the same checks on any `Mac`, comparing against its own one-shot `expected`.
+    fn check_mac<M: Mac>(key: &M::Key, expected: &M::Tag) {
+        let data = [0u8; 300];
+
+        let mut state = M::new(key);
+        for chunk in data.chunks(7) {
+            state.update(chunk).unwrap();
+        }
+        assert!(&state.finalize().unwrap() == expected);
+        assert!(state.update(&data).is_err());
+        assert!(state.finalize().is_err());
+
+        state.reset_with_key(key);
+        state.update(&data).unwrap();
+        assert!(&state.finalize().unwrap() == expected);
+
+        assert!(M::verify(expected, key, &data).is_ok());
+        assert!(M::verify(expected, key, &data[1..]).is_err());
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        let key = hmac::sha256::SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+        let expected = hmac::sha256::HmacSha256::hmac(&key, &[0u8; 300]).unwrap();
+        check_mac::<hmac::sha256::HmacSha256>(&key, &expected);
+    }
+
+    #[test]
+    fn test_hmac_sha384() {
+        let key = hmac::sha384::SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+        let expected = hmac::sha384::HmacSha384::hmac(&key, &[0u8; 300]).unwrap();
+        check_mac::<hmac::sha384::HmacSha384>(&key, &expected);
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        let key = hmac::sha512::SecretKey::from_slice_padded(&[1u8; 32]).unwrap();
+        let expected = hmac::sha512::HmacSha512::hmac(&key, &[0u8; 300]).unwrap();
+        check_mac::<hmac::sha512::HmacSha512>(&key, &expected);
+    }
+
+    #[test]
+    fn test_poly1305() {
+        let key = poly1305::OneTimeKey::from_slice(&[1u8; 32]).unwrap();
+        let expected = poly1305::Poly1305::poly1305(&key, &[0u8; 300]).unwrap();
+        check_mac::<poly1305::Poly1305>(&key, &expected);
+    }
+}
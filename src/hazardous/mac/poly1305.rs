@@ -24,6 +24,9 @@
 //! # About:
 //! This implementation is based on [poly1305-donna] by Andrew Moon.
 //!
+//! The key, message blocks and tag are always converted as little-endian,
+//! as specified in RFC 8439. Tags do not depend on the endianness of the host.
+//!
 //! # Parameters:
 //! - `data`: Data to be authenticated.
 //! - `one_time_key`: One-time key used to authenticate.
@@ -31,10 +34,16 @@
 //!
 //! # Errors:
 //! An error will be returned if:
-//! - [`finalize()`] is called twice without a [`reset()`] in between.
-//! - [`update()`] is called after [`finalize()`] without a [`reset()`] in
+//! - [`finalize()`] is called twice without a [`reset_with_key()`] in between.
+//! - [`update()`] is called after [`finalize()`] without a [`reset_with_key()`] in
 //!   between.
+//! - More than 2^64-1 bytes are passed to [`update()`] without a [`reset_with_key()`]
+//!   in between.
 //! - The calculated tag does not match the expected when verifying.
+//! - The amount of data passed to [`update()`] does not match the expected
+//!   length given to [`finalize_verify()`].
+//! - [`update_aligned()`] is called while [`update()`] has left bytes
+//!   buffered in the state.
 //!
 //! # Security:
 //! - A given key must never be used more than once. A unique [`OneTimeKey`],
@@ -42,6 +51,14 @@
 //!   it reveals enough information for an attacker to forge future authentications with the same key.
 //! - The one-time key should be generated using a CSPRNG.
 //!   [`OneTimeKey::generate()`] can be used for this.
+//! - The `poly1305-audit` feature replaces the final reduction modulo 2^130-5
+//!   with a version written out step by step for review, that selects its
+//!   result with [`subtle`]. It produces the same tags. Since the reduction runs once
+//!   per tag, it only adds a few nanoseconds to each call of [`finalize()`],
+//!   which is measurable for very short messages and negligible otherwise.
+//! - The `debug-reuse-check` feature makes [`new_guarded()`] return an error when
+//!   a one-time key is used twice on the same thread. This is only meant for
+//!   catching key reuse during testing, and does not replace generating a unique key.
 //!
 //! # Recommendation:
 //! - If you are unsure of whether to use HMAC or Poly1305, it is most often
@@ -64,8 +81,11 @@
 //! # Ok::<(), orion::errors::UnknownCryptoError>(())
 //! ```
 //! [`update()`]: poly1305::Poly1305::update
-//! [`reset()`]: poly1305::Poly1305::reset
+//! [`reset_with_key()`]: poly1305::Poly1305::reset_with_key
+//! [`update_aligned()`]: poly1305::Poly1305::update_aligned
 //! [`finalize()`]: poly1305::Poly1305::finalize
+//! [`finalize_verify()`]: poly1305::Poly1305::finalize_verify
+//! [`new_guarded()`]: poly1305::Poly1305::new_guarded
 //! [`OneTimeKey::generate()`]: poly1305::OneTimeKey::generate
 //! [`OneTimeKey`]: poly1305::OneTimeKey
 //! [poly1305-donna]: https://github.com/floodyberry/poly1305-donna
@@ -75,14 +95,17 @@ use crate::{
     errors::UnknownCryptoError,
     util::endianness::{load_u32_le, store_u32_into_le},
 };
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::string::String;
 use fiat_crypto::poly1305_32::{
     fiat_poly1305_add, fiat_poly1305_carry, fiat_poly1305_carry_mul, fiat_poly1305_from_bytes,
     fiat_poly1305_loose_field_element, fiat_poly1305_relax, fiat_poly1305_selectznz,
     fiat_poly1305_subborrowx_u26, fiat_poly1305_tight_field_element, fiat_poly1305_u1,
 };
+use zeroize::Zeroizing;
 
 /// The blocksize which Poly1305 operates on.
-const POLY1305_BLOCKSIZE: usize = 16;
+pub const POLY1305_BLOCKSIZE: usize = 16;
 /// The output size for Poly1305.
 pub const POLY1305_OUTSIZE: usize = 16;
 /// The key size for Poly1305.
@@ -90,6 +113,41 @@ pub const POLY1305_KEYSIZE: usize = 32;
 /// Type for a Poly1305 tag.
 type Poly1305Tag = [u8; POLY1305_OUTSIZE];
 
+#[cfg(feature = "debug-reuse-check")]
+/// The amount of one-time keys that `Poly1305::new_guarded()` remembers per thread.
+const REUSE_CHECK_KEYS: usize = 1024;
+
+#[cfg(feature = "debug-reuse-check")]
+std::thread_local! {
+    /// SHA-256 digests of the one-time keys most recently passed to
+    /// `Poly1305::new_guarded()` on this thread, oldest first.
+    static RECENT_KEYS: core::cell::RefCell<std::collections::VecDeque<[u8; 32]>> =
+        const { core::cell::RefCell::new(std::collections::VecDeque::new()) };
+}
+
+#[cfg(feature = "debug-reuse-check")]
+/// Record that `one_time_key` has been used on this thread, failing if it already was.
+fn record_key_use(one_time_key: &OneTimeKey) -> Result<(), UnknownCryptoError> {
+    use crate::hazardous::hash::sha2::sha256::Sha256;
+
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(Sha256::digest(one_time_key.unprotected_as_bytes())?.as_ref());
+
+    RECENT_KEYS.with(|keys| {
+        let mut keys = keys.borrow_mut();
+        if keys.contains(&digest) {
+            log_misuse!("Poly1305: one-time key passed to new_guarded() more than once");
+            return Err(UnknownCryptoError);
+        }
+        if keys.len() == REUSE_CHECK_KEYS {
+            keys.pop_front();
+        }
+        keys.push_back(digest);
+
+        Ok(())
+    })
+}
+
 construct_secret_key! {
     /// A type to represent the `OneTimeKey` that Poly1305 uses for authentication.
     ///
@@ -111,10 +169,85 @@ construct_tag! {
     /// # Errors:
     /// An error will be returned if:
     /// - `slice` is not 16 bytes.
-    (Tag, test_tag, POLY1305_OUTSIZE, POLY1305_OUTSIZE)
+    ///
+    /// # Note:
+    /// Tags of different MACs are distinct types and cannot be compared with each other:
+    /// ```rust,compile_fail
+    /// use orion::hazardous::mac::{hmac, poly1305};
+    ///
+    /// let poly1305_tag = poly1305::Tag::from([0u8; 16]);
+    /// let hmac_tag = hmac::sha256::Tag::from([0u8; 32]);
+    ///
+    /// assert!(poly1305_tag != hmac_tag);
+    /// ```
+    ///
+    /// Nor can one be passed where the other is expected:
+    /// ```rust,compile_fail
+    /// use orion::hazardous::mac::{hmac, poly1305};
+    ///
+    /// let poly1305_tag = poly1305::Tag::from([0u8; 16]);
+    /// let key = hmac::sha256::SecretKey::from_slice(&[0u8; 64])?;
+    ///
+    /// let _ = hmac::sha256::HmacSha256::verify(&poly1305_tag, &key, b"data");
+    /// # Ok::<(), orion::errors::UnknownCryptoError>(())
+    /// ```
+    // A Poly1305 tag is exactly one block: the accumulator reduced modulo 2^128.
+    (Tag, test_tag, POLY1305_OUTSIZE, POLY1305_OUTSIZE, size = POLY1305_BLOCKSIZE)
+}
+
+impl_from_trait!(@fixed_bytes Tag, POLY1305_OUTSIZE);
+
+const _: () = assert!(POLY1305_KEYSIZE == 2 * POLY1305_BLOCKSIZE);
+
+impl Tag {
+    /// Return the tag as the little-endian encoding of the 128-bit number
+    /// `(h + s) mod 2^128`. This is the order defined in RFC 8439, and the same as
+    /// [`Self::unprotected_as_bytes()`]. __**Warning**__: Should not be used unless strictly
+    /// needed. This __**breaks protections**__ that the type implements.
+    pub fn to_le_bytes(&self) -> [u8; POLY1305_OUTSIZE] {
+        let mut bytes = [0u8; POLY1305_OUTSIZE];
+        bytes.copy_from_slice(self.unprotected_as_bytes());
+
+        bytes
+    }
+
+    /// Return the tag as the big-endian encoding of the 128-bit number
+    /// `(h + s) mod 2^128`, which is [`Self::to_le_bytes()`] reversed. __**Warning**__:
+    /// Should not be used unless strictly needed. This __**breaks protections**__ that
+    /// the type implements.
+    pub fn to_be_bytes(&self) -> [u8; POLY1305_OUTSIZE] {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+
+        bytes
+    }
 }
 
-impl_from_trait!(Tag, POLY1305_OUTSIZE);
+/// The masks clamping each of the five 26-bit limbs of `r`, as specified in RFC 8439.
+const R_CLAMP: [u32; 5] = [0x3ffffff, 0x3ffff03, 0x3ffc0ff, 0x3f03fff, 0x00fffff];
+/// Upper bound of each limb of a `fiat_poly1305_tight_field_element`.
+const TIGHT_LIMB_MAX: u32 = 0x4000000;
+/// Upper bound of each limb of a `fiat_poly1305_loose_field_element`.
+const LOOSE_LIMB_MAX: u32 = 0xc000000;
+
+// Bounds analysis of `process_block()`. The accumulator `a` and the message block
+// `m` are tight, so `h = a + m` is loose. `r` is clamped, so each of its limbs is
+// at most 2^26 - 1 and it is also loose. `fiat_poly1305_carry_mul(h, r)` computes
+// `r[i] * 5` in `u32`, and then sums five products of a limb of `h` and one of
+// `r` or `r * 5` in `u64`, plus a carry. With both operands at their loose bounds,
+// neither can overflow, so this holds for clamped and non-clamped `r` alike as
+// long as its limbs are loose.
+const _: () = assert!(TIGHT_LIMB_MAX as u64 * 2 <= LOOSE_LIMB_MAX as u64);
+const _: () = assert!(R_CLAMP[0] < TIGHT_LIMB_MAX);
+const _: () = assert!(LOOSE_LIMB_MAX as u64 * 5 <= u32::MAX as u64);
+const _: () = assert!(
+    (LOOSE_LIMB_MAX as u64 * (LOOSE_LIMB_MAX as u64 * 5)) * 5 + (u64::MAX >> 26) < u64::MAX
+);
+
+/// Check that every limb is at most `max`.
+fn limbs_within(limbs: &[u32; 5], max: u32) -> bool {
+    limbs.iter().all(|limb| *limb <= max)
+}
 
 #[derive(Clone)]
 /// Poly1305 streaming state.
@@ -124,16 +257,13 @@ pub struct Poly1305 {
     s: [u32; 4],
     leftover: usize,
     buffer: [u8; POLY1305_BLOCKSIZE],
+    bytes_processed: u64,
     is_finalized: bool,
 }
 
 impl Drop for Poly1305 {
     fn drop(&mut self) {
-        use zeroize::Zeroize;
-        self.a.0.zeroize();
-        self.r.0.zeroize();
-        self.s.zeroize();
-        self.buffer.zeroize();
+        self.wipe();
     }
 }
 
@@ -141,12 +271,14 @@ impl core::fmt::Debug for Poly1305 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "Poly1305 {{ a: [***OMITTED***], r: [***OMITTED***], s: [***OMITTED***], leftover: [***OMITTED***], buffer: [***OMITTED***], is_finalized: {:?} }}",
-            self.is_finalized
+            "Poly1305 {{ a: [***OMITTED***], r: [***OMITTED***], s: [***OMITTED***], leftover: [***OMITTED***], buffer: [***OMITTED***], bytes_processed: {:?}, is_finalized: {:?} }}",
+            self.bytes_processed, self.is_finalized
         )
     }
 }
 
+impl_extend_trait!(Poly1305);
+
 impl Poly1305 {
     /// Prime 2^130-5 in little-endian.
     const PRIME: [u8; 17] = [
@@ -167,19 +299,26 @@ impl Poly1305 {
         let mut m = fiat_poly1305_tight_field_element([0u32; 5]);
         fiat_poly1305_from_bytes(&mut m, &mb);
 
+        // See the bounds analysis at `LOOSE_LIMB_MAX`.
+        debug_assert!(limbs_within(&self.a.0, TIGHT_LIMB_MAX));
+        debug_assert!(limbs_within(&m.0, TIGHT_LIMB_MAX));
+        debug_assert!(limbs_within(&self.r.0, LOOSE_LIMB_MAX));
+
         // h += m
         let mut h = fiat_poly1305_loose_field_element([0u32; 5]);
         fiat_poly1305_add(&mut h, &self.a, &m);
+        debug_assert!(limbs_within(&h.0, LOOSE_LIMB_MAX));
         // h *= r with partial reduction modulo p
         fiat_poly1305_carry_mul(&mut self.a, &h, &self.r);
+        debug_assert!(limbs_within(&self.a.0, TIGHT_LIMB_MAX));
 
         Ok(())
     }
 
     #[rustfmt::skip]
-    #[allow(clippy::identity_op)]
-    /// Remaining processing after all data blocks have been processed.
-    fn process_end_of_stream(&mut self) {
+    #[cfg_attr(feature = "poly1305-audit", allow(dead_code))]
+    /// Fully reduce the accumulator modulo 2^130-5, using Fiat Crypto primitives.
+    fn reduce_fiat(&self) -> [u32; 5] {
         // full carry h
         let mut buf_h = fiat_poly1305_tight_field_element([0u32; 5]);
         let mut a_relaxed = fiat_poly1305_loose_field_element([0u32; 5]);
@@ -201,6 +340,68 @@ impl Poly1305 {
         let mut ret = [0u32; 5];
         fiat_poly1305_selectznz(&mut ret, carry,&[g0, g1, g2, g3, g4], &buf_h.0);
 
+        ret
+    }
+
+    #[rustfmt::skip]
+    #[cfg(any(test, feature = "poly1305-audit"))]
+    /// Fully reduce the accumulator modulo 2^130-5, written out step by step for review.
+    ///
+    /// Every step is a fixed sequence of additions, shifts and masks over all five
+    /// 26-bit limbs. There are no branches, no table lookups and no early exits. The final
+    /// choice between `h` and `h - p` is made with [`subtle`], whose selection is
+    /// guarded by an optimization barrier, instead of a mask the compiler could
+    /// turn back into a branch.
+    fn reduce_audit(&self) -> [u32; 5] {
+        use subtle::{Choice, ConditionallySelectable};
+        const MASK: u32 = 0x3ffffff;
+
+        let mut h = self.a.0;
+
+        // Three full carry passes, each folding the carry out of the top limb back into
+        // the bottom one, since 2^130 = 5 (mod p). Tight limbs are at most 2^26, so the
+        // first pass leaves a small carry, the second can only carry if every
+        // limb is saturated, and the third never carries. Afterwards every limb is below
+        // 2^26, so that `h < 2^130`.
+        let mut c: u32 = 0;
+        for _ in 0..3 {
+            h[0] += c * 5; c = h[0] >> 26; h[0] &= MASK;
+            h[1] += c; c = h[1] >> 26; h[1] &= MASK;
+            h[2] += c; c = h[2] >> 26; h[2] &= MASK;
+            h[3] += c; c = h[3] >> 26; h[3] &= MASK;
+            h[4] += c; c = h[4] >> 26; h[4] &= MASK;
+        }
+        debug_assert_eq!(c, 0);
+
+        // g = h + 5 - 2^130 = h - p, computed for every input.
+        let mut g = [0u32; 5];
+        g[0] = h[0] + 5; c = g[0] >> 26; g[0] &= MASK;
+        g[1] = h[1] + c; c = g[1] >> 26; g[1] &= MASK;
+        g[2] = h[2] + c; c = g[2] >> 26; g[2] &= MASK;
+        g[3] = h[3] + c; c = g[3] >> 26; g[3] &= MASK;
+        g[4] = (h[4] + c).wrapping_sub(1 << 26);
+
+        // h >= p exactly when g did not borrow, in which case the top bit of g[4] is clear.
+        let h_ge_p = Choice::from(((g[4] >> 31) ^ 1) as u8);
+        g[4] &= MASK;
+
+        let mut ret = [0u32; 5];
+        for (out, (h_limb, g_limb)) in ret.iter_mut().zip(h.iter().zip(g.iter())) {
+            *out = u32::conditional_select(h_limb, g_limb, h_ge_p);
+        }
+
+        ret
+    }
+
+    #[rustfmt::skip]
+    #[allow(clippy::identity_op)]
+    /// Remaining processing after all data blocks have been processed.
+    fn process_end_of_stream(&mut self) {
+        #[cfg(not(feature = "poly1305-audit"))]
+        let ret = self.reduce_fiat();
+        #[cfg(feature = "poly1305-audit")]
+        let ret = self.reduce_audit();
+
         let mut h0 = ret[0];
         let mut h1 = ret[1];
         let mut h2 = ret[2];
@@ -229,29 +430,100 @@ impl Poly1305 {
     #[allow(clippy::unreadable_literal)]
     /// Initialize a `Poly1305` struct with a given one-time key.
     pub fn new(one_time_key: &OneTimeKey) -> Self {
-        let mut state = Self {
+        let key = one_time_key.unprotected_as_bytes();
+
+        // The limbs are derived from the key, so they are wiped once copied into the state.
+        let r = Zeroizing::new([
+            (load_u32_le(&key[0..4])) & R_CLAMP[0],
+            (load_u32_le(&key[3..7]) >> 2) & R_CLAMP[1],
+            (load_u32_le(&key[6..10]) >> 4) & R_CLAMP[2],
+            (load_u32_le(&key[9..13]) >> 6) & R_CLAMP[3],
+            (load_u32_le(&key[12..16]) >> 8) & R_CLAMP[4],
+        ]);
+
+        let s = Zeroizing::new([
+            load_u32_le(&key[16..20]),
+            load_u32_le(&key[20..24]),
+            load_u32_le(&key[24..28]),
+            load_u32_le(&key[28..32]),
+        ]);
+
+        Self::from_clamped(*r, *s)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Initialize a `Poly1305` struct with a given one-time key, checking that the
+    /// key has not been used before.
+    ///
+    /// With the `debug-reuse-check` feature, a SHA-256 digest of each of the last 1024
+    /// one-time keys passed to this function is kept per thread, and an error is
+    /// returned if `one_time_key` is one of them. Without the feature, this is the
+    /// same as [`Self::new()`] and never fails.
+    pub fn new_guarded(one_time_key: &OneTimeKey) -> Result<Self, UnknownCryptoError> {
+        #[cfg(feature = "debug-reuse-check")]
+        record_key_use(one_time_key)?;
+
+        Ok(Self::new(one_time_key))
+    }
+
+    /// Initialize a `Poly1305` struct from an already clamped `r`, given as five
+    /// 26-bit limbs, and `s`, given as four little-endian words.
+    ///
+    /// The caller is responsible for clamping `r`. It is only checked in debug builds.
+    pub(crate) fn from_clamped(r: [u32; 5], s: [u32; 4]) -> Self {
+        debug_assert!(r.iter().zip(R_CLAMP.iter()).all(|(r, c)| r & !c == 0));
+
+        Self {
             a: fiat_poly1305_tight_field_element([0u32; 5]),
-            r: fiat_poly1305_loose_field_element([0u32; 5]),
-            s: [0u32; 4],
+            r: fiat_poly1305_loose_field_element(r),
+            s,
             leftover: 0,
             buffer: [0u8; POLY1305_BLOCKSIZE],
+            bytes_processed: 0,
             is_finalized: false,
-        };
+        }
+    }
 
-        state.r[0] = (load_u32_le(&one_time_key.unprotected_as_bytes()[0..4])) & 0x3ffffff;
-        state.r[1] = (load_u32_le(&one_time_key.unprotected_as_bytes()[3..7]) >> 2) & 0x3ffff03;
-        state.r[2] = (load_u32_le(&one_time_key.unprotected_as_bytes()[6..10]) >> 4) & 0x3ffc0ff;
-        state.r[3] = (load_u32_le(&one_time_key.unprotected_as_bytes()[9..13]) >> 6) & 0x3f03fff;
-        state.r[4] = (load_u32_le(&one_time_key.unprotected_as_bytes()[12..16]) >> 8) & 0x00fffff;
+    /// Initialize a `Poly1305` struct with a given one-time key and wipe the key
+    /// from `one_time_key` once it has been loaded into the state.
+    fn _new_and_wipe(one_time_key: &mut OneTimeKey) -> Self {
+        use zeroize::Zeroize;
 
-        state.s[0] = load_u32_le(&one_time_key.unprotected_as_bytes()[16..20]);
-        state.s[1] = load_u32_le(&one_time_key.unprotected_as_bytes()[20..24]);
-        state.s[2] = load_u32_le(&one_time_key.unprotected_as_bytes()[24..28]);
-        state.s[3] = load_u32_le(&one_time_key.unprotected_as_bytes()[28..32]);
+        let state = Self::new(one_time_key);
+        one_time_key.value.zeroize();
 
         state
     }
 
+    /// Initialize a `Poly1305` struct, taking ownership of the one-time key.
+    ///
+    /// The clamped `r` and `s` are copied into the state, which holds no reference
+    /// to `one_time_key`. The key is wiped immediately after initialization, instead
+    /// of remaining in memory for as long as the stream is being authenticated.
+    pub fn take_key(mut one_time_key: OneTimeKey) -> Self {
+        Self::_new_and_wipe(&mut one_time_key)
+    }
+
+    /// Zeroize the key and all data held by the state. This is what `Drop` does, so a
+    /// state that is abandoned before being finalized leaves nothing behind.
+    fn wipe(&mut self) {
+        use zeroize::Zeroize;
+        self.a.0.zeroize();
+        self.r.0.zeroize();
+        self.s.zeroize();
+        self.buffer.zeroize();
+    }
+
+    /// Add `len` to the amount of bytes processed, failing if the total overflows a `u64`.
+    fn count_bytes(&mut self, len: usize) -> Result<(), UnknownCryptoError> {
+        self.bytes_processed = u64::try_from(len)
+            .ok()
+            .and_then(|len| self.bytes_processed.checked_add(len))
+            .ok_or(UnknownCryptoError)?;
+
+        Ok(())
+    }
+
     /// Update state with a `data` and pad it to blocksize with 0, if not
     /// evenly divisible by blocksize.
     pub(crate) fn process_pad_to_blocksize(
@@ -264,6 +536,7 @@ impl Poly1305 {
         if data.is_empty() {
             return Ok(());
         }
+        self.count_bytes(data.len())?;
 
         let mut blocksize_iter = data.chunks_exact(POLY1305_BLOCKSIZE);
         for block in &mut blocksize_iter {
@@ -280,23 +553,53 @@ impl Poly1305 {
         Ok(())
     }
 
+    #[deprecated(
+        note = "reset() keeps the one-time key, and reusing it breaks Poly1305. Use reset_with_key() instead."
+    )]
     /// Reset to `new()` state.
+    ///
+    /// This keeps the clamped `r` and `s` of the current key, so the next tag is computed
+    /// with the same one-time key. That is only secure if no tag was ever made public with
+    /// this state before. Use [`Self::reset_with_key()`] instead.
     pub fn reset(&mut self) {
         self.a = fiat_poly1305_tight_field_element([0u32; 5]);
         self.leftover = 0;
+        self.bytes_processed = 0;
         self.is_finalized = false;
         self.buffer = [0u8; POLY1305_BLOCKSIZE];
     }
 
+    /// Replace the key with `one_time_key` and reset to the state `new()` would return
+    /// for it. The `r` and `s` of the previous key are overwritten.
+    pub fn reset_with_key(&mut self, one_time_key: &OneTimeKey) {
+        // The previous state is dropped, which wipes it.
+        *self = Self::new(one_time_key);
+    }
+
+    /// Return the number of bytes authenticated since [`Self::new()`] or the
+    /// last [`Self::reset()`].
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Update state with `data`. This can be called multiple times.
-    pub fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+    ///
+    /// `data` can be anything that is viewable as bytes, such as `&str`, `Vec<u8>`
+    /// or a byte array.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) -> Result<(), UnknownCryptoError> {
+        self._update(data.as_ref())
+    }
+
+    fn _update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
         if self.is_finalized {
+            log_misuse!("Poly1305: update() called on a finalized state without reset()");
             return Err(UnknownCryptoError);
         }
         if data.is_empty() {
             return Ok(());
         }
+        self.count_bytes(data.len())?;
 
         let mut bytes = data;
 
@@ -334,16 +637,50 @@ impl Poly1305 {
         Ok(())
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Update state with all whole blocks of `data`, without copying any of it
+    /// into the internal buffer. The remaining `data.len() % 16` bytes are not
+    /// processed and are returned, so they can be passed to [`Self::update()`]
+    /// together with following data.
+    ///
+    /// This requires that no bytes are buffered from an earlier call to
+    /// [`Self::update()`], meaning all previous input has been a multiple of
+    /// 16 bytes.
+    pub fn update_aligned<'a>(&mut self, data: &'a [u8]) -> Result<&'a [u8], UnknownCryptoError> {
+        if self.is_finalized {
+            log_misuse!("Poly1305: update_aligned() called on a finalized state without reset()");
+            return Err(UnknownCryptoError);
+        }
+        if self.leftover != 0 {
+            return Err(UnknownCryptoError);
+        }
+
+        let aligned_len = data.len() - (data.len() % POLY1305_BLOCKSIZE);
+        let (blocks, tail) = data.split_at(aligned_len);
+        if blocks.is_empty() {
+            return Ok(tail);
+        }
+        self.count_bytes(blocks.len())?;
+
+        for block in blocks.chunks_exact(POLY1305_BLOCKSIZE) {
+            self.process_block(block)?;
+        }
+
+        Ok(tail)
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Return a Poly1305 tag.
     pub fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
         if self.is_finalized {
+            log_misuse!("Poly1305: finalize() called on a finalized state without reset()");
             return Err(UnknownCryptoError);
         }
 
         self.is_finalized = true;
 
-        let mut local_buffer: Poly1305Tag = self.buffer;
+        // Wiped when dropped, also when unwinding from a panic.
+        let mut local_buffer: Zeroizing<Poly1305Tag> = Zeroizing::new(self.buffer);
 
         if self.leftover != 0 {
             local_buffer[self.leftover] = 1;
@@ -356,13 +693,70 @@ impl Poly1305 {
                 *buf_itm = 0u8;
             }
 
-            self.process_block(&local_buffer)?;
+            self.process_block(&local_buffer[..])?;
         }
 
         self.process_end_of_stream();
-        store_u32_into_le(&self.a.0[0..4], &mut local_buffer);
+        store_u32_into_le(&self.a.0[0..4], &mut local_buffer[..]);
 
-        Ok(Tag::from(local_buffer))
+        Ok(Tag::from(*local_buffer))
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(feature = "bytes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    /// Finalize the state and write the Poly1305 tag to the end of `buf`.
+    ///
+    /// An error is returned, without finalizing the state, if `buf` cannot hold
+    /// [`POLY1305_OUTSIZE`] more bytes.
+    pub fn finalize_into_buf<B: bytes::BufMut + ?Sized>(
+        &mut self,
+        buf: &mut B,
+    ) -> Result<(), UnknownCryptoError> {
+        if buf.remaining_mut() < POLY1305_OUTSIZE {
+            return Err(UnknownCryptoError);
+        }
+
+        self.finalize()?.put_into(buf)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return a Poly1305 tag, together with the number of bytes it authenticates.
+    pub fn finalize_with_len(&mut self) -> Result<(Tag, u64), UnknownCryptoError> {
+        let tag = self.finalize()?;
+
+        Ok((tag, self.bytes_processed()))
+    }
+
+    #[cfg(any(feature = "safe_api", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return a Poly1305 tag as a lowercase hex string. This finalizes the state,
+    /// the same as [`Self::finalize()`].
+    pub fn finalize_hex(&mut self) -> Result<String, UnknownCryptoError> {
+        Ok(crate::util::to_hex(self.finalize()?.unprotected_as_bytes()))
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Finalize the state and verify the result against `expected` in constant time.
+    ///
+    /// If [`Self::bytes_processed()`] differs from `expected_len`, an error is
+    /// returned immediately, without finalizing the state. The length of the
+    /// input is not secret, so this check does not need to be constant time.
+    pub fn finalize_verify(
+        &mut self,
+        expected: &Tag,
+        expected_len: u64,
+    ) -> Result<(), UnknownCryptoError> {
+        if self.bytes_processed() != expected_len {
+            return Err(UnknownCryptoError);
+        }
+
+        if &self.finalize()? == expected {
+            Ok(())
+        } else {
+            Err(UnknownCryptoError)
+        }
     }
 
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
@@ -373,8 +767,44 @@ impl Poly1305 {
         poly_1305_state.finalize()
     }
 
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// One-shot function for generating a Poly1305 tag of `data`, consuming the one-time key.
+    ///
+    /// Because `one_time_key` is taken by value, using it for a second message is a
+    /// compile-time error. The key is wiped before the tag is returned.
+    ///
+    /// ```rust,compile_fail
+    /// use orion::hazardous::mac::poly1305::{OneTimeKey, Poly1305};
+    ///
+    /// let key = OneTimeKey::from_slice(&[0u8; 32])?;
+    ///
+    /// let _ = Poly1305::poly1305_consume(key, b"first message")?;
+    /// let _ = Poly1305::poly1305_consume(key, b"second message")?;
+    /// # Ok::<(), orion::errors::UnknownCryptoError>(())
+    /// ```
+    pub fn poly1305_consume(
+        one_time_key: OneTimeKey,
+        data: &[u8],
+    ) -> Result<Tag, UnknownCryptoError> {
+        let mut poly_1305_state = Self::take_key(one_time_key);
+        poly_1305_state.update(data)?;
+        poly_1305_state.finalize()
+    }
+
     #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
     /// Verify a Poly1305 tag in constant time.
+    ///
+    /// The result must be checked, ignoring it is a warning:
+    /// ```rust,compile_fail
+    /// #![deny(unused_must_use)]
+    /// use orion::hazardous::mac::poly1305::{OneTimeKey, Poly1305};
+    ///
+    /// let key = OneTimeKey::from_slice(&[0u8; 32])?;
+    /// let tag = Poly1305::poly1305(&key, b"Some message.")?;
+    ///
+    /// Poly1305::verify(&tag, &key, b"Some message.");
+    /// # Ok::<(), orion::errors::UnknownCryptoError>(())
+    /// ```
     pub fn verify(
         expected: &Tag,
         one_time_key: &OneTimeKey,
@@ -386,10 +816,56 @@ impl Poly1305 {
             Err(UnknownCryptoError)
         }
     }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Verify a Poly1305 tag, given as an array, in constant time.
+    ///
+    /// This is the same as [`Self::verify()`], but `expected` can be taken directly
+    /// from a received packet, without first constructing a [`Tag`].
+    pub fn verify_array(
+        expected: &[u8; POLY1305_OUTSIZE],
+        one_time_key: &OneTimeKey,
+        data: &[u8],
+    ) -> Result<(), UnknownCryptoError> {
+        if Self::poly1305(one_time_key, data)? == &expected[..] {
+            Ok(())
+        } else {
+            Err(UnknownCryptoError)
+        }
+    }
+}
+
+impl crate::hazardous::mac::sealed::Sealed for Poly1305 {}
+
+impl crate::hazardous::mac::Mac for Poly1305 {
+    type Key = OneTimeKey;
+    type Tag = Tag;
+
+    fn new(key: &OneTimeKey) -> Self {
+        Poly1305::new(key)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        Poly1305::update(self, data)
+    }
+
+    fn finalize(&mut self) -> Result<Tag, UnknownCryptoError> {
+        Poly1305::finalize(self)
+    }
+
+    fn reset_with_key(&mut self, key: &OneTimeKey) {
+        Poly1305::reset_with_key(self, key)
+    }
+
+    fn verify(expected: &Tag, key: &OneTimeKey, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        Poly1305::verify(expected, key, data)
+    }
 }
 
 // Testing public functions in the module.
 #[cfg(test)]
+// The deprecated reset() is still tested.
+#[allow(deprecated)]
 mod public {
     use super::*;
 
@@ -399,7 +875,7 @@ mod public {
         let secret_key = OneTimeKey::generate();
         let initial_state = Poly1305::new(&secret_key);
         let debug = format!("{:?}", initial_state);
-        let expected = "Poly1305 { a: [***OMITTED***], r: [***OMITTED***], s: [***OMITTED***], leftover: [***OMITTED***], buffer: [***OMITTED***], is_finalized: false }";
+        let expected = "Poly1305 { a: [***OMITTED***], r: [***OMITTED***], s: [***OMITTED***], leftover: [***OMITTED***], buffer: [***OMITTED***], bytes_processed: 0, is_finalized: false }";
         assert_eq!(debug, expected);
     }
 
@@ -420,6 +896,710 @@ mod public {
 
             Poly1305::verify(&tag, &bad_sk, &data[..]).is_err()
         }
+
+        #[quickcheck]
+        #[cfg(feature = "safe_api")]
+        /// verify_array() should agree with verify() for the same and a different key.
+        fn prop_verify_array_matches_verify(data: Vec<u8>) -> bool {
+            let sk = OneTimeKey::generate();
+            let tag = Poly1305::poly1305(&sk, &data[..]).unwrap();
+            let tag_array: [u8; POLY1305_OUTSIZE] = tag.clone().into();
+            let bad_sk = OneTimeKey::generate();
+
+            Poly1305::verify_array(&tag_array, &sk, &data[..]).is_ok()
+                && Poly1305::verify(&tag, &sk, &data[..]).is_ok()
+                && Poly1305::verify_array(&tag_array, &bad_sk, &data[..]).is_err()
+                && Poly1305::verify(&tag, &bad_sk, &data[..]).is_err()
+        }
+
+        #[test]
+        fn test_verify_array_modified_tag() {
+            let sk = OneTimeKey::generate();
+            let mut tag: [u8; POLY1305_OUTSIZE] =
+                Poly1305::poly1305(&sk, b"packet").unwrap().into();
+            assert!(Poly1305::verify_array(&tag, &sk, b"packet").is_ok());
+            assert!(Poly1305::verify_array(&tag, &sk, b"packet.").is_err());
+
+            tag[POLY1305_OUTSIZE - 1] ^= 1;
+            assert!(Poly1305::verify_array(&tag, &sk, b"packet").is_err());
+        }
+    }
+
+    #[cfg(feature = "safe_api")]
+    mod test_finalize_hex {
+        use super::*;
+
+        #[test]
+        fn test_finalize_hex_matches_tag() {
+            let key = OneTimeKey::from_slice(&[1u8; POLY1305_KEYSIZE]).unwrap();
+            let mut state = Poly1305::new(&key);
+            state.update(b"Some message.").unwrap();
+            let tag = state.finalize().unwrap();
+
+            state.reset_with_key(&key);
+            state.update(b"Some message.").unwrap();
+            let hex = state.finalize_hex().unwrap();
+
+            let expected: String = tag
+                .unprotected_as_bytes()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            assert_eq!(hex, expected);
+            assert_eq!(hex.len(), tag.len() * 2);
+            // The state was finalized.
+            assert!(state.finalize_hex().is_err());
+        }
+    }
+
+    #[cfg(feature = "safe_api")]
+    mod test_update_as_ref {
+        use super::*;
+
+        #[test]
+        fn test_update_str_vec_array() {
+            let key = OneTimeKey::from_slice(&[1u8; POLY1305_KEYSIZE]).unwrap();
+            let expected = Poly1305::poly1305(&key, &[b'a'; 16]).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update("aaaaaaaa").unwrap();
+            state.update(vec![b'a'; 4]).unwrap();
+            state.update([b'a'; 4]).unwrap();
+            assert_eq!(state.finalize().unwrap(), expected);
+
+            let data = vec![b'a'; 16];
+            let mut state = Poly1305::new(&key);
+            state.update(&data).unwrap();
+            state.update(&data[..0]).unwrap();
+            assert_eq!(state.finalize().unwrap(), expected);
+        }
+    }
+
+    mod test_empty_input {
+        use super::*;
+
+        #[test]
+        fn test_empty_input_kat() {
+            // With no message blocks, the accumulator stays zero and the tag is `s`.
+            // The key is from RFC 8439, section 2.5.2.
+            let key = OneTimeKey::from_slice(&[
+                0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5,
+                0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf,
+                0x41, 0x49, 0xf5, 0x1b,
+            ])
+            .unwrap();
+            let expected = Tag::from_slice(&[
+                0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf, 0x41, 0x49,
+                0xf5, 0x1b,
+            ])
+            .unwrap();
+
+            assert_eq!(Poly1305::poly1305(&key, &[]).unwrap(), expected);
+            assert!(Poly1305::verify(&expected, &key, &[]).is_ok());
+            assert!(Poly1305::verify(&expected, &key, &[0u8]).is_err());
+
+            let mut state = Poly1305::new(&key);
+            assert_eq!(state.leftover, 0);
+            assert_eq!(state.finalize().unwrap(), expected);
+
+            let mut state = Poly1305::new(&key);
+            state.update([]).unwrap();
+            assert!(state.update_aligned(&[]).unwrap().is_empty());
+            assert_eq!(state.finalize().unwrap(), expected);
+        }
+
+        #[test]
+        fn test_empty_input_s_max() {
+            // `s` is added modulo 2^128, which must not carry out for an all-ones `s`.
+            let key = OneTimeKey::from_slice(&[0xffu8; 32]).unwrap();
+
+            assert_eq!(
+                Poly1305::poly1305(&key, &[]).unwrap(),
+                Tag::from_slice(&[0xffu8; 16]).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    mod test_finalize_into_buf {
+        use super::*;
+        use bytes::BufMut;
+
+        #[test]
+        fn test_finalize_into_bytes_mut() {
+            let key = OneTimeKey::from_slice(&[74u8; 32]).unwrap();
+            let mut state = Poly1305::new(&key);
+            state.update(b"Some message.").unwrap();
+
+            let mut packet = bytes::BytesMut::new();
+            packet.put_slice(b"Some message.");
+            state.finalize_into_buf(&mut packet).unwrap();
+
+            let (message, tag) = packet.split_at(packet.len() - POLY1305_OUTSIZE);
+            assert_eq!(message, b"Some message.");
+            let tag = Tag::from_slice(tag).unwrap();
+            assert!(Poly1305::verify(&tag, &key, message).is_ok());
+
+            // The state is finalized, like after finalize().
+            assert!(state.finalize_into_buf(&mut packet).is_err());
+        }
+
+        #[test]
+        fn test_finalize_into_buf_too_small() {
+            let key = OneTimeKey::from_slice(&[74u8; 32]).unwrap();
+            let mut state = Poly1305::new(&key);
+            state.update(b"Some message.").unwrap();
+
+            let mut short = [0u8; POLY1305_OUTSIZE - 1];
+            assert!(state.finalize_into_buf(&mut &mut short[..]).is_err());
+
+            // The state was not finalized.
+            let mut exact = [0u8; POLY1305_OUTSIZE];
+            state.finalize_into_buf(&mut &mut exact[..]).unwrap();
+            assert_eq!(
+                Poly1305::poly1305(&key, b"Some message.").unwrap(),
+                &exact[..]
+            );
+        }
+    }
+
+    mod test_new_guarded {
+        use super::*;
+
+        #[test]
+        fn test_new_guarded_same_as_new() {
+            let key = OneTimeKey::from_slice(&[71u8; 32]).unwrap();
+            let mut state = Poly1305::new_guarded(&key).unwrap();
+            state.update(b"Some message.").unwrap();
+
+            assert_eq!(
+                state.finalize().unwrap(),
+                Poly1305::poly1305(&key, b"Some message.").unwrap()
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "debug-reuse-check")]
+        fn test_new_guarded_detects_reuse() {
+            let first = OneTimeKey::from_slice(&[72u8; 32]).unwrap();
+            let second = OneTimeKey::from_slice(&[73u8; 32]).unwrap();
+
+            assert!(Poly1305::new_guarded(&first).is_ok());
+            assert!(Poly1305::new_guarded(&second).is_ok());
+            assert!(Poly1305::new_guarded(&first).is_err());
+            assert!(Poly1305::new_guarded(&second).is_err());
+
+            // Only keys used on the same thread are remembered.
+            std::thread::spawn(move || {
+                assert!(Poly1305::new_guarded(&first).is_ok());
+            })
+            .join()
+            .unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "debug-reuse-check")]
+        fn test_new_guarded_forgets_oldest() {
+            let mut key = [0u8; 32];
+            key[0] = 74;
+            assert!(Poly1305::new_guarded(&OneTimeKey::from_slice(&key).unwrap()).is_ok());
+            for idx in 0..REUSE_CHECK_KEYS as u32 {
+                key[1..5].copy_from_slice(&(idx + 1).to_le_bytes());
+                assert!(Poly1305::new_guarded(&OneTimeKey::from_slice(&key).unwrap()).is_ok());
+            }
+
+            key[1..5].copy_from_slice(&[0u8; 4]);
+            assert!(Poly1305::new_guarded(&OneTimeKey::from_slice(&key).unwrap()).is_ok());
+        }
+
+        #[test]
+        #[cfg(not(feature = "debug-reuse-check"))]
+        fn test_new_guarded_without_feature() {
+            let key = OneTimeKey::from_slice(&[75u8; 32]).unwrap();
+            assert!(Poly1305::new_guarded(&key).is_ok());
+            assert!(Poly1305::new_guarded(&key).is_ok());
+        }
+    }
+
+    mod test_take_key {
+        use super::*;
+
+        #[test]
+        fn test_take_key_equals_new() {
+            let data = [1u8; 100];
+            let mut state = Poly1305::take_key(OneTimeKey::from_slice(&[24u8; 32]).unwrap());
+            state.update(data).unwrap();
+
+            assert_eq!(
+                state.finalize().unwrap(),
+                Poly1305::poly1305(&OneTimeKey::from_slice(&[24u8; 32]).unwrap(), &data).unwrap()
+            );
+        }
+    }
+
+    mod test_drop_mid_stream {
+        use super::*;
+
+        #[test]
+        fn test_wipe_mid_stream() {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&[24u8; 32]).unwrap());
+            // Leaves a partial block in the buffer.
+            state.update([1u8; 40]).unwrap();
+            assert_ne!(state.r.0, [0u32; 5]);
+            assert_ne!(state.s, [0u32; 4]);
+            assert_ne!(state.buffer, [0u8; POLY1305_BLOCKSIZE]);
+
+            // Drop calls wipe().
+            state.wipe();
+            assert_eq!(state.a.0, [0u32; 5]);
+            assert_eq!(state.r.0, [0u32; 5]);
+            assert_eq!(state.s, [0u32; 4]);
+            assert_eq!(state.buffer, [0u8; POLY1305_BLOCKSIZE]);
+        }
+    }
+
+    mod test_reset_with_key {
+        use super::*;
+
+        #[test]
+        fn test_reset_with_key_equals_new() {
+            let data = [1u8; 100];
+            let first_key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let second_key = OneTimeKey::from_slice(&[25u8; 32]).unwrap();
+
+            let mut state = Poly1305::new(&first_key);
+            state.update(&data[..17]).unwrap();
+            let first_tag = state.finalize().unwrap();
+
+            // Also after a partial block, without finalizing.
+            for _ in 0..2 {
+                state.reset_with_key(&second_key);
+                state.update(data).unwrap();
+                let second_tag = state.finalize().unwrap();
+                assert_eq!(second_tag, Poly1305::poly1305(&second_key, &data).unwrap());
+                assert_ne!(second_tag, first_tag);
+                assert_eq!(state.bytes_processed(), data.len() as u64);
+
+                state.reset_with_key(&first_key);
+                state.update(&data[..17]).unwrap();
+            }
+
+            let mut other = Poly1305::new(&second_key);
+            state.reset_with_key(&second_key);
+            assert_eq!(state.r.0, other.r.0);
+            assert_eq!(state.s, other.s);
+            assert_eq!(state.bytes_processed(), 0);
+            other.update(data).unwrap();
+            state.update(data).unwrap();
+            assert_eq!(state.finalize().unwrap(), other.finalize().unwrap());
+        }
+    }
+
+    mod test_from_clamped {
+        use super::*;
+
+        #[test]
+        #[allow(clippy::unreadable_literal)]
+        fn test_from_clamped_equals_new() {
+            // Key, clamped r and s from RFC 8439, section 2.5.2.
+            let key = [
+                0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5,
+                0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf,
+                0x41, 0x49, 0xf5, 0x1b,
+            ];
+            let clamped_r: u128 = 0x806d5400e52447c036d555408bed685;
+            let r: [u32; 5] =
+                core::array::from_fn(|i| ((clamped_r >> (26 * i)) as u32) & 0x3ffffff);
+            let s = [0x8a800301, 0xfdb20dfb, 0xaff6bf4a, 0x1bf54941];
+            let data = b"Cryptographic Forum Research Group";
+
+            let one_time_key = OneTimeKey::from_slice(&key).unwrap();
+            let from_key = Poly1305::new(&one_time_key);
+            assert_eq!(from_key.r.0, r);
+            assert_eq!(from_key.s, s);
+
+            let mut state = Poly1305::from_clamped(r, s);
+            state.update(data).unwrap();
+            let tag = state.finalize().unwrap();
+            assert_eq!(tag, Poly1305::poly1305(&one_time_key, data).unwrap());
+            assert_eq!(
+                tag,
+                Tag::from_slice(&[
+                    0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c,
+                    0x01, 0x27, 0xa9
+                ])
+                .unwrap()
+            );
+        }
+    }
+
+    mod test_poly1305_consume {
+        use super::*;
+
+        #[test]
+        fn test_consume_equals_poly1305() {
+            let data = [1u8; 100];
+            for len in [0, 1, 15, 16, 17, 100] {
+                assert_eq!(
+                    Poly1305::poly1305_consume(
+                        OneTimeKey::from_slice(&[24u8; 32]).unwrap(),
+                        &data[..len]
+                    )
+                    .unwrap(),
+                    Poly1305::poly1305(&OneTimeKey::from_slice(&[24u8; 32]).unwrap(), &data[..len])
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    mod test_bytes_processed {
+        use super::*;
+
+        #[test]
+        fn test_counts_updates_and_resets() {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&[24u8; 32]).unwrap());
+            assert_eq!(state.bytes_processed(), 0);
+
+            state.update([0u8; 7]).unwrap();
+            state.update([]).unwrap();
+            state.update([0u8; 16]).unwrap();
+            state.update([0u8; 100]).unwrap();
+            assert_eq!(state.bytes_processed(), 123);
+
+            let _ = state.finalize().unwrap();
+            assert_eq!(state.bytes_processed(), 123);
+
+            state.reset();
+            assert_eq!(state.bytes_processed(), 0);
+        }
+
+        #[test]
+        fn test_err_on_overflow() {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&[24u8; 32]).unwrap());
+            state.bytes_processed = u64::MAX - 1;
+
+            assert!(state.update([0u8; 1]).is_ok());
+            assert!(state.update([0u8; 1]).is_err());
+            assert_eq!(state.bytes_processed(), u64::MAX);
+            assert!(state.process_pad_to_blocksize(&[0u8; 1]).is_err());
+            // Empty updates do not change the count.
+            assert!(state.update([]).is_ok());
+        }
+    }
+
+    mod test_finalize_with_len {
+        use super::*;
+
+        #[test]
+        fn test_len_across_chunks() {
+            let key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let data = [0u8; 300];
+
+            let mut state = Poly1305::new(&key);
+            let mut expected_len = 0u64;
+            for chunk in data.chunks(7) {
+                state.update(chunk).unwrap();
+                expected_len += chunk.len() as u64;
+            }
+            state.update([]).unwrap();
+
+            let mut other = Poly1305::new(&key);
+            other.update(data).unwrap();
+            let (tag, len) = state.finalize_with_len().unwrap();
+            assert_eq!(len, expected_len);
+            assert_eq!(len, 300);
+            assert_eq!(tag, other.finalize().unwrap());
+            assert!(state.finalize_with_len().is_err());
+
+            state.reset();
+            let (_, len) = state.finalize_with_len().unwrap();
+            assert_eq!(len, 0);
+        }
+    }
+
+    mod test_extend {
+        use super::*;
+
+        #[test]
+        fn test_extend_same_as_update() {
+            let key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+
+            for len in [0, 1, 15, 16, 17, 63, 64, 65, 300] {
+                let mut state = Poly1305::new(&key);
+                state.extend(data[..len].iter().copied());
+                assert_eq!(state.bytes_processed(), len as u64);
+                let mut state_ref = Poly1305::new(&key);
+                state_ref.extend(data[..len].iter());
+
+                let expected = Poly1305::poly1305(&key, &data[..len]).unwrap();
+                assert_eq!(state.finalize().unwrap(), expected);
+                assert_eq!(state_ref.finalize().unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn test_extend_after_finalize_ignored() {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&[24u8; 32]).unwrap());
+            let _ = state.finalize().unwrap();
+            state.extend([0u8; 100]);
+            assert_eq!(state.bytes_processed(), 0);
+            assert!(state.finalize().is_err());
+        }
+    }
+
+    mod test_update_aligned {
+        use super::*;
+
+        const KEY: [u8; 32] = [24u8; 32];
+
+        #[test]
+        fn test_aligned_with_update_tail_same_as_update() {
+            let data: [u8; 5 * POLY1305_BLOCKSIZE + 7] = core::array::from_fn(|i| i as u8);
+            let key = OneTimeKey::from_slice(&KEY).unwrap();
+
+            for len in 0..data.len() {
+                let mut state = Poly1305::new(&key);
+                let tail = state.update_aligned(&data[..len]).unwrap();
+                assert_eq!(tail.len(), len % POLY1305_BLOCKSIZE);
+                assert_eq!(state.bytes_processed(), (len - tail.len()) as u64);
+                state.update(tail).unwrap();
+
+                assert_eq!(
+                    state.finalize().unwrap(),
+                    Poly1305::poly1305(&key, &data[..len]).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn test_aligned_after_aligned_update() {
+            let data = [1u8; 4 * POLY1305_BLOCKSIZE + 3];
+            let key = OneTimeKey::from_slice(&KEY).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update(&data[..POLY1305_BLOCKSIZE]).unwrap();
+            let tail = state.update_aligned(&data[POLY1305_BLOCKSIZE..]).unwrap();
+            assert_eq!(tail, &data[4 * POLY1305_BLOCKSIZE..]);
+            let tail = state.update_aligned(tail).unwrap();
+            state.update(tail).unwrap();
+
+            assert_eq!(
+                state.finalize().unwrap(),
+                Poly1305::poly1305(&key, &data).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_err_on_leftover() {
+            let key = OneTimeKey::from_slice(&KEY).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update([0u8; 3]).unwrap();
+            assert!(state.update_aligned(&[0u8; POLY1305_BLOCKSIZE]).is_err());
+            // The state is left untouched by the error.
+            assert_eq!(state.bytes_processed(), 3);
+            state.update([0u8; POLY1305_BLOCKSIZE - 3]).unwrap();
+            assert!(state.update_aligned(&[0u8; POLY1305_BLOCKSIZE]).is_ok());
+        }
+
+        #[test]
+        fn test_err_after_finalize() {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&KEY).unwrap());
+            let _ = state.finalize().unwrap();
+            assert!(state.update_aligned(&[0u8; POLY1305_BLOCKSIZE]).is_err());
+            assert!(state.update_aligned(&[]).is_err());
+            state.reset();
+            assert!(state.update_aligned(&[0u8; POLY1305_BLOCKSIZE]).is_ok());
+        }
+    }
+
+    mod test_finalize_verify {
+        use super::*;
+
+        #[test]
+        fn test_finalize_verify_ok() {
+            let key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let data = [0u8; 100];
+            let expected = Poly1305::poly1305(&key, &data).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update(&data[..40]).unwrap();
+            state.update(&data[40..]).unwrap();
+            assert!(state.finalize_verify(&expected, 100).is_ok());
+            // The state has been finalized.
+            assert!(state.finalize_verify(&expected, 100).is_err());
+        }
+
+        #[test]
+        fn test_finalize_verify_length_mismatch() {
+            let key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let data = [0u8; 100];
+            let expected = Poly1305::poly1305(&key, &data).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update(data).unwrap();
+            assert!(state.finalize_verify(&expected, 99).is_err());
+            assert!(state.finalize_verify(&expected, 101).is_err());
+            // Rejecting on length does not finalize the state.
+            assert!(state.finalize_verify(&expected, 100).is_ok());
+        }
+
+        #[test]
+        fn test_finalize_verify_bad_tag() {
+            let key = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let data = [0u8; 100];
+            let bad = Poly1305::poly1305(&key, &data[..99]).unwrap();
+
+            let mut state = Poly1305::new(&key);
+            state.update(data).unwrap();
+            assert!(state.finalize_verify(&bad, 100).is_err());
+        }
+    }
+
+    mod test_tag_byte_order {
+        use super::*;
+
+        #[test]
+        fn test_le_is_stored_order() {
+            // RFC 8439, Section 2.5.2.
+            let tag = Poly1305::poly1305(
+                &OneTimeKey::from([
+                    0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42,
+                    0xd5, 0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf,
+                    0xf6, 0xaf, 0x41, 0x49, 0xf5, 0x1b,
+                ]),
+                b"Cryptographic Forum Research Group",
+            )
+            .unwrap();
+            let expected: [u8; 16] = [
+                0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c, 0x01,
+                0x27, 0xa9,
+            ];
+
+            assert_eq!(tag.to_le_bytes(), expected);
+            assert_eq!(tag.to_le_bytes(), tag.unprotected_as_bytes());
+            assert_eq!(
+                u128::from_be_bytes(tag.to_be_bytes()),
+                u128::from_le_bytes(expected)
+            );
+        }
+
+        #[test]
+        fn test_be_is_reversed_le() {
+            let mut bytes = [0u8; POLY1305_OUTSIZE];
+            for (idx, byte) in bytes.iter_mut().enumerate() {
+                *byte = idx as u8;
+            }
+            let tag = Tag::from(bytes);
+
+            let mut reversed = tag.to_be_bytes();
+            reversed.reverse();
+            assert_eq!(reversed, tag.to_le_bytes());
+            assert_eq!(tag.to_be_bytes()[0], 15);
+        }
+
+        #[test]
+        fn test_into_array_and_back() {
+            let tag = Poly1305::poly1305(&OneTimeKey::from([1u8; 32]), b"Some message.").unwrap();
+            let expected = tag.to_le_bytes();
+
+            let bytes: [u8; POLY1305_OUTSIZE] = tag.clone().into();
+            assert_eq!(bytes, expected);
+            assert_eq!(<[u8; POLY1305_OUTSIZE]>::from(tag.clone()), expected);
+            assert_eq!(Tag::from(bytes), tag);
+        }
+    }
+
+    mod test_leftover {
+        use super::*;
+
+        const KEY: [u8; 32] = [24u8; 32];
+
+        fn tag_in_chunks(data: &[u8], splits: &[usize]) -> Tag {
+            let mut state = Poly1305::new(&OneTimeKey::from_slice(&KEY).unwrap());
+            let mut start = 0;
+            for split in splits.iter() {
+                state.update(&data[start..*split]).unwrap();
+                start = *split;
+            }
+            state.update(&data[start..]).unwrap();
+
+            state.finalize().unwrap()
+        }
+
+        #[test]
+        /// A chunk that exactly fills the remaining bytes of the buffer, for every
+        /// possible amount of buffered bytes, followed by more data.
+        fn test_chunk_exactly_fills_leftover() {
+            let data: [u8; 3 * POLY1305_BLOCKSIZE] = core::array::from_fn(|i| i as u8);
+            let expected =
+                Poly1305::poly1305(&OneTimeKey::from_slice(&KEY).unwrap(), &data).unwrap();
+
+            for leftover in 1..POLY1305_BLOCKSIZE {
+                assert_eq!(
+                    tag_in_chunks(&data, &[leftover, POLY1305_BLOCKSIZE]),
+                    expected
+                );
+                assert_eq!(
+                    tag_in_chunks(
+                        &data,
+                        &[leftover, POLY1305_BLOCKSIZE, 2 * POLY1305_BLOCKSIZE]
+                    ),
+                    expected
+                );
+                // Exactly filling the buffer at the end of the input.
+                assert_eq!(
+                    tag_in_chunks(
+                        &data[..2 * POLY1305_BLOCKSIZE],
+                        &[POLY1305_BLOCKSIZE + leftover]
+                    ),
+                    Poly1305::poly1305(
+                        &OneTimeKey::from_slice(&KEY).unwrap(),
+                        &data[..2 * POLY1305_BLOCKSIZE]
+                    )
+                    .unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn test_byte_by_byte() {
+            let data: [u8; 4 * POLY1305_BLOCKSIZE + 3] = core::array::from_fn(|i| i as u8);
+            for len in 0..data.len() {
+                let splits: [usize; 4 * POLY1305_BLOCKSIZE + 3] = core::array::from_fn(|i| i);
+                assert_eq!(
+                    tag_in_chunks(&data[..len], &splits[..len]),
+                    Poly1305::poly1305(&OneTimeKey::from_slice(&KEY).unwrap(), &data[..len])
+                        .unwrap()
+                );
+            }
+        }
+
+        #[quickcheck]
+        #[cfg(feature = "safe_api")]
+        /// Splitting the input into two or three chunks at every possible position
+        /// should always produce the same tag as the one-shot function.
+        fn prop_every_split_equals_one_shot(data: Vec<u8>) -> bool {
+            let expected =
+                Poly1305::poly1305(&OneTimeKey::from_slice(&KEY).unwrap(), &data).unwrap();
+
+            for first in 0..=data.len() {
+                if tag_in_chunks(&data, &[first]) != expected {
+                    return false;
+                }
+                // Limit the three-chunk splits to keep the test fast.
+                for second in first..=data.len().min(first + 2 * POLY1305_BLOCKSIZE) {
+                    if tag_in_chunks(&data, &[first, second]) != expected {
+                        return false;
+                    }
+                }
+            }
+
+            let splits: Vec<usize> = (0..data.len()).collect();
+            tag_in_chunks(&data, &splits) == expected
+        }
     }
 
     mod test_streaming_interface {
@@ -462,6 +1642,7 @@ mod public {
                 assert_eq!(state_1.s, state_2.s);
                 assert_eq!(state_1.leftover, state_2.leftover);
                 assert_eq!(state_1.buffer[..], state_2.buffer[..]);
+                assert_eq!(state_1.bytes_processed, state_2.bytes_processed);
                 assert_eq!(state_1.is_finalized, state_2.is_finalized);
             }
         }
@@ -496,6 +1677,8 @@ mod public {
 
 // Testing private functions in the module.
 #[cfg(test)]
+// The deprecated reset() is still tested.
+#[allow(deprecated)]
 mod private {
     use super::*;
 
@@ -529,6 +1712,32 @@ mod private {
         }
     }
 
+    mod test_new_and_wipe {
+        use super::*;
+
+        #[test]
+        fn test_key_wiped_after_init() {
+            let data = [1u8; 100];
+            let mut sk = OneTimeKey::from_slice(&[24u8; 32]).unwrap();
+            let mut state = Poly1305::_new_and_wipe(&mut sk);
+            assert_eq!(sk, [0u8; 32].as_ref());
+
+            // The state should still be usable after the key has been wiped
+            state.update(data).unwrap();
+            assert_eq!(
+                state.finalize().unwrap(),
+                Poly1305::poly1305(&OneTimeKey::from_slice(&[24u8; 32]).unwrap(), &data).unwrap()
+            );
+
+            state.reset();
+            state.update(data).unwrap();
+            assert_eq!(
+                state.finalize().unwrap(),
+                Poly1305::poly1305(&OneTimeKey::from_slice(&[24u8; 32]).unwrap(), &data).unwrap()
+            );
+        }
+    }
+
     mod test_process_block {
         use super::*;
 
@@ -547,6 +1756,34 @@ mod private {
             assert!(state.process_block(&block_2).is_err());
             assert!(state.process_block(&block_3).is_ok());
         }
+
+        #[test]
+        /// The largest clamped `r` and accumulator, with all-ones blocks, must stay
+        /// within the bounds asserted in `process_block()` in debug builds.
+        fn test_process_block_maximal_values() {
+            let sk = OneTimeKey::from_slice(&[255u8; 32]).unwrap();
+            let mut state = Poly1305::new(&sk);
+            assert_eq!(state.r.0, R_CLAMP);
+
+            for _ in 0..1000 {
+                state.process_block(&[255u8; 16]).unwrap();
+                assert!(limbs_within(&state.a.0, TIGHT_LIMB_MAX));
+            }
+
+            // A non-clamped `r` with the largest 26-bit limbs does not overflow either.
+            state.r = fiat_poly1305_loose_field_element([0x3ffffff; 5]);
+            state.a = fiat_poly1305_tight_field_element([TIGHT_LIMB_MAX; 5]);
+            for _ in 0..1000 {
+                state.process_block(&[255u8; 16]).unwrap();
+            }
+        }
+
+        #[test]
+        #[cfg(debug_assertions)]
+        #[should_panic]
+        fn test_from_clamped_rejects_unclamped_r() {
+            let _ = Poly1305::from_clamped([0x3ffffff; 5], [0u32; 4]);
+        }
     }
 
     mod test_process_end_of_stream {
@@ -570,4 +1807,78 @@ mod private {
             state.process_end_of_stream();
         }
     }
+
+    mod test_reduce_audit {
+        use super::*;
+
+        fn state_with_accumulator(a: [u32; 5]) -> Poly1305 {
+            let sk = OneTimeKey::from_slice(&[0u8; 32]).unwrap();
+            let mut state = Poly1305::new(&sk);
+            state.a = fiat_poly1305_tight_field_element(a);
+
+            state
+        }
+
+        #[test]
+        fn test_reduce_audit_edge_cases() {
+            const M: u32 = 0x3ffffff;
+            const MAX: u32 = 0x4000000;
+
+            let accumulators: [[u32; 5]; 9] = [
+                // 0
+                [0, 0, 0, 0, 0],
+                // p - 1
+                [M - 5, M, M, M, M],
+                // p
+                [M - 4, M, M, M, M],
+                // p + 1
+                [M - 3, M, M, M, M],
+                // 2^130 - 1
+                [M, M, M, M, M],
+                // All limbs at the tight upper bound
+                [MAX, MAX, MAX, MAX, MAX],
+                // Unnormalized limbs that carry into exactly p
+                [MAX - 4, M - 1, M, M, M],
+                [MAX, MAX, 0, 0, 0],
+                [0, 0, 0, 0, MAX],
+            ];
+
+            for a in accumulators.iter() {
+                let state = state_with_accumulator(*a);
+                assert_eq!(state.reduce_fiat(), state.reduce_audit());
+            }
+        }
+
+        #[test]
+        fn test_reduce_audit_after_blocks() {
+            let mut data = [0u8; 255];
+            for (idx, byte) in data.iter_mut().enumerate() {
+                *byte = !(idx as u8).wrapping_mul(37);
+            }
+            let sk = OneTimeKey::from_slice(&[0xffu8; 32]).unwrap();
+            let mut state = Poly1305::new(&sk);
+
+            for block in data.chunks_exact(POLY1305_BLOCKSIZE) {
+                state.process_block(block).unwrap();
+                assert_eq!(state.reduce_fiat(), state.reduce_audit());
+            }
+        }
+
+        #[quickcheck]
+        #[cfg(feature = "safe_api")]
+        /// The audit reduction must agree with the default one for any accumulator
+        /// within the tight bounds.
+        fn prop_reduce_audit_same_as_fiat(a: (u32, u32, u32, u32, u32)) -> bool {
+            let bound = |limb: u32| limb % 0x4000001;
+            let state = state_with_accumulator([
+                bound(a.0),
+                bound(a.1),
+                bound(a.2),
+                bound(a.3),
+                bound(a.4),
+            ]);
+
+            state.reduce_fiat() == state.reduce_audit()
+        }
+    }
 }
@@ -0,0 +1,214 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! A non-standard, 32-byte tag derived from Poly1305.
+//!
+//! Two Poly1305 one-time keys are derived from `one_time_key` as
+//! `BLAKE2b-512(key = one_time_key, "orion-poly1305-wide")`, with the first 32
+//! bytes being the first key and the last 32 bytes the second. The tag is
+//! `Poly1305(first_key, data) || Poly1305(second_key, data)`.
+//!
+//! # Parameters:
+//! - `one_time_key`: The one-time key used for authentication.
+//! - `data`: Data to be authenticated.
+//! - `expected`: The expected authentication tag.
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - The tag does not match the expected when verifying.
+//!
+//! # Security:
+//! - This is __not__ a standardized construction and the tags it produces are
+//!   not compatible with Poly1305. Neither half of the tag is equal to the
+//!   Poly1305 tag of `data` under `one_time_key`.
+//! - As with Poly1305, a `one_time_key` must only be used for a single message.
+//!   The wider tag does not make it safe to reuse the key.
+//! - The two halves are computed under independent keys, but each is still a
+//!   Poly1305 tag. Forging the whole tag is not known to be easier than forging
+//!   both halves, but this has not been formally analyzed.
+//!
+//! # Recommendation:
+//! - Only use this if a protocol requires a 32-byte tag derived from Poly1305.
+//!   Otherwise, [`Poly1305`] or [`HmacSha512`] should be preferred.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::mac::{poly1305::OneTimeKey, poly1305_wide};
+//!
+//! let one_time_key = OneTimeKey::generate();
+//! let tag = poly1305_wide::poly1305_wide(&one_time_key, b"Some message.")?;
+//!
+//! assert_eq!(tag.len(), 32);
+//! assert!(poly1305_wide::verify(&tag, &one_time_key, b"Some message.").is_ok());
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [`Poly1305`]: crate::hazardous::mac::poly1305::Poly1305
+//! [`HmacSha512`]: crate::hazardous::mac::hmac::sha512::HmacSha512
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::mac::blake2b::{self, Blake2b};
+use crate::hazardous::mac::poly1305::{
+    OneTimeKey, Poly1305, POLY1305_BLOCKSIZE, POLY1305_KEYSIZE, POLY1305_OUTSIZE,
+};
+
+/// The size of a tag returned by [`poly1305_wide`].
+pub const POLY1305_WIDE_OUTSIZE: usize = 2 * POLY1305_OUTSIZE;
+
+/// Domain separation label for deriving the two one-time keys.
+const KEY_DERIVATION_LABEL: &[u8] = b"orion-poly1305-wide";
+
+construct_tag! {
+    /// A type to represent the `Tag` that the wide Poly1305 returns.
+    ///
+    /// # Errors:
+    /// An error will be returned if:
+    /// - `slice` is not 32 bytes.
+    // Two Poly1305 tags of one block each.
+    (Tag, test_tag, POLY1305_WIDE_OUTSIZE, POLY1305_WIDE_OUTSIZE, size = 2 * POLY1305_BLOCKSIZE)
+}
+
+impl_from_trait!(@fixed_bytes Tag, POLY1305_WIDE_OUTSIZE);
+
+/// Derive the two Poly1305 one-time keys from `one_time_key`.
+fn derive_keys(one_time_key: &OneTimeKey) -> Result<(OneTimeKey, OneTimeKey), UnknownCryptoError> {
+    let blake2b_key = blake2b::SecretKey::from_slice(one_time_key.unprotected_as_bytes())?;
+    let mut ctx = Blake2b::new(&blake2b_key, 2 * POLY1305_KEYSIZE)?;
+    ctx.update(KEY_DERIVATION_LABEL)?;
+    let okm = ctx.finalize()?;
+    let (first, second) = okm.unprotected_as_bytes().split_at(POLY1305_KEYSIZE);
+
+    Ok((
+        OneTimeKey::from_slice(first)?,
+        OneTimeKey::from_slice(second)?,
+    ))
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Generate a non-standard, 32-byte tag of `data`, from two domain-separated Poly1305 tags.
+pub fn poly1305_wide(one_time_key: &OneTimeKey, data: &[u8]) -> Result<Tag, UnknownCryptoError> {
+    let (first_key, second_key) = derive_keys(one_time_key)?;
+
+    let mut tag = [0u8; POLY1305_WIDE_OUTSIZE];
+    tag[..POLY1305_OUTSIZE]
+        .copy_from_slice(Poly1305::poly1305(&first_key, data)?.unprotected_as_bytes());
+    tag[POLY1305_OUTSIZE..]
+        .copy_from_slice(Poly1305::poly1305(&second_key, data)?.unprotected_as_bytes());
+
+    Ok(Tag::from(tag))
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Verify a tag made with [`poly1305_wide`] in constant time.
+pub fn verify(
+    expected: &Tag,
+    one_time_key: &OneTimeKey,
+    data: &[u8],
+) -> Result<(), UnknownCryptoError> {
+    if &poly1305_wide(one_time_key, data)? == expected {
+        Ok(())
+    } else {
+        Err(UnknownCryptoError)
+    }
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    const KEY: [u8; 32] = [1u8; 32];
+
+    #[test]
+    fn test_deterministic() {
+        let key = OneTimeKey::from_slice(&KEY).unwrap();
+
+        assert_eq!(
+            poly1305_wide(&key, b"Some message.").unwrap(),
+            poly1305_wide(&key, b"Some message.").unwrap()
+        );
+        assert_ne!(
+            poly1305_wide(&key, b"Some message.").unwrap(),
+            poly1305_wide(&key, b"Other message.").unwrap()
+        );
+        assert_ne!(
+            poly1305_wide(&key, b"Some message.").unwrap(),
+            poly1305_wide(
+                &OneTimeKey::from_slice(&[2u8; 32]).unwrap(),
+                b"Some message."
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_differs_from_poly1305() {
+        let key = OneTimeKey::from_slice(&KEY).unwrap();
+        let plain = Poly1305::poly1305(&key, b"Some message.").unwrap();
+        let wide = poly1305_wide(&key, b"Some message.").unwrap();
+        let (first, second) = wide.unprotected_as_bytes().split_at(POLY1305_OUTSIZE);
+
+        assert_ne!(plain, first);
+        assert_ne!(plain, second);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_halves_are_poly1305_under_derived_keys() {
+        let key = OneTimeKey::from_slice(&KEY).unwrap();
+        let (first_key, second_key) = derive_keys(&key).unwrap();
+        let wide = poly1305_wide(&key, b"Some message.").unwrap();
+        let (first, second) = wide.unprotected_as_bytes().split_at(POLY1305_OUTSIZE);
+
+        assert_eq!(
+            Poly1305::poly1305(&first_key, b"Some message.").unwrap(),
+            first
+        );
+        assert_eq!(
+            Poly1305::poly1305(&second_key, b"Some message.").unwrap(),
+            second
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let key = OneTimeKey::from_slice(&KEY).unwrap();
+        let tag = poly1305_wide(&key, b"Some message.").unwrap();
+        assert!(verify(&tag, &key, b"Some message.").is_ok());
+        assert!(verify(&tag, &key, b"Other message.").is_err());
+
+        let mut bad_tag = [0u8; POLY1305_WIDE_OUTSIZE];
+        bad_tag.copy_from_slice(tag.unprotected_as_bytes());
+        bad_tag[POLY1305_WIDE_OUTSIZE - 1] ^= 1;
+        assert!(verify(&Tag::from_slice(&bad_tag).unwrap(), &key, b"Some message.").is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let key = OneTimeKey::from_slice(&KEY).unwrap();
+        let tag = poly1305_wide(&key, b"").unwrap();
+        assert!(verify(&tag, &key, b"").is_ok());
+        assert_eq!(tag.len(), POLY1305_WIDE_OUTSIZE);
+    }
+}
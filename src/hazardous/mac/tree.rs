@@ -0,0 +1,258 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! A tree mode of HMAC-SHA512 for authenticating large inputs.
+//!
+//! The input is split into chunks of `chunk_size` bytes. Each chunk is a leaf,
+//! authenticated as `HMAC(secret_key, 0x00 || LE64(index) || chunk)`. The
+//! root tag is then computed as `HMAC(secret_key, 0x01 || LE64(chunk_size) ||
+//! LE64(leaf_count) || leaf_tag_0 || leaf_tag_1 || ...)`. Empty input is
+//! treated as a single, empty leaf.
+//!
+//! With the `rayon` feature enabled, leaves are authenticated in parallel.
+//! The resulting tag is the same regardless of whether `rayon` is enabled.
+//!
+//! # Parameters:
+//! - `secret_key`: The authentication key.
+//! - `chunk_size`: The size of each leaf, in bytes.
+//! - `data`: Data to be authenticated.
+//! - `expected`: The expected authentication tag.
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - `chunk_size` is 0.
+//! - The tag does not match the expected when verifying.
+//!
+//! # Security:
+//! - This is __not__ a standardized construction and the tags it produces are
+//!   not compatible with plain HMAC-SHA512. The same `chunk_size` must be used
+//!   when authenticating and verifying.
+//! - The secret key should always be generated using a CSPRNG.
+//!   [`SecretKey::generate()`] can be used for this.
+//!
+//! # Recommendation:
+//! - Only use this if the throughput of [`HmacSha512`] is a bottleneck.
+//!   Otherwise, [`HmacSha512`] should be preferred.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::mac::{hmac::sha512::SecretKey, tree};
+//!
+//! let key = SecretKey::generate();
+//! let data = vec![0u8; 1024 * 1024];
+//!
+//! let tag = tree::authenticate(&key, 64 * 1024, &data)?;
+//! assert!(tree::authenticate_verify(&tag, &key, 64 * 1024, &data).is_ok());
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [`SecretKey::generate()`]: crate::hazardous::mac::hmac::sha512::SecretKey::generate
+//! [`HmacSha512`]: crate::hazardous::mac::hmac::sha512::HmacSha512
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::mac::hmac::sha512::{HmacSha512, SecretKey, Tag};
+
+/// Domain separation prefix for leaf tags.
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix for the root tag.
+const ROOT_PREFIX: u8 = 0x01;
+
+/// Authenticate a single leaf of the tree.
+fn leaf(secret_key: &SecretKey, index: usize, chunk: &[u8]) -> Result<Tag, UnknownCryptoError> {
+    let mut ctx = HmacSha512::new(secret_key);
+    ctx.update([LEAF_PREFIX])?;
+    ctx.update((index as u64).to_le_bytes())?;
+    ctx.update(chunk)?;
+    ctx.finalize()
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Generate a tree mode HMAC-SHA512 tag of `data`, using leaves of `chunk_size` bytes.
+pub fn authenticate(
+    secret_key: &SecretKey,
+    chunk_size: usize,
+    data: &[u8],
+) -> Result<Tag, UnknownCryptoError> {
+    if chunk_size == 0 {
+        return Err(UnknownCryptoError);
+    }
+
+    let leaf_count = if data.is_empty() {
+        1
+    } else {
+        data.len().div_ceil(chunk_size)
+    };
+
+    let mut root = HmacSha512::new(secret_key);
+    root.update([ROOT_PREFIX])?;
+    root.update((chunk_size as u64).to_le_bytes())?;
+    root.update((leaf_count as u64).to_le_bytes())?;
+
+    if data.is_empty() {
+        root.update(leaf(secret_key, 0, &[])?.unprotected_as_bytes())?;
+        return root.finalize();
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let leaves = data
+            .par_chunks(chunk_size)
+            .enumerate()
+            .map(|(index, chunk)| leaf(secret_key, index, chunk))
+            .collect::<Result<Vec<Tag>, UnknownCryptoError>>()?;
+
+        for leaf_tag in leaves.iter() {
+            root.update(leaf_tag.unprotected_as_bytes())?;
+        }
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    for (index, chunk) in data.chunks(chunk_size).enumerate() {
+        root.update(leaf(secret_key, index, chunk)?.unprotected_as_bytes())?;
+    }
+
+    root.finalize()
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Verify a tree mode HMAC-SHA512 tag in constant time.
+pub fn authenticate_verify(
+    expected: &Tag,
+    secret_key: &SecretKey,
+    chunk_size: usize,
+    data: &[u8],
+) -> Result<(), UnknownCryptoError> {
+    if &authenticate(secret_key, chunk_size, data)? == expected {
+        Ok(())
+    } else {
+        Err(UnknownCryptoError)
+    }
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    const KEY: [u8; 128] = [1u8; 128];
+
+    #[test]
+    fn test_err_on_zero_chunk_size() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        assert!(authenticate(&sk, 0, b"Some message.").is_err());
+        assert!(authenticate(&sk, 0, &[]).is_err());
+    }
+
+    #[test]
+    fn test_deterministic_for_chunk_size() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let data = [255u8; 4096 + 3];
+
+        assert_eq!(
+            authenticate(&sk, 512, &data).unwrap(),
+            authenticate(&sk, 512, &data).unwrap()
+        );
+        assert_ne!(
+            authenticate(&sk, 512, &data).unwrap(),
+            authenticate(&sk, 1024, &data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_order_sensitive() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let mut data = [0u8; 256];
+        data[..128].copy_from_slice(&[1u8; 128]);
+        data[128..].copy_from_slice(&[2u8; 128]);
+
+        let mut swapped = [0u8; 256];
+        swapped[..128].copy_from_slice(&data[128..]);
+        swapped[128..].copy_from_slice(&data[..128]);
+
+        assert_ne!(
+            authenticate(&sk, 128, &data).unwrap(),
+            authenticate(&sk, 128, &swapped).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_not_plain_hmac() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let data = [0u8; 64];
+
+        assert_ne!(
+            authenticate(&sk, 64, &data).unwrap(),
+            HmacSha512::hmac(&sk, &data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_matches_sequential_reference() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let data = [7u8; 1000];
+        let chunk_size = 300;
+
+        let mut root = HmacSha512::new(&sk);
+        root.update([ROOT_PREFIX]).unwrap();
+        root.update((chunk_size as u64).to_le_bytes()).unwrap();
+        root.update(4u64.to_le_bytes()).unwrap();
+        for (index, chunk) in data.chunks(chunk_size).enumerate() {
+            let mut ctx = HmacSha512::new(&sk);
+            ctx.update([LEAF_PREFIX]).unwrap();
+            ctx.update((index as u64).to_le_bytes()).unwrap();
+            ctx.update(chunk).unwrap();
+            root.update(ctx.finalize().unwrap().unprotected_as_bytes())
+                .unwrap();
+        }
+
+        assert_eq!(
+            authenticate(&sk, chunk_size, &data).unwrap(),
+            root.finalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let tag = authenticate(&sk, 64, &[]).unwrap();
+
+        assert!(authenticate_verify(&tag, &sk, 64, &[]).is_ok());
+        assert!(authenticate_verify(&tag, &sk, 64, &[0u8]).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let sk = SecretKey::from_slice(&KEY).unwrap();
+        let bad_sk = SecretKey::from_slice(&[2u8; 128]).unwrap();
+        let data = [0u8; 1024];
+        let tag = authenticate(&sk, 100, &data).unwrap();
+
+        assert!(authenticate_verify(&tag, &sk, 100, &data).is_ok());
+        assert!(authenticate_verify(&tag, &sk, 101, &data).is_err());
+        assert!(authenticate_verify(&tag, &bad_sk, 100, &data).is_err());
+        assert!(authenticate_verify(&tag, &sk, 100, &data[..1023]).is_err());
+    }
+}
@@ -39,15 +39,26 @@ pub mod mac;
 /// Function).
 pub mod kdf;
 
+/// Deterministic random bit generators.
+pub mod rng;
+
 /// Stream ciphers.
 pub mod stream;
 
 /// Elliptic-Curve Cryptography.
 pub mod ecc;
 
+/// Sizes of keys, nonces, blocks and outputs.
+pub mod constants;
+
 #[cfg(feature = "experimental")]
 /// Fully-committing Authenticated Encryption. __WARNING:__ Experimental feature.
 pub mod cae;
 
 /// Key Encapsulation Mechanisms (KEMs).
 pub mod kem;
+
+#[cfg(feature = "experimental")]
+#[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+/// Digital signatures. __WARNING:__ Experimental feature.
+pub mod sign;
@@ -0,0 +1,453 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! HMAC_DRBG as specified in [NIST SP 800-90A Rev. 1], using HMAC-SHA512 and
+//! without prediction resistance. The security strength is 256 bits.
+//!
+//! # Parameters:
+//! - `entropy`: Entropy input, from an approved entropy source.
+//! - `nonce`: Nonce used when instantiating.
+//! - `personalization`: Optional personalization string. Can be empty.
+//! - `additional_input`: Optional additional input. Can be empty.
+//! - `dst_out`: Destination buffer for the generated bytes.
+//! - `secret_key`: Secret key that a nonce is derived from with [`derive_nonce()`].
+//! - `message`: Message that a nonce is derived for with [`derive_nonce()`].
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - The length of `entropy` is less than 32 bytes.
+//! - The length of `nonce` is less than 16 bytes.
+//! - The length of `secret_key` is less than 32 bytes.
+//! - The length of `entropy`, `nonce`, `personalization` or `additional_input`
+//!   is greater than 2^32 bytes.
+//! - The length of `dst_out` is less than 1 or greater than 65536 bytes.
+//! - [`generate()`] is called more than 2^48 times without a [`reseed()`] in between.
+//!
+//! # Security:
+//! - The output is only as unpredictable as the entropy input. `entropy` must
+//!   come from a source with at least 256 bits of entropy, such as
+//!   [`secure_rand_bytes()`].
+//! - The same entropy input and nonce must never be used to instantiate more than
+//!   one state, unless the same output is wanted, as in deterministic nonce
+//!   generation.
+//! - If a process that holds a state is forked, both processes will produce the
+//!   same output until reseeded.
+//! - If you just need random bytes, use [`secure_rand_bytes()`] instead.
+//! - [`derive_nonce()`] gives the same output for the same `secret_key` and `message`.
+//!   This is its purpose, but it means a nonce must never be derived this way for
+//!   two different uses of the same key and message.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::{hazardous::rng::hmac_drbg::HmacDrbg, util};
+//!
+//! let mut entropy = [0u8; 32];
+//! let mut nonce = [0u8; 16];
+//! util::secure_rand_bytes(&mut entropy)?;
+//! util::secure_rand_bytes(&mut nonce)?;
+//!
+//! let mut drbg = HmacDrbg::instantiate(&entropy, &nonce, b"Personalization")?;
+//! let mut dst_out = [0u8; 64];
+//! drbg.generate(&[], &mut dst_out)?;
+//!
+//! util::secure_rand_bytes(&mut entropy)?;
+//! drbg.reseed(&entropy, &[])?;
+//! drbg.generate(b"Additional input", &mut dst_out)?;
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [NIST SP 800-90A Rev. 1]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-90Ar1.pdf
+//! [`generate()`]: crate::hazardous::rng::hmac_drbg::HmacDrbg::generate
+//! [`reseed()`]: crate::hazardous::rng::hmac_drbg::HmacDrbg::reseed
+//! [`derive_nonce()`]: crate::hazardous::rng::hmac_drbg::derive_nonce
+//! [`secure_rand_bytes()`]: crate::util::secure_rand_bytes
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::hash::sha2::sha512::{Sha512, SHA512_OUTSIZE};
+use crate::hazardous::mac::hmac::{sha512::HmacSha512, HmacFunction};
+use zeroize::Zeroize;
+
+/// The minimum length of the entropy input, which is the security strength.
+const MIN_ENTROPY_LEN: usize = 32;
+/// The minimum length of the nonce, which is half the security strength.
+const MIN_NONCE_LEN: usize = 16;
+/// The maximum length of any input (2^35 bits).
+const MAX_INPUT_LEN: u64 = 1 << 32;
+/// The maximum amount of bytes generated per request (2^19 bits).
+const MAX_REQUEST_LEN: usize = 1 << 16;
+/// The maximum amount of requests between reseeds.
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// Check that the length of an input is within `[min, MAX_INPUT_LEN]`.
+fn check_input_len(input: &[u8], min: usize) -> Result<(), UnknownCryptoError> {
+    if input.len() < min || input.len() as u64 > MAX_INPUT_LEN {
+        return Err(UnknownCryptoError);
+    }
+
+    Ok(())
+}
+
+/// HMAC_DRBG state using HMAC-SHA512.
+pub struct HmacDrbg {
+    k: [u8; SHA512_OUTSIZE],
+    v: [u8; SHA512_OUTSIZE],
+    reseed_counter: u64,
+}
+
+impl Drop for HmacDrbg {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
+
+impl core::fmt::Debug for HmacDrbg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HmacDrbg {{ k: [***OMITTED***], v: [***OMITTED***], reseed_counter: {:?} }}",
+            self.reseed_counter
+        )
+    }
+}
+
+impl HmacDrbg {
+    /// Compute HMAC(K, V || `separator` || `provided_data`) into `dest`.
+    /// No separator is used if `separator` is `None`.
+    fn hmac_kv(
+        &self,
+        separator: Option<u8>,
+        provided_data: &[&[u8]],
+        dest: &mut [u8; SHA512_OUTSIZE],
+    ) -> Result<(), UnknownCryptoError> {
+        let mut ctx = HmacSha512::_new(&self.k)?;
+        ctx._update(&self.v)?;
+        if let Some(separator) = separator {
+            ctx._update(&[separator])?;
+        }
+        for data in provided_data.iter() {
+            ctx._update(data)?;
+        }
+
+        ctx._finalize(dest)
+    }
+
+    /// The HMAC_DRBG_Update function, with `provided_data` given as the concatenation
+    /// of its parts.
+    fn update(&mut self, provided_data: &[&[u8]]) -> Result<(), UnknownCryptoError> {
+        let mut tmp = [0u8; SHA512_OUTSIZE];
+
+        self.hmac_kv(Some(0x00), provided_data, &mut tmp)?;
+        self.k.copy_from_slice(&tmp);
+        self.hmac_kv(None, &[], &mut tmp)?;
+        self.v.copy_from_slice(&tmp);
+
+        if provided_data.iter().any(|data| !data.is_empty()) {
+            self.hmac_kv(Some(0x01), provided_data, &mut tmp)?;
+            self.k.copy_from_slice(&tmp);
+            self.hmac_kv(None, &[], &mut tmp)?;
+            self.v.copy_from_slice(&tmp);
+        }
+
+        tmp.zeroize();
+
+        Ok(())
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Instantiate a new state from `entropy`, `nonce` and `personalization`.
+    pub fn instantiate(
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Result<Self, UnknownCryptoError> {
+        check_input_len(entropy, MIN_ENTROPY_LEN)?;
+        check_input_len(nonce, MIN_NONCE_LEN)?;
+        check_input_len(personalization, 0)?;
+
+        let mut state = Self {
+            k: [0x00; SHA512_OUTSIZE],
+            v: [0x01; SHA512_OUTSIZE],
+            reseed_counter: 1,
+        };
+        state.update(&[entropy, nonce, personalization])?;
+
+        Ok(state)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Reseed the state with new `entropy` and `additional_input`.
+    pub fn reseed(
+        &mut self,
+        entropy: &[u8],
+        additional_input: &[u8],
+    ) -> Result<(), UnknownCryptoError> {
+        check_input_len(entropy, MIN_ENTROPY_LEN)?;
+        check_input_len(additional_input, 0)?;
+
+        self.update(&[entropy, additional_input])?;
+        self.reseed_counter = 1;
+
+        Ok(())
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Fill `dst_out` with pseudorandom bytes, using `additional_input`.
+    pub fn generate(
+        &mut self,
+        additional_input: &[u8],
+        dst_out: &mut [u8],
+    ) -> Result<(), UnknownCryptoError> {
+        check_input_len(additional_input, 0)?;
+        if dst_out.is_empty() || dst_out.len() > MAX_REQUEST_LEN {
+            return Err(UnknownCryptoError);
+        }
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(UnknownCryptoError);
+        }
+
+        if !additional_input.is_empty() {
+            self.update(&[additional_input])?;
+        }
+
+        let mut tmp = [0u8; SHA512_OUTSIZE];
+        for chunk in dst_out.chunks_mut(SHA512_OUTSIZE) {
+            self.hmac_kv(None, &[], &mut tmp)?;
+            self.v.copy_from_slice(&tmp);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        tmp.zeroize();
+
+        self.update(&[additional_input])?;
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Derive a deterministic nonce from `secret_key` and `message` into `dst_out`.
+///
+/// Following [RFC 6979, section 3.2], without the conversions to integers modulo a
+/// group order, an [`HmacDrbg`] is instantiated with `secret_key` as the entropy input
+/// and the SHA-512 digest of `message` as the nonce, and `dst_out` is filled from it.
+///
+/// # Example:
+/// ```rust
+/// use orion::hazardous::rng::hmac_drbg;
+///
+/// let secret_key = [1u8; 32];
+/// let mut nonce = [0u8; 32];
+/// hmac_drbg::derive_nonce(&secret_key, b"Message", &mut nonce)?;
+///
+/// let mut same_nonce = [0u8; 32];
+/// hmac_drbg::derive_nonce(&secret_key, b"Message", &mut same_nonce)?;
+/// assert_eq!(nonce, same_nonce);
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
+/// [RFC 6979, section 3.2]: https://www.rfc-editor.org/rfc/rfc6979#section-3.2
+pub fn derive_nonce(
+    secret_key: &[u8],
+    message: &[u8],
+    dst_out: &mut [u8],
+) -> Result<(), UnknownCryptoError> {
+    let digest = Sha512::digest(message)?;
+    let mut drbg = HmacDrbg::instantiate(secret_key, digest.as_ref(), &[])?;
+
+    drbg.generate(&[], dst_out)
+}
+
+// Testing public functions in the module.
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "safe_api")]
+    fn test_debug_impl() {
+        let state = HmacDrbg::instantiate(&[0u8; 32], &[0u8; 16], &[]).unwrap();
+        let debug = format!("{:?}", state);
+        let expected = "HmacDrbg { k: [***OMITTED***], v: [***OMITTED***], reseed_counter: 1 }";
+        assert_eq!(debug, expected);
+    }
+
+    mod test_instantiate {
+        use super::*;
+
+        #[test]
+        fn test_input_lengths() {
+            assert!(HmacDrbg::instantiate(&[0u8; 32], &[0u8; 16], &[]).is_ok());
+            assert!(HmacDrbg::instantiate(&[0u8; 64], &[0u8; 32], &[0u8; 64]).is_ok());
+            assert!(HmacDrbg::instantiate(&[0u8; 31], &[0u8; 16], &[]).is_err());
+            assert!(HmacDrbg::instantiate(&[0u8; 32], &[0u8; 15], &[]).is_err());
+            assert!(HmacDrbg::instantiate(&[], &[], &[]).is_err());
+        }
+
+        #[test]
+        fn test_personalization_changes_output() {
+            let mut out = [0u8; 64];
+            let mut out_pers = [0u8; 64];
+            let mut drbg = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            drbg.generate(&[], &mut out).unwrap();
+            let mut drbg = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[3u8; 16]).unwrap();
+            drbg.generate(&[], &mut out_pers).unwrap();
+
+            assert_ne!(out, out_pers);
+        }
+    }
+
+    mod test_reseed {
+        use super::*;
+
+        #[test]
+        fn test_reseed() {
+            let mut drbg = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            assert!(drbg.reseed(&[0u8; 31], &[]).is_err());
+            assert!(drbg.reseed(&[4u8; 32], &[]).is_ok());
+            assert!(drbg.reseed(&[4u8; 32], &[5u8; 32]).is_ok());
+        }
+
+        #[test]
+        fn test_reseed_changes_output() {
+            let mut first = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut second = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            second.reseed(&[4u8; 32], &[]).unwrap();
+
+            let mut out_first = [0u8; 64];
+            let mut out_second = [0u8; 64];
+            first.generate(&[], &mut out_first).unwrap();
+            second.generate(&[], &mut out_second).unwrap();
+            assert_ne!(out_first, out_second);
+        }
+
+        #[test]
+        fn test_reseed_required() {
+            let mut drbg = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut out = [0u8; 16];
+            drbg.reseed_counter = RESEED_INTERVAL;
+            assert!(drbg.generate(&[], &mut out).is_ok());
+            assert!(drbg.generate(&[], &mut out).is_err());
+            drbg.reseed(&[4u8; 32], &[]).unwrap();
+            assert!(drbg.generate(&[], &mut out).is_ok());
+        }
+    }
+
+    mod test_generate {
+        use super::*;
+
+        #[test]
+        fn test_output_lengths() {
+            let mut drbg = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut out = [0u8; MAX_REQUEST_LEN + 1];
+            assert!(drbg.generate(&[], &mut out[..0]).is_err());
+            assert!(drbg.generate(&[], &mut out).is_err());
+            assert!(drbg.generate(&[], &mut out[..1]).is_ok());
+            assert!(drbg.generate(&[], &mut out[..MAX_REQUEST_LEN]).is_ok());
+        }
+
+        #[test]
+        fn test_deterministic() {
+            let mut first = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut second = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut out_first = [0u8; 100];
+            let mut out_second = [0u8; 100];
+
+            first.generate(&[3u8; 10], &mut out_first).unwrap();
+            second.generate(&[3u8; 10], &mut out_second).unwrap();
+            assert_eq!(out_first, out_second);
+
+            // The state advances after each request.
+            first.generate(&[3u8; 10], &mut out_second).unwrap();
+            assert_ne!(out_first, out_second);
+        }
+
+        #[test]
+        fn test_shorter_output_is_prefix() {
+            let mut first = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut second = HmacDrbg::instantiate(&[1u8; 32], &[2u8; 16], &[]).unwrap();
+            let mut out_long = [0u8; 130];
+            let mut out_short = [0u8; 65];
+
+            first.generate(&[], &mut out_long).unwrap();
+            second.generate(&[], &mut out_short).unwrap();
+            assert_eq!(out_long[..65], out_short);
+        }
+    }
+
+    mod test_derive_nonce {
+        use super::*;
+
+        #[test]
+        fn test_deterministic() {
+            let mut first = [0u8; 32];
+            let mut second = [0u8; 32];
+            derive_nonce(&[1u8; 32], b"Message", &mut first).unwrap();
+            derive_nonce(&[1u8; 32], b"Message", &mut second).unwrap();
+            assert_eq!(first, second);
+        }
+
+        #[test]
+        fn test_diff_input_diff_output() {
+            let mut nonce = [0u8; 32];
+            let mut other = [0u8; 32];
+            derive_nonce(&[1u8; 32], b"Message", &mut nonce).unwrap();
+
+            derive_nonce(&[1u8; 32], b"Other message", &mut other).unwrap();
+            assert_ne!(nonce, other);
+            derive_nonce(&[1u8; 32], b"", &mut other).unwrap();
+            assert_ne!(nonce, other);
+            derive_nonce(&[2u8; 32], b"Message", &mut other).unwrap();
+            assert_ne!(nonce, other);
+        }
+
+        #[test]
+        fn test_same_as_drbg() {
+            let mut expected = [0u8; 100];
+            let mut drbg = HmacDrbg::instantiate(
+                &[1u8; 32],
+                Sha512::digest(b"Message").unwrap().as_ref(),
+                &[],
+            )
+            .unwrap();
+            drbg.generate(&[], &mut expected).unwrap();
+
+            let mut actual = [0u8; 100];
+            derive_nonce(&[1u8; 32], b"Message", &mut actual).unwrap();
+            assert_eq!(actual, expected);
+
+            // A shorter nonce is a prefix of a longer one.
+            let mut short = [0u8; 16];
+            derive_nonce(&[1u8; 32], b"Message", &mut short).unwrap();
+            assert_eq!(short, expected[..16]);
+        }
+
+        #[test]
+        fn test_input_lengths() {
+            let mut nonce = [0u8; 32];
+            assert!(derive_nonce(&[1u8; 31], b"Message", &mut nonce).is_err());
+            assert!(derive_nonce(&[1u8; 32], b"Message", &mut nonce[..0]).is_err());
+            assert!(derive_nonce(&[1u8; 64], b"Message", &mut nonce[..1]).is_ok());
+        }
+    }
+}
@@ -0,0 +1,1021 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # About:
+//! Ed25519 and Ed25519ph signatures over edwards25519, as specified in the [RFC 8032].
+//!
+//! # Parameters:
+//! - `secret_key`: The secret key used to sign `message`.
+//! - `public_key`: The public key used to verify `signature`.
+//! - `message`: The message that is signed or verified.
+//! - `signature`: The signature to verify.
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - [`Ed25519ph::finalize_sign()`] or [`Ed25519ph::finalize_verify()`] is called twice
+//!   without a [`Ed25519ph::reset()`] in between.
+//! - [`Ed25519ph::update()`] is called after finalizing, without a [`Ed25519ph::reset()`]
+//!   in between.
+//! - `public_key` is not the encoding of a point on the curve.
+//! - The `R` half of `signature` is not the encoding of a point on the curve.
+//! - The `S` half of `signature` is not less than the order of the base point.
+//! - `signature` is not valid for `public_key` and `message`.
+//!
+//! # Security:
+//! - The secret key should always be generated using a CSPRNG.
+//!   [`SecretKey::generate()`] can be used for this.
+//! - Signing is deterministic and does not require any randomness.
+//! - Verification uses the cofactored equation `[8][S]B = [8]R + [8][k]A` recommended
+//!   by the RFC. Implementations using the cofactorless equation may disagree with
+//!   this one on whether some specially crafted signatures are valid.
+//! - Scalar multiplication is done with a constant-time double-and-add, without
+//!   branches or table lookups that depend on the scalar.
+//!
+//! # Recommendation:
+//! - Use [`Ed25519ph`] only when a message is too large to be held in memory, as
+//!   Ed25519 is not affected by collisions in the hash function while Ed25519ph is.
+//!
+//! # Example:
+//! ```rust
+//! # #[cfg(feature = "safe_api")] {
+//! use orion::hazardous::sign::ed25519::{sign, verify, Ed25519ph, PublicKey, SecretKey};
+//! use core::convert::TryFrom;
+//!
+//! let secret_key = SecretKey::generate();
+//! let public_key = PublicKey::try_from(&secret_key)?;
+//!
+//! let signature = sign(&secret_key, b"Some message.")?;
+//! assert!(verify(&signature, &public_key, b"Some message.").is_ok());
+//!
+//! // Prehashed, for messages that arrive in chunks.
+//! let mut state = Ed25519ph::new();
+//! state.update(b"Some ")?;
+//! state.update(b"message.")?;
+//! let signature = state.finalize_sign(&secret_key)?;
+//!
+//! let mut state = Ed25519ph::new();
+//! state.update(b"Some message.")?;
+//! assert!(state.finalize_verify(&signature, &public_key).is_ok());
+//! # }
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [RFC 8032]: https://datatracker.ietf.org/doc/html/rfc8032
+//! [`SecretKey::generate()`]: crate::hazardous::sign::ed25519::SecretKey::generate
+//! [`Ed25519ph`]: crate::hazardous::sign::ed25519::Ed25519ph
+//! [`Ed25519ph::update()`]: crate::hazardous::sign::ed25519::Ed25519ph::update
+//! [`Ed25519ph::reset()`]: crate::hazardous::sign::ed25519::Ed25519ph::reset
+//! [`Ed25519ph::finalize_sign()`]: crate::hazardous::sign::ed25519::Ed25519ph::finalize_sign
+//! [`Ed25519ph::finalize_verify()`]: crate::hazardous::sign::ed25519::Ed25519ph::finalize_verify
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::ecc::x25519::FieldElement;
+use crate::hazardous::hash::sha2::sha512::{Sha512, SHA512_OUTSIZE};
+use fiat_crypto::curve25519_scalar_64::{
+    fiat_25519_scalar_add, fiat_25519_scalar_from_bytes, fiat_25519_scalar_from_montgomery,
+    fiat_25519_scalar_montgomery_domain_field_element, fiat_25519_scalar_mul,
+    fiat_25519_scalar_non_montgomery_domain_field_element, fiat_25519_scalar_to_bytes,
+    fiat_25519_scalar_to_montgomery,
+};
+use zeroize::Zeroize;
+
+/// The size of an Ed25519 secret key.
+pub const SECRET_KEY_SIZE: usize = 32;
+
+/// The size of an Ed25519 public key.
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// The size of an Ed25519 signature.
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// Encoding of the base point `B`.
+const BASEPOINT: [u8; 32] = [
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+];
+
+/// The curve constant `d = -121665/121666`, in little-endian.
+const EDWARDS_D: [u8; 32] = [
+    0xa3, 0x78, 0x59, 0x13, 0xca, 0x4d, 0xeb, 0x75, 0xab, 0xd8, 0x41, 0x41, 0x4d, 0x0a, 0x70, 0x00,
+    0x98, 0xe8, 0x79, 0x77, 0x79, 0x40, 0xc7, 0x8c, 0x73, 0xfe, 0x6f, 0x2b, 0xee, 0x6c, 0x03, 0x52,
+];
+
+/// A square root of `-1` in the field, in little-endian.
+const SQRT_M1: [u8; 32] = [
+    0xb0, 0xa0, 0x0e, 0x4a, 0x27, 0x1b, 0xee, 0xc4, 0x78, 0xe4, 0x2f, 0xad, 0x06, 0x18, 0x43, 0x2f,
+    0xa7, 0xd7, 0xfb, 0x3d, 0x99, 0x00, 0x4d, 0x2b, 0x0b, 0xdf, 0xc1, 0x4f, 0x80, 0x24, 0x83, 0x2b,
+];
+
+/// The order `L` of the base point, in little-endian.
+const ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// `2^248 mod L`, in little-endian.
+const TWO_POW_248: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// `2^496 mod L`, in little-endian.
+const TWO_POW_496: [u8; 32] = [
+    0x44, 0x51, 0x91, 0x7a, 0x51, 0x4b, 0x6c, 0x06, 0x47, 0xf9, 0x3a, 0xfc, 0x1d, 0x90, 0xf4, 0xbf,
+    0xf5, 0x17, 0xd2, 0x73, 0xec, 0xce, 0x3d, 0x9a, 0x30, 0x7c, 0x1b, 0x41, 0x99, 0xb3, 0x31, 0x01,
+];
+
+impl FieldElement {
+    /// Return `-self`.
+    fn neg(&self) -> Self {
+        Self::zero() - *self
+    }
+
+    /// Return `1` if the canonical encoding of `self` is odd, `0` otherwise.
+    fn is_negative(&self) -> u8 {
+        self.as_bytes()[0] & 1
+    }
+
+    /// Return `b` if `choice == 1` and `a` if `choice == 0`, in constant time.
+    fn conditional_select(choice: u8, a: &Self, b: &Self) -> Self {
+        let mut ret = *a;
+        let mut other = *b;
+        Self::conditional_swap(choice, &mut ret, &mut other);
+
+        ret
+    }
+
+    /// Compute `self^((p - 5) / 8) = self^(2^252 - 3)`.
+    ///
+    /// Ref: https://github.com/golang/crypto/blob/0c34fe9e7dc2486962ef9867e3edb3503537209f/ed25519/internal/edwards25519/edwards25519.go#L426
+    fn pow_p58(&self) -> Self {
+        let mut t0 = self.square();
+        let mut t1 = t0.square().square();
+        t1 = *self * t1;
+        t0 = t0 * t1;
+        t0 = t0.square();
+        t0 = t1 * t0;
+        t1 = t0.square();
+        for _ in 1..5 {
+            t1 = t1.square();
+        }
+        t0 = t1 * t0;
+        t1 = t0.square();
+        for _ in 1..10 {
+            t1 = t1.square();
+        }
+        t1 = t1 * t0;
+        let mut t2 = t1.square();
+        for _ in 1..20 {
+            t2 = t2.square();
+        }
+        t1 = t2 * t1;
+        t1 = t1.square();
+        for _ in 1..10 {
+            t1 = t1.square();
+        }
+        t0 = t1 * t0;
+        t1 = t0.square();
+        for _ in 1..50 {
+            t1 = t1.square();
+        }
+        t1 = t1 * t0;
+        t2 = t1.square();
+        for _ in 1..100 {
+            t2 = t2.square();
+        }
+        t1 = t2 * t1;
+        t1 = t1.square();
+        for _ in 1..50 {
+            t1 = t1.square();
+        }
+        t0 = t1 * t0;
+        t0 = t0.square().square();
+
+        t0 * *self
+    }
+}
+
+#[derive(Clone, Copy)]
+/// A point on edwards25519 in extended coordinates `(X : Y : Z : T)`, where
+/// `x = X/Z`, `y = Y/Z` and `x * y = T/Z`.
+///
+/// Ref: https://eprint.iacr.org/2008/522.pdf
+struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+    t: FieldElement,
+}
+
+impl EdwardsPoint {
+    /// The neutral element `(0, 1)`.
+    fn identity() -> Self {
+        Self {
+            x: FieldElement::zero(),
+            y: FieldElement::one(),
+            z: FieldElement::one(),
+            t: FieldElement::zero(),
+        }
+    }
+
+    /// Add `self` and `other`, using the complete formula "add-2008-hwcd-3".
+    /// `d2` must be `2 * d`.
+    fn add(&self, other: &Self, d2: &FieldElement) -> Self {
+        let a = (self.y - self.x) * (other.y - other.x);
+        let b = (self.y + self.x) * (other.y + other.x);
+        let c = self.t * *d2 * other.t;
+        let zz = self.z * other.z;
+        let d = zz + zz;
+        let e = b - a;
+        let f = d - c;
+        let g = d + c;
+        let h = b + a;
+
+        Self {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// Double `self`, using the formula "dbl-2008-hwcd" with `a = -1`.
+    fn double(&self) -> Self {
+        let a = self.x.square();
+        let b = self.y.square();
+        let zz = self.z.square();
+        let c = zz + zz;
+        let h = a.neg() - b;
+        let e = (self.x + self.y).square() + h;
+        let g = b - a;
+        let f = g - c;
+
+        Self {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// Return `-self`.
+    fn neg(&self) -> Self {
+        Self {
+            x: self.x.neg(),
+            y: self.y,
+            z: self.z,
+            t: self.t.neg(),
+        }
+    }
+
+    /// Return `b` if `choice == 1` and `a` if `choice == 0`, in constant time.
+    fn conditional_select(choice: u8, a: &Self, b: &Self) -> Self {
+        Self {
+            x: FieldElement::conditional_select(choice, &a.x, &b.x),
+            y: FieldElement::conditional_select(choice, &a.y, &b.y),
+            z: FieldElement::conditional_select(choice, &a.z, &b.z),
+            t: FieldElement::conditional_select(choice, &a.t, &b.t),
+        }
+    }
+
+    /// Compute `[scalar]self`, where `scalar` is in little-endian. This always performs
+    /// 256 doublings and additions, regardless of the value of `scalar`.
+    fn mul(&self, scalar: &[u8; 32]) -> Self {
+        let d = FieldElement::from_bytes(&EDWARDS_D);
+        let d2 = d + d;
+        let mut acc = Self::identity();
+
+        for idx in (0..256).rev() {
+            let bit = (scalar[idx >> 3] >> (idx & 7)) & 1;
+            acc = acc.double();
+            let sum = acc.add(self, &d2);
+            acc = Self::conditional_select(bit, &acc, &sum);
+        }
+
+        acc
+    }
+
+    /// Return `true` if `self` is the neutral element.
+    fn is_identity(&self) -> bool {
+        self.x == FieldElement::zero() && self.y == self.z
+    }
+
+    /// Encode `self` as specified in RFC 8032, section 5.1.2.
+    fn compress(&self) -> [u8; 32] {
+        let mut z_inv = self.z;
+        z_inv.invert();
+        let x = self.x * z_inv;
+        let y = self.y * z_inv;
+
+        let mut ret = y.as_bytes();
+        ret[31] |= x.is_negative() << 7;
+
+        ret
+    }
+
+    /// Decode a point as specified in RFC 8032, section 5.1.3.
+    ///
+    /// This operates on public data only, and so may branch.
+    fn decompress(bytes: &[u8; 32]) -> Result<Self, UnknownCryptoError> {
+        let sign = bytes[31] >> 7;
+        let y = FieldElement::from_bytes(bytes);
+        // Reject encodings of y that are not less than p.
+        let mut masked = *bytes;
+        masked[31] &= 127;
+        if y.as_bytes() != masked {
+            return Err(UnknownCryptoError);
+        }
+
+        let d = FieldElement::from_bytes(&EDWARDS_D);
+        let yy = y.square();
+        let u = yy - FieldElement::one();
+        let v = d * yy + FieldElement::one();
+        let v3 = v.square() * v;
+        let v7 = v3.square() * v;
+        let mut x = u * v3 * (u * v7).pow_p58();
+
+        let vxx = v * x.square();
+        if vxx == u {
+            // x is a square root.
+        } else if vxx == u.neg() {
+            x = x * FieldElement::from_bytes(&SQRT_M1);
+        } else {
+            return Err(UnknownCryptoError);
+        }
+
+        if x == FieldElement::zero() && sign == 1 {
+            return Err(UnknownCryptoError);
+        }
+        if x.is_negative() != sign {
+            x = x.neg();
+        }
+
+        Ok(Self {
+            x,
+            y,
+            z: FieldElement::one(),
+            t: x * y,
+        })
+    }
+}
+
+/// Convert a little-endian value less than `L` into the Montgomery domain.
+fn scalar_to_montgomery(bytes: &[u8; 32]) -> fiat_25519_scalar_montgomery_domain_field_element {
+    let mut limbs = [0u64; 4];
+    fiat_25519_scalar_from_bytes(&mut limbs, bytes);
+    let mut ret = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_to_montgomery(
+        &mut ret,
+        &fiat_25519_scalar_non_montgomery_domain_field_element(limbs),
+    );
+    limbs.zeroize();
+
+    ret
+}
+
+/// Convert an element in the Montgomery domain into its canonical little-endian encoding.
+fn scalar_from_montgomery(element: &fiat_25519_scalar_montgomery_domain_field_element) -> [u8; 32] {
+    let mut limbs = fiat_25519_scalar_non_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_from_montgomery(&mut limbs, element);
+    let mut ret = [0u8; 32];
+    fiat_25519_scalar_to_bytes(&mut ret, &limbs.0);
+    limbs.0.zeroize();
+
+    ret
+}
+
+/// Reduce a 512-bit little-endian value modulo `L`.
+///
+/// The value is split into 248-bit limbs, which are all less than `L`, and
+/// recombined as `lo + mid * 2^248 + hi * 2^496 (mod L)`.
+fn scalar_reduce(wide: &[u8; SHA512_OUTSIZE]) -> [u8; 32] {
+    let mut lo = [0u8; 32];
+    let mut mid = [0u8; 32];
+    let mut hi = [0u8; 32];
+    lo[..31].copy_from_slice(&wide[..31]);
+    mid[..31].copy_from_slice(&wide[31..62]);
+    hi[..2].copy_from_slice(&wide[62..]);
+
+    let mut mid_shifted = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_mul(
+        &mut mid_shifted,
+        &scalar_to_montgomery(&mid),
+        &scalar_to_montgomery(&TWO_POW_248),
+    );
+    let mut hi_shifted = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_mul(
+        &mut hi_shifted,
+        &scalar_to_montgomery(&hi),
+        &scalar_to_montgomery(&TWO_POW_496),
+    );
+
+    let mut acc = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_add(&mut acc, &scalar_to_montgomery(&lo), &mid_shifted);
+    let mut ret = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_add(&mut ret, &acc, &hi_shifted);
+
+    lo.zeroize();
+    mid.zeroize();
+    hi.zeroize();
+    mid_shifted.0.zeroize();
+    hi_shifted.0.zeroize();
+    acc.0.zeroize();
+    let reduced = scalar_from_montgomery(&ret);
+    ret.0.zeroize();
+
+    reduced
+}
+
+/// Compute `a * b + c (mod L)`, where all inputs are less than `L`.
+fn scalar_mul_add(a: &[u8; 32], b: &[u8; 32], c: &[u8; 32]) -> [u8; 32] {
+    let mut product = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_mul(
+        &mut product,
+        &scalar_to_montgomery(a),
+        &scalar_to_montgomery(b),
+    );
+    let mut ret = fiat_25519_scalar_montgomery_domain_field_element([0u64; 4]);
+    fiat_25519_scalar_add(&mut ret, &product, &scalar_to_montgomery(c));
+
+    product.0.zeroize();
+    let result = scalar_from_montgomery(&ret);
+    ret.0.zeroize();
+
+    result
+}
+
+/// Return `true` if the little-endian `scalar` is less than `L`.
+///
+/// This operates on public data only, and so may branch.
+fn scalar_is_canonical(scalar: &[u8; 32]) -> bool {
+    for (s, l) in scalar.iter().zip(ORDER.iter()).rev() {
+        if s != l {
+            return s < l;
+        }
+    }
+
+    false
+}
+
+/// Compute `SHA-512(parts[0] || parts[1] || ...) mod L`.
+fn hash_to_scalar(parts: &[&[u8]]) -> Result<[u8; 32], UnknownCryptoError> {
+    let mut ctx = Sha512::new();
+    for part in parts {
+        ctx.update(part)?;
+    }
+    let mut digest = [0u8; SHA512_OUTSIZE];
+    ctx._finalize_internal(&mut digest)?;
+    let ret = scalar_reduce(&digest);
+    digest.zeroize();
+
+    Ok(ret)
+}
+
+/// The clamped secret scalar `s` and the `prefix` that are expanded from a [`SecretKey`].
+///
+/// Ref: RFC 8032, section 5.1.5.
+struct ExpandedSecretKey {
+    scalar: [u8; 32],
+    prefix: [u8; 32],
+}
+
+impl Drop for ExpandedSecretKey {
+    fn drop(&mut self) {
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl ExpandedSecretKey {
+    fn from_secret_key(secret_key: &SecretKey) -> Result<Self, UnknownCryptoError> {
+        let mut ctx = Sha512::new();
+        ctx.update(secret_key.unprotected_as_bytes())?;
+        let mut digest = [0u8; SHA512_OUTSIZE];
+        ctx._finalize_internal(&mut digest)?;
+
+        let mut ret = Self {
+            scalar: [0u8; 32],
+            prefix: [0u8; 32],
+        };
+        ret.scalar.copy_from_slice(&digest[..32]);
+        ret.prefix.copy_from_slice(&digest[32..]);
+        digest.zeroize();
+        // Clamp
+        ret.scalar[0] &= 248;
+        ret.scalar[31] &= 127;
+        ret.scalar[31] |= 64;
+
+        Ok(ret)
+    }
+
+    /// The encoding of the public key `[s]B`.
+    fn public_key(&self) -> Result<[u8; 32], UnknownCryptoError> {
+        Ok(EdwardsPoint::decompress(&BASEPOINT)?
+            .mul(&self.scalar)
+            .compress())
+    }
+}
+
+construct_secret_key! {
+    /// A type to represent the `SecretKey` that Ed25519 uses. This is the 32-byte
+    /// seed that the signing scalar and nonce prefix are derived from.
+    ///
+    /// # Errors:
+    /// An error will be returned if:
+    /// - `slice` is not 32 bytes.
+    ///
+    /// # Panics:
+    /// A panic will occur if:
+    /// - Failure to generate random bytes securely.
+    (SecretKey, test_secret_key, SECRET_KEY_SIZE, SECRET_KEY_SIZE, SECRET_KEY_SIZE)
+}
+
+impl_from_trait!(SecretKey, SECRET_KEY_SIZE);
+
+construct_public! {
+    /// A type to represent the `PublicKey` that Ed25519 uses.
+    ///
+    /// This type only holds bytes. The point it encodes is decoded, and
+    /// validated, by [`verify()`].
+    ///
+    /// # Errors:
+    /// An error will be returned if:
+    /// - `slice` is not 32 bytes.
+    (PublicKey, test_public_key, PUBLIC_KEY_SIZE, PUBLIC_KEY_SIZE)
+}
+
+impl_from_trait!(PublicKey, PUBLIC_KEY_SIZE);
+
+impl TryFrom<&SecretKey> for PublicKey {
+    type Error = UnknownCryptoError;
+
+    fn try_from(secret_key: &SecretKey) -> Result<Self, Self::Error> {
+        Ok(PublicKey::from(
+            ExpandedSecretKey::from_secret_key(secret_key)?.public_key()?,
+        ))
+    }
+}
+
+construct_public! {
+    /// A type to represent a `Signature` that Ed25519 produces.
+    ///
+    /// # Errors:
+    /// An error will be returned if:
+    /// - `slice` is not 64 bytes.
+    (Signature, test_signature, SIGNATURE_SIZE, SIGNATURE_SIZE)
+}
+
+impl_from_trait!(Signature, SIGNATURE_SIZE);
+
+/// The `dom2(1, "")` prefix that Ed25519ph prepends to all hash inputs, with an empty context.
+///
+/// Ref: RFC 8032, section 2.
+const ED25519PH_DOM: &[u8] = b"SigEd25519 no Ed25519 collisions\x01\x00";
+
+/// Sign `message` with `secret_key`, prepending `dom` to all hash inputs.
+///
+/// Ref: RFC 8032, section 5.1.6.
+fn sign_with_dom(
+    secret_key: &SecretKey,
+    dom: &[u8],
+    message: &[u8],
+) -> Result<Signature, UnknownCryptoError> {
+    let expanded = ExpandedSecretKey::from_secret_key(secret_key)?;
+    let public_key = expanded.public_key()?;
+    let basepoint = EdwardsPoint::decompress(&BASEPOINT)?;
+
+    let mut r = hash_to_scalar(&[dom, &expanded.prefix, message])?;
+    let big_r = basepoint.mul(&r).compress();
+    let k = hash_to_scalar(&[dom, &big_r, &public_key, message])?;
+
+    let mut wide = [0u8; SHA512_OUTSIZE];
+    wide[..32].copy_from_slice(&expanded.scalar);
+    let mut s = scalar_reduce(&wide);
+    wide.zeroize();
+    let big_s = scalar_mul_add(&k, &s, &r);
+    r.zeroize();
+    s.zeroize();
+
+    let mut signature = [0u8; SIGNATURE_SIZE];
+    signature[..32].copy_from_slice(&big_r);
+    signature[32..].copy_from_slice(&big_s);
+
+    Ok(Signature::from(signature))
+}
+
+/// Verify `signature` of `message` by `public_key`, prepending `dom` to all hash inputs.
+///
+/// Ref: RFC 8032, section 5.1.7.
+fn verify_with_dom(
+    signature: &Signature,
+    public_key: &PublicKey,
+    dom: &[u8],
+    message: &[u8],
+) -> Result<(), UnknownCryptoError> {
+    // NOTE: The lengths of these are guaranteed by the types, so .unwrap() is OK.
+    let big_r: [u8; 32] = signature.as_ref()[..32].try_into().unwrap();
+    let big_s: [u8; 32] = signature.as_ref()[32..].try_into().unwrap();
+    let public_key: [u8; 32] = public_key.as_ref().try_into().unwrap();
+
+    if !scalar_is_canonical(&big_s) {
+        return Err(UnknownCryptoError);
+    }
+    let point_r = EdwardsPoint::decompress(&big_r)?;
+    let point_a = EdwardsPoint::decompress(&public_key)?;
+    let k = hash_to_scalar(&[dom, &big_r, &public_key, message])?;
+
+    // Check [8]([S]B - [k]A - R) is the neutral element.
+    let d = FieldElement::from_bytes(&EDWARDS_D);
+    let d2 = d + d;
+    let sb = EdwardsPoint::decompress(&BASEPOINT)?.mul(&big_s);
+    let ka = point_a.mul(&k);
+    let check = sb
+        .add(&ka.neg(), &d2)
+        .add(&point_r.neg(), &d2)
+        .double()
+        .double()
+        .double();
+
+    if check.is_identity() {
+        Ok(())
+    } else {
+        Err(UnknownCryptoError)
+    }
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Sign `message` with `secret_key`.
+pub fn sign(secret_key: &SecretKey, message: &[u8]) -> Result<Signature, UnknownCryptoError> {
+    sign_with_dom(secret_key, &[], message)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Verify that `signature` is a valid signature of `message` by `public_key`.
+pub fn verify(
+    signature: &Signature,
+    public_key: &PublicKey,
+    message: &[u8],
+) -> Result<(), UnknownCryptoError> {
+    verify_with_dom(signature, public_key, &[], message)
+}
+
+#[derive(Clone, Debug)]
+/// Streaming state for Ed25519ph, the prehashed variant of Ed25519, with an empty context.
+///
+/// The message is hashed incrementally with SHA-512, so it never has to be held in
+/// memory in full. Ed25519ph signatures are not compatible with Ed25519 signatures
+/// of the same message.
+pub struct Ed25519ph {
+    _state: Sha512,
+}
+
+impl Default for Ed25519ph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ed25519ph {
+    /// Initialize an `Ed25519ph` struct.
+    pub fn new() -> Self {
+        Self {
+            _state: Sha512::new(),
+        }
+    }
+
+    /// Reset to `new()` state.
+    pub fn reset(&mut self) {
+        self._state.reset();
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Update state with `data`. This can be called multiple times.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+        self._state.update(data)
+    }
+
+    /// Return `SHA-512(message)`.
+    fn prehash(&mut self) -> Result<[u8; SHA512_OUTSIZE], UnknownCryptoError> {
+        let mut digest = [0u8; SHA512_OUTSIZE];
+        self._state._finalize_internal(&mut digest)?;
+
+        Ok(digest)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Return an Ed25519ph signature by `secret_key` of the message passed to [`Self::update()`].
+    pub fn finalize_sign(
+        &mut self,
+        secret_key: &SecretKey,
+    ) -> Result<Signature, UnknownCryptoError> {
+        sign_with_dom(secret_key, ED25519PH_DOM, &self.prehash()?)
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Verify that `signature` is a valid Ed25519ph signature by `public_key` of the message
+    /// passed to [`Self::update()`].
+    pub fn finalize_verify(
+        &mut self,
+        signature: &Signature,
+        public_key: &PublicKey,
+    ) -> Result<(), UnknownCryptoError> {
+        verify_with_dom(signature, public_key, ED25519PH_DOM, &self.prehash()?)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "safe_api")]
+// Mark safe_api because the test vectors are decoded into a Vec.
+mod public {
+    use super::*;
+
+    /// Test vectors from RFC 8032, section 7.1: (secret key, public key, message, signature).
+    const RFC_8032_VECTORS: [(&str, &str, &str, &str); 5] = [
+        // TEST 1
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        // TEST 2
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        // TEST 3
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+        // TEST 1024
+        (
+            "f5e5767cf153319517630f226876b86c8160cc583bc013744c6bf255f5cc0ee5",
+            "278117fc144c72340f67d0f2316e8386ceffbf2b2428c9c51fef7c597f1d426e",
+            concat!(
+                "08b8b2b733424243760fe426a4b54908632110a66c2f6591eabd3345e3e4eb98fa6e264bf09efe12ee50f8f54e9f77b1",
+                "e355f6c50544e23fb1433ddf73be84d879de7c0046dc4996d9e773f4bc9efe5738829adb26c81b37c93a1b270b20329d",
+                "658675fc6ea534e0810a4432826bf58c941efb65d57a338bbd2e26640f89ffbc1a858efcb8550ee3a5e1998bd177e93a",
+                "7363c344fe6b199ee5d02e82d522c4feba15452f80288a821a579116ec6dad2b3b310da903401aa62100ab5d1a36553e",
+                "06203b33890cc9b832f79ef80560ccb9a39ce767967ed628c6ad573cb116dbefefd75499da96bd68a8a97b928a8bbc10",
+                "3b6621fcde2beca1231d206be6cd9ec7aff6f6c94fcd7204ed3455c68c83f4a41da4af2b74ef5c53f1d8ac70bdcb7ed1",
+                "85ce81bd84359d44254d95629e9855a94a7c1958d1f8ada5d0532ed8a5aa3fb2d17ba70eb6248e594e1a2297acbbb39d",
+                "502f1a8c6eb6f1ce22b3de1a1f40cc24554119a831a9aad6079cad88425de6bde1a9187ebb6092cf67bf2b13fd65f270",
+                "88d78b7e883c8759d2c4f5c65adb7553878ad575f9fad878e80a0c9ba63bcbcc2732e69485bbc9c90bfbd62481d9089b",
+                "eccf80cfe2df16a2cf65bd92dd597b0707e0917af48bbb75fed413d238f5555a7a569d80c3414a8d0859dc65a46128ba",
+                "b27af87a71314f318c782b23ebfe808b82b0ce26401d2e22f04d83d1255dc51addd3b75a2b1ae0784504df543af8969b",
+                "e3ea7082ff7fc9888c144da2af58429ec96031dbcad3dad9af0dcbaaaf268cb8fcffead94f3c7ca495e056a9b47acdb7",
+                "51fb73e666c6c655ade8297297d07ad1ba5e43f1bca32301651339e22904cc8c42f58c30c04aafdb038dda0847dd988d",
+                "cda6f3bfd15c4b4c4525004aa06eeff8ca61783aacec57fb3d1f92b0fe2fd1a85f6724517b65e614ad6808d6f6ee34df",
+                "f7310fdc82aebfd904b01e1dc54b2927094b2db68d6f903b68401adebf5a7e08d78ff4ef5d63653a65040cf9bfd4aca7",
+                "984a74d37145986780fc0b16ac451649de6188a7dbdf191f64b5fc5e2ab47b57f7f7276cd419c17a3ca8e1b939ae49e4",
+                "88acba6b965610b5480109c8b17b80e1b7b750dfc7598d5d5011fd2dcc5600a32ef5b52a1ecc820e308aa342721aac09",
+                "43bf6686b64b2579376504ccc493d97e6aed3fb0f9cd71a43dd497f01f17c0e2cb3797aa2a2f256656168e6c496afc5f",
+                "b93246f6b1116398a346f1a641f3b041e989f7914f90cc2c7fff357876e506b50d334ba77c225bc307ba537152f3f161",
+                "0e4eafe595f6d9d90d11faa933a15ef1369546868a7f3a45a96768d40fd9d03412c091c6315cf4fde7cb68606937380d",
+                "b2eaaa707b4c4185c32eddcdd306705e4dc1ffc872eeee475a64dfac86aba41c0618983f8741c5ef68d3a101e8a3b8ca",
+                "c60c905c15fc910840b94c00a0b9d0",
+            ),
+            "0aab4c900501b3e24d7cdf4663326a3a87df5e4843b2cbdb67cbf6e460fec350aa5371b1508f9f4528ecea23c436d94b5e8fcd4f681e30a6ac00a9704a188a03",
+        ),
+        // TEST SHA(abc)
+        (
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+        ),
+    ];
+
+    fn rfc_vector(idx: usize) -> (SecretKey, PublicKey, Vec<u8>, Signature) {
+        let (sk, pk, msg, sig) = RFC_8032_VECTORS[idx];
+        (
+            SecretKey::from_slice(&hex::decode(sk).unwrap()).unwrap(),
+            PublicKey::from_slice(&hex::decode(pk).unwrap()).unwrap(),
+            hex::decode(msg).unwrap(),
+            Signature::from_slice(&hex::decode(sig).unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    /// Ref: https://datatracker.ietf.org/doc/html/rfc8032#section-7.1
+    fn test_rfc_8032_section_7_1() {
+        for idx in 0..RFC_8032_VECTORS.len() {
+            let (sk, pk, msg, sig) = rfc_vector(idx);
+
+            assert_eq!(PublicKey::try_from(&sk).unwrap(), pk);
+            assert_eq!(sign(&sk, &msg).unwrap(), sig);
+            assert!(verify(&sig, &pk, &msg).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_verify_err_on_modified_input() {
+        let (sk, pk, msg, sig) = rfc_vector(2);
+
+        let mut bad_msg = msg.clone();
+        bad_msg[0] ^= 1;
+        assert!(verify(&sig, &pk, &bad_msg).is_err());
+        assert!(verify(&sig, &pk, &msg[..1]).is_err());
+
+        for idx in 0..SIGNATURE_SIZE {
+            let mut bad_sig = [0u8; SIGNATURE_SIZE];
+            bad_sig.copy_from_slice(sig.as_ref());
+            bad_sig[idx] ^= 1;
+            assert!(verify(&Signature::from(bad_sig), &pk, &msg).is_err());
+        }
+
+        let other_pk = PublicKey::try_from(&SecretKey::from([7u8; SECRET_KEY_SIZE])).unwrap();
+        assert!(verify(&sig, &other_pk, &msg).is_err());
+        assert_ne!(
+            sign(&SecretKey::from([7u8; SECRET_KEY_SIZE]), &msg).unwrap(),
+            sig
+        );
+        assert!(verify(&sign(&sk, &msg).unwrap(), &pk, &msg).is_ok());
+    }
+
+    #[test]
+    /// A signature where `S` has been replaced with `S + L` still satisfies the
+    /// verification equation, but must be rejected as `S` is no longer canonical.
+    fn test_verify_err_on_non_canonical_s() {
+        let (_, pk, msg, sig) = rfc_vector(0);
+
+        let mut malleated = [0u8; SIGNATURE_SIZE];
+        malleated.copy_from_slice(sig.as_ref());
+        let mut carry = 0u16;
+        for (s, l) in malleated[32..].iter_mut().zip(ORDER.iter()) {
+            let sum = u16::from(*s) + u16::from(*l) + carry;
+            *s = sum as u8;
+            carry = sum >> 8;
+        }
+        assert_eq!(carry, 0);
+        assert!(verify(&Signature::from(malleated), &pk, &msg).is_err());
+
+        // S = L
+        malleated[32..].copy_from_slice(&ORDER);
+        assert!(verify(&Signature::from(malleated), &pk, &msg).is_err());
+        // S with the top bits set.
+        malleated[32..].copy_from_slice(&[255u8; 32]);
+        assert!(verify(&Signature::from(malleated), &pk, &msg).is_err());
+    }
+
+    #[test]
+    fn test_verify_err_on_invalid_points() {
+        let (_, pk, msg, sig) = rfc_vector(0);
+
+        // y = 2 is not the y-coordinate of any point on the curve.
+        let mut not_on_curve = [0u8; 32];
+        not_on_curve[0] = 2;
+        // y = p is a non-canonical encoding of y = 0.
+        let mut non_canonical = [255u8; 32];
+        non_canonical[0] = 0xed;
+        non_canonical[31] = 0x7f;
+        // y = 1 gives x = 0, which cannot have its sign bit set.
+        let mut negative_zero = [0u8; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+
+        for invalid in [not_on_curve, non_canonical, negative_zero] {
+            assert!(EdwardsPoint::decompress(&invalid).is_err());
+            assert!(verify(&sig, &PublicKey::from(invalid), &msg).is_err());
+
+            let mut bad_sig = [0u8; SIGNATURE_SIZE];
+            bad_sig.copy_from_slice(sig.as_ref());
+            bad_sig[..32].copy_from_slice(&invalid);
+            assert!(verify(&Signature::from(bad_sig), &pk, &msg).is_err());
+        }
+    }
+
+    #[test]
+    /// Ref: https://datatracker.ietf.org/doc/html/rfc8032#section-7.3
+    fn test_rfc_8032_section_7_3_ed25519ph() {
+        let (sk, pk, _, _) = rfc_vector(4);
+        let expected = Signature::from_slice(&hex::decode("98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406").unwrap()).unwrap();
+
+        let mut state = Ed25519ph::new();
+        state.update(b"abc").unwrap();
+        assert_eq!(state.finalize_sign(&sk).unwrap(), expected);
+
+        let mut state = Ed25519ph::new();
+        state.update(b"a").unwrap();
+        state.update(b"").unwrap();
+        state.update(b"bc").unwrap();
+        assert!(state.finalize_verify(&expected, &pk).is_ok());
+
+        // Ed25519ph and Ed25519 signatures are not interchangeable.
+        assert!(verify(&expected, &pk, b"abc").is_err());
+        let mut state = Ed25519ph::new();
+        state.update(b"abc").unwrap();
+        assert!(state
+            .finalize_verify(&sign(&sk, b"abc").unwrap(), &pk)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ed25519ph_streaming() {
+        let (sk, pk, _, _) = rfc_vector(4);
+        let message: Vec<u8> = (0..=255u8).cycle().take(1024).collect();
+        // Computed with an independent implementation of RFC 8032.
+        let expected = Signature::from_slice(&hex::decode("42588f92f9ef6802e8ededa8b8348803e9ba3cc634bde1863fc56e00a537c0d5c956ecc49dc57d7d5e25fcf14f45db73e2629d68be17a45e7256a6b836114306").unwrap()).unwrap();
+
+        let mut state = Ed25519ph::new();
+        for chunk in message.chunks(100) {
+            state.update(chunk).unwrap();
+        }
+        assert_eq!(state.finalize_sign(&sk).unwrap(), expected);
+        assert!(state.finalize_sign(&sk).is_err());
+        assert!(state.update(b"").is_err());
+        assert!(state.finalize_verify(&expected, &pk).is_err());
+
+        state.reset();
+        state.update(&message).unwrap();
+        assert!(state.finalize_verify(&expected, &pk).is_ok());
+
+        state.reset();
+        state.update(&message[1..]).unwrap();
+        assert!(state.finalize_verify(&expected, &pk).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_generated() {
+        let sk = SecretKey::generate();
+        let pk = PublicKey::try_from(&sk).unwrap();
+        let sig = sign(&sk, b"Some message.").unwrap();
+
+        assert!(verify(&sig, &pk, b"Some message.").is_ok());
+        assert!(verify(&sig, &pk, b"Some other message.").is_err());
+    }
+}
+
+#[cfg(test)]
+mod private {
+    use super::*;
+
+    #[test]
+    fn test_basepoint_roundtrip() {
+        let basepoint = EdwardsPoint::decompress(&BASEPOINT).unwrap();
+        assert_eq!(basepoint.compress(), BASEPOINT);
+        assert_eq!(
+            basepoint.double().compress(),
+            basepoint
+                .mul(&{
+                    let mut two = [0u8; 32];
+                    two[0] = 2;
+                    two
+                })
+                .compress()
+        );
+        assert!(basepoint.mul(&ORDER).is_identity());
+        assert!(!basepoint.is_identity());
+        assert!(EdwardsPoint::identity().is_identity());
+    }
+
+    #[test]
+    fn test_scalar_reduce() {
+        assert_eq!(scalar_reduce(&[0u8; SHA512_OUTSIZE]), [0u8; 32]);
+
+        let mut expected = [0u8; 32];
+        hex::decode_to_slice(
+            "000f9c44e31106a447938568a71b0ed065bef517d273ecce3d9a307c1b419903",
+            &mut expected,
+        )
+        .unwrap();
+        assert_eq!(scalar_reduce(&[255u8; SHA512_OUTSIZE]), expected);
+
+        let mut wide = [0u8; SHA512_OUTSIZE];
+        wide[..32].copy_from_slice(&ORDER);
+        assert_eq!(scalar_reduce(&wide), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_scalar_is_canonical() {
+        let mut below = ORDER;
+        below[0] -= 1;
+        assert!(scalar_is_canonical(&below));
+        assert!(scalar_is_canonical(&[0u8; 32]));
+        assert!(!scalar_is_canonical(&ORDER));
+        assert!(!scalar_is_canonical(&[255u8; 32]));
+    }
+}
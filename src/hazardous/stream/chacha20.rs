@@ -44,6 +44,7 @@
 //! - The length of `dst_out` is less than `plaintext` or `ciphertext`.
 //! - `plaintext` or `ciphertext` is empty.
 //! - The `initial_counter` is high enough to cause a potential overflow.
+//! - The counter would wrap when calling [`next_counter()`].
 //!
 //! Even though `dst_out` is allowed to be of greater length than `plaintext`,
 //! the `ciphertext` produced by `chacha20`/`xchacha20` will always be of the
@@ -95,6 +96,7 @@
 //! [`SecretKey::generate()`]: chacha20::SecretKey::generate()
 //! [`XChaCha20Poly1305`]: super::aead::xchacha20poly1305
 //! [RFC]: https://tools.ietf.org/html/rfc8439
+//! [`next_counter()`]: chacha20::next_counter
 use crate::errors::UnknownCryptoError;
 use crate::util::endianness::load_u32_le;
 use crate::util::u32x4::U32x4;
@@ -105,9 +107,24 @@ pub const CHACHA_KEYSIZE: usize = 32;
 /// The nonce size for IETF ChaCha20.
 pub const IETF_CHACHA_NONCESIZE: usize = 12;
 /// The blocksize which ChaCha20 operates on.
-pub(crate) const CHACHA_BLOCKSIZE: usize = 64;
+pub const CHACHA_BLOCKSIZE: usize = 64;
+/// The amount of keystream blocks that are computed in parallel by `keystream_blocks_wide()`.
+const WIDE_BLOCKS: usize = 4;
+/// The amount of keystream bytes that `keystream_blocks_wide()` produces.
+const WIDE_BLOCKSIZE: usize = WIDE_BLOCKS * CHACHA_BLOCKSIZE;
+/// Whether `apply_keystream()` uses `keystream_blocks_wide()`. The `force-reference`
+/// feature restricts it to the single-block reference path, for builds that need
+/// predictable codegen.
+const USE_WIDE_PATH: bool = !cfg!(feature = "force-reference");
 /// The size of the subkey that HChaCha20 returns.
 const HCHACHA_OUTSIZE: usize = 32;
+
+#[cfg(all(test, feature = "safe_api"))]
+std::thread_local! {
+    /// The amount of times `keystream_blocks_wide()` has run on the current thread.
+    /// Only used to test which path `apply_keystream()` takes.
+    static WIDE_PATH_CALLS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
 /// The nonce size for HChaCha20.
 pub(crate) const HCHACHA_NONCESIZE: usize = 16;
 
@@ -171,6 +188,7 @@ macro_rules! DOUBLE_ROUND {
         $r3 = $r3.shl_1();
     };
 }
+
 pub(crate) struct ChaCha20 {
     state: [U32x4; 4],
     internal_counter: u32,
@@ -243,13 +261,99 @@ impl ChaCha20 {
     ///
     /// If the internal counter would overflow, we return an error.
     pub(crate) fn next_produceable(&self) -> Result<(), UnknownCryptoError> {
-        if self.internal_counter.checked_add(1).is_none() {
+        if self.blocks_produceable() == 0 {
             Err(UnknownCryptoError)
         } else {
             Ok(())
         }
     }
 
+    /// The number of keystream blocks that can still be produced, given the current state.
+    fn blocks_produceable(&self) -> u32 {
+        u32::MAX - self.internal_counter
+    }
+
+    #[cfg(all(test, feature = "safe_api"))]
+    /// The number of keystream blocks produced so far.
+    pub(crate) fn blocks_produced(&self) -> u32 {
+        self.internal_counter
+    }
+
+    /// Check that we can produce `WIDE_BLOCKS` more keystream blocks, starting at
+    /// `block_counter`, given the current state.
+    fn wide_produceable(&self, block_counter: u32) -> bool {
+        self.is_ietf
+            && block_counter.checked_add(WIDE_BLOCKS as u32 - 1).is_some()
+            && self
+                .internal_counter
+                .checked_add(WIDE_BLOCKS as u32)
+                .is_some()
+    }
+
+    /// Process the next `WIDE_BLOCKS` keystream blocks, for the counters `block_counter`,
+    /// `block_counter + 1`, ..., and copy them into `dst`.
+    ///
+    /// The blocks are computed in parallel lanes, so that the compiler can vectorize the
+    /// rounds with whatever SIMD instructions the target has. The output is identical to
+    /// that of calling `keystream_block()` with each counter in turn.
+    fn keystream_blocks_wide(&mut self, block_counter: u32, dst: &mut [u8]) {
+        debug_assert!(self.wide_produceable(block_counter));
+        debug_assert_eq!(dst.len(), WIDE_BLOCKSIZE);
+        #[cfg(all(test, feature = "safe_api"))]
+        WIDE_PATH_CALLS.with(|calls| calls.set(calls.get() + 1));
+
+        // If this panics, max amount of keystream blocks
+        // have been retrieved.
+        self.internal_counter = self
+            .internal_counter
+            .checked_add(WIDE_BLOCKS as u32)
+            .unwrap();
+
+        // Each element holds one state word, with one lane per block.
+        let mut initial = [U32x4(0, 0, 0, 0); 16];
+        for (row_idx, row) in self.state.iter().enumerate() {
+            initial[row_idx * 4] = U32x4(row.0, row.0, row.0, row.0);
+            initial[row_idx * 4 + 1] = U32x4(row.1, row.1, row.1, row.1);
+            initial[row_idx * 4 + 2] = U32x4(row.2, row.2, row.2, row.2);
+            initial[row_idx * 4 + 3] = U32x4(row.3, row.3, row.3, row.3);
+        }
+        initial[12] = U32x4(
+            block_counter,
+            block_counter + 1,
+            block_counter + 2,
+            block_counter + 3,
+        );
+
+        let mut x = initial;
+        for _ in 0..10 {
+            // Column rounds
+            ROUND!(x[0], x[4], x[8], x[12]);
+            ROUND!(x[1], x[5], x[9], x[13]);
+            ROUND!(x[2], x[6], x[10], x[14]);
+            ROUND!(x[3], x[7], x[11], x[15]);
+            // Diagonal rounds
+            ROUND!(x[0], x[5], x[10], x[15]);
+            ROUND!(x[1], x[6], x[11], x[12]);
+            ROUND!(x[2], x[7], x[8], x[13]);
+            ROUND!(x[3], x[4], x[9], x[14]);
+        }
+
+        for (word_idx, (word, init)) in x.iter().zip(initial.iter()).enumerate() {
+            let word = word.wrapping_add(*init);
+            let offset = word_idx * 4;
+            dst[offset..offset + 4].copy_from_slice(&word.0.to_le_bytes());
+            dst[CHACHA_BLOCKSIZE + offset..CHACHA_BLOCKSIZE + offset + 4]
+                .copy_from_slice(&word.1.to_le_bytes());
+            dst[2 * CHACHA_BLOCKSIZE + offset..2 * CHACHA_BLOCKSIZE + offset + 4]
+                .copy_from_slice(&word.2.to_le_bytes());
+            dst[3 * CHACHA_BLOCKSIZE + offset..3 * CHACHA_BLOCKSIZE + offset + 4]
+                .copy_from_slice(&word.3.to_le_bytes());
+        }
+
+        x.iter_mut().zeroize();
+        initial.iter_mut().zeroize();
+    }
+
     /// Process the next keystream and copy into destination array.
     pub(crate) fn keystream_block(&mut self, block_counter: u32, inplace: &mut [u8]) {
         debug_assert!(if self.is_ietf {
@@ -301,6 +405,80 @@ impl ChaCha20 {
     }
 }
 
+/// Produce `len` bytes of keystream, starting at the block `initial_counter`, and pass
+/// each chunk of it to `f` along with its offset into the keystream.
+///
+/// Whenever at least `WIDE_BLOCKSIZE` bytes remain, the blocks are computed with
+/// `keystream_blocks_wide()`, unless the `force-reference` feature is enabled. Remaining
+/// blocks are computed one at a time into `tmp_block`.
+fn apply_keystream<F: FnMut(usize, &[u8])>(
+    ctx: &mut ChaCha20,
+    initial_counter: u32,
+    tmp_block: &mut [u8],
+    len: usize,
+    mut f: F,
+) -> Result<(), UnknownCryptoError> {
+    debug_assert_eq!(tmp_block.len(), CHACHA_BLOCKSIZE);
+    let mut wide_block = Zeroizing::new([0u8; WIDE_BLOCKSIZE]);
+    let mut offset = 0;
+
+    while offset < len {
+        let remaining = len - offset;
+        let counter = u32::try_from(offset / CHACHA_BLOCKSIZE)
+            .ok()
+            .and_then(|blocks| initial_counter.checked_add(blocks))
+            .ok_or(UnknownCryptoError)?;
+
+        if USE_WIDE_PATH && remaining >= WIDE_BLOCKSIZE && ctx.wide_produceable(counter) {
+            ctx.keystream_blocks_wide(counter, wide_block.as_mut());
+            f(offset, wide_block.as_ref());
+            offset += WIDE_BLOCKSIZE;
+        } else {
+            // See https://github.com/orion-rs/orion/issues/308
+            ctx.next_produceable()?;
+            ctx.keystream_block(counter, tmp_block);
+            let block_len = core::cmp::min(remaining, CHACHA_BLOCKSIZE);
+            f(offset, &tmp_block[..block_len]);
+            offset += block_len;
+        }
+    }
+
+    Ok(())
+}
+
+/// XOR keystream into `bytes` in place, and pass each chunk of the result to `f` while it
+/// is still in cache. Every chunk but the last is a multiple of `CHACHA_BLOCKSIZE` bytes.
+/// The first error returned by `f` is returned, after the whole keystream is applied.
+pub(crate) fn xor_keystream_then<F: FnMut(&[u8]) -> Result<(), UnknownCryptoError>>(
+    ctx: &mut ChaCha20,
+    initial_counter: u32,
+    tmp_block: &mut [u8],
+    bytes: &mut [u8],
+    mut f: F,
+) -> Result<(), UnknownCryptoError> {
+    debug_assert_eq!(tmp_block.len(), CHACHA_BLOCKSIZE);
+    if bytes.is_empty() {
+        return Err(UnknownCryptoError);
+    }
+
+    let mut result = Ok(());
+    apply_keystream(
+        ctx,
+        initial_counter,
+        tmp_block,
+        bytes.len(),
+        |offset, keystream| {
+            let chunk = &mut bytes[offset..offset + keystream.len()];
+            xor_slices!(keystream, chunk);
+            if result.is_ok() {
+                result = f(chunk);
+            }
+        },
+    )?;
+
+    result
+}
+
 /// XOR keystream into destination array using a temporary buffer for each keystream block.
 pub(crate) fn xor_keystream(
     ctx: &mut ChaCha20,
@@ -313,17 +491,44 @@ pub(crate) fn xor_keystream(
         return Err(UnknownCryptoError);
     }
 
-    for (ctr, out_block) in bytes.chunks_mut(CHACHA_BLOCKSIZE).enumerate() {
-        match initial_counter.checked_add(ctr as u32) {
-            Some(counter) => {
-                ctx.keystream_block(counter, tmp_block);
-                xor_slices!(tmp_block, out_block);
-            }
-            None => return Err(UnknownCryptoError),
-        }
+    apply_keystream(
+        ctx,
+        initial_counter,
+        tmp_block,
+        bytes.len(),
+        |offset, keystream| {
+            xor_slices!(keystream, bytes[offset..offset + keystream.len()]);
+        },
+    )
+}
+
+/// XOR keystream with `src` into `dst` using a temporary buffer for each keystream block.
+///
+/// `dst` must be at least as long as `src`. If an error is returned, only ciphertext has been
+/// written to `dst`.
+pub(crate) fn xor_keystream_into(
+    ctx: &mut ChaCha20,
+    initial_counter: u32,
+    tmp_block: &mut [u8],
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), UnknownCryptoError> {
+    debug_assert!(dst.len() >= src.len());
+    if src.is_empty() {
+        return Err(UnknownCryptoError);
     }
 
-    Ok(())
+    apply_keystream(
+        ctx,
+        initial_counter,
+        tmp_block,
+        src.len(),
+        |offset, keystream| {
+            let out = &mut dst[offset..offset + keystream.len()];
+            out.copy_from_slice(keystream);
+            xor_slices!(src[offset..offset + keystream.len()], out);
+        },
+    )
 }
 
 /// In-place IETF ChaCha20 encryption as specified in the [RFC 8439](https://tools.ietf.org/html/rfc8439).
@@ -360,27 +565,13 @@ pub fn encrypt(
 
     let mut ctx = ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true)?;
     let mut keystream_block = Zeroizing::new([0u8; CHACHA_BLOCKSIZE]);
-
-    for (ctr, (p_block, c_block)) in plaintext
-        .chunks(CHACHA_BLOCKSIZE)
-        .zip(dst_out.chunks_mut(CHACHA_BLOCKSIZE))
-        .enumerate()
-    {
-        match initial_counter.checked_add(ctr as u32) {
-            Some(counter) => {
-                // See https://github.com/orion-rs/orion/issues/308
-                ctx.next_produceable()?;
-
-                ctx.keystream_block(counter, keystream_block.as_mut());
-                xor_slices!(p_block, keystream_block.as_mut());
-                c_block[..p_block.len()]
-                    .copy_from_slice(&keystream_block.as_ref()[..p_block.len()]);
-            }
-            None => return Err(UnknownCryptoError),
-        }
-    }
-
-    Ok(())
+    xor_keystream_into(
+        &mut ctx,
+        initial_counter,
+        keystream_block.as_mut(),
+        plaintext,
+        dst_out,
+    )
 }
 
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
@@ -395,6 +586,116 @@ pub fn decrypt(
     encrypt(secret_key, nonce, initial_counter, ciphertext, dst_out)
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Return the counter to use as `initial_counter` for the next chunk, after
+/// `processed` bytes have been encrypted/decrypted starting at `counter`.
+///
+/// A partially used keystream block is never used again, so the returned counter
+/// always starts a fresh block.
+///
+/// # Errors:
+/// An error will be returned if:
+/// - The 32-bit block counter would wrap, because processing further data would
+///   then reuse keystream.
+pub fn next_counter(counter: u32, processed: usize) -> Result<u32, UnknownCryptoError> {
+    let blocks =
+        u32::try_from(processed.div_ceil(CHACHA_BLOCKSIZE)).map_err(|_| UnknownCryptoError)?;
+    counter.checked_add(blocks).ok_or(UnknownCryptoError)
+}
+
+/// An iterator over the IETF ChaCha20 keystream, yielding one 64-byte block at a time.
+///
+/// The first block is the one for `initial_counter`. The iterator ends, instead of
+/// wrapping the 32-bit block counter, after the block for counter `2^32-1`. As with
+/// [`encrypt()`], at most `2^32-1` blocks are produced, so if `initial_counter` is 0
+/// the iterator ends after the block for counter `2^32-2`.
+///
+/// # Security:
+/// - The same rules for nonces apply as for [`encrypt()`]. The keystream must
+///   never be used twice.
+/// - The yielded blocks are not wiped by the iterator. Callers should wipe
+///   them once used.
+///
+/// # Example:
+/// ```rust
+/// # #[cfg(feature = "safe_api")] {
+/// use orion::hazardous::stream::chacha20;
+///
+/// let secret_key = chacha20::SecretKey::generate();
+/// let nonce = chacha20::Nonce::from([0u8; 12]);
+///
+/// let mut data = *b"Data to protect";
+/// let keystream = chacha20::KeystreamIter::new(&secret_key, &nonce, 0);
+/// for (byte, key_byte) in data.iter_mut().zip(keystream.flatten()) {
+///     *byte ^= key_byte;
+/// }
+///
+/// let mut expected = [0u8; 15];
+/// chacha20::encrypt(&secret_key, &nonce, 0, b"Data to protect", &mut expected)?;
+/// assert_eq!(data, expected);
+/// # }
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
+pub struct KeystreamIter {
+    ctx: ChaCha20,
+    next_block_counter: Option<u32>,
+}
+
+impl core::fmt::Debug for KeystreamIter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "KeystreamIter {{ ctx: [***OMITTED***], next_block_counter: {:?} }}",
+            self.next_block_counter
+        )
+    }
+}
+
+impl KeystreamIter {
+    /// Initialize a `KeystreamIter` with a given secret key, nonce and initial counter.
+    pub fn new(secret_key: &SecretKey, nonce: &Nonce, initial_counter: u32) -> Self {
+        Self {
+            // .unwrap() should not be able to panic because the key and nonce
+            // have the correct sizes.
+            ctx: ChaCha20::new(secret_key.unprotected_as_bytes(), nonce.as_ref(), true).unwrap(),
+            next_block_counter: Some(initial_counter),
+        }
+    }
+}
+
+impl Iterator for KeystreamIter {
+    type Item = [u8; CHACHA_BLOCKSIZE];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let block_counter = self.next_block_counter?;
+        // See https://github.com/orion-rs/orion/issues/308
+        self.ctx.next_produceable().ok()?;
+
+        let mut block = [0u8; CHACHA_BLOCKSIZE];
+        self.ctx.keystream_block(block_counter, &mut block);
+        self.next_block_counter = block_counter.checked_add(1);
+
+        Some(block)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Bounded both by the block counter and by the check in `next()`.
+        let remaining = self.next_block_counter.map_or(0, |counter| {
+            core::cmp::min(
+                u64::from(u32::MAX - counter) + 1,
+                u64::from(self.ctx.blocks_produceable()),
+            )
+        });
+
+        match usize::try_from(remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for KeystreamIter {}
+
 /// HChaCha20 as specified in the [draft-RFC](https://github.com/bikeshedders/xchacha-rfc/blob/master).
 pub(super) fn hchacha20(
     secret_key: &SecretKey,
@@ -412,6 +713,48 @@ pub(super) fn hchacha20(
 mod public {
     use super::*;
 
+    mod test_next_counter {
+        use super::*;
+
+        #[test]
+        fn test_next_counter_blocks() {
+            assert_eq!(next_counter(0, 0).unwrap(), 0);
+            assert_eq!(next_counter(0, 1).unwrap(), 1);
+            assert_eq!(next_counter(0, CHACHA_BLOCKSIZE).unwrap(), 1);
+            assert_eq!(next_counter(0, CHACHA_BLOCKSIZE + 1).unwrap(), 2);
+            assert_eq!(next_counter(5, 3 * CHACHA_BLOCKSIZE).unwrap(), 8);
+        }
+
+        #[test]
+        fn test_next_counter_overflow_err() {
+            assert_eq!(next_counter(u32::MAX - 1, 1).unwrap(), u32::MAX);
+            assert_eq!(next_counter(u32::MAX, 0).unwrap(), u32::MAX);
+            assert!(next_counter(u32::MAX, 1).is_err());
+            assert!(next_counter(u32::MAX - 1, CHACHA_BLOCKSIZE + 1).is_err());
+            assert!(next_counter(0, usize::MAX).is_err());
+        }
+
+        #[test]
+        fn test_next_counter_chunked_equals_oneshot() {
+            let k = SecretKey::from_slice(&[1u8; 32]).unwrap();
+            let n = Nonce::from_slice(&[0u8; 12]).unwrap();
+            let input = [0u8; 300];
+
+            let mut expected = [0u8; 300];
+            encrypt(&k, &n, 1, &input, &mut expected).unwrap();
+
+            let mut actual = [0u8; 300];
+            let mut counter = 1;
+            for (src, dst) in input.chunks(128).zip(actual.chunks_mut(128)) {
+                encrypt(&k, &n, counter, src, dst).unwrap();
+                counter = next_counter(counter, src.len()).unwrap();
+            }
+
+            assert_eq!(actual, expected);
+            assert_eq!(counter, 6);
+        }
+    }
+
     #[cfg(feature = "safe_api")]
     #[test]
     // See https://github.com/orion-rs/orion/issues/308
@@ -458,6 +801,126 @@ mod public {
         }
     }
 
+    mod test_keystream_iter {
+        use super::*;
+
+        #[test]
+        fn test_xor_matches_encrypt() {
+            let secret_key = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+            let plaintext = [3u8; 10 * CHACHA_BLOCKSIZE + 7];
+
+            for initial_counter in [0, 1, 7] {
+                for len in [1, 63, 64, 65, 256, plaintext.len()] {
+                    let mut expected = [0u8; 10 * CHACHA_BLOCKSIZE + 7];
+                    encrypt(
+                        &secret_key,
+                        &nonce,
+                        initial_counter,
+                        &plaintext[..len],
+                        &mut expected,
+                    )
+                    .unwrap();
+
+                    let mut actual = plaintext;
+                    let keystream = KeystreamIter::new(&secret_key, &nonce, initial_counter);
+                    for (byte, key_byte) in actual[..len].iter_mut().zip(keystream.flatten()) {
+                        *byte ^= key_byte;
+                    }
+
+                    assert_eq!(actual[..len], expected[..len]);
+                }
+            }
+        }
+
+        #[test]
+        fn test_blocks_match_keystream() {
+            let secret_key = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let mut expected = [0u8; 3 * CHACHA_BLOCKSIZE];
+            encrypt(
+                &secret_key,
+                &nonce,
+                5,
+                &[0u8; 3 * CHACHA_BLOCKSIZE],
+                &mut expected,
+            )
+            .unwrap();
+
+            let mut keystream = KeystreamIter::new(&secret_key, &nonce, 5);
+            for expected_block in expected.chunks_exact(CHACHA_BLOCKSIZE) {
+                assert_eq!(keystream.next().unwrap(), expected_block);
+            }
+        }
+
+        #[test]
+        fn test_stops_at_counter_overflow() {
+            let secret_key = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let mut expected = [0u8; CHACHA_BLOCKSIZE];
+            encrypt(
+                &secret_key,
+                &nonce,
+                u32::MAX,
+                &[0u8; CHACHA_BLOCKSIZE],
+                &mut expected,
+            )
+            .unwrap();
+
+            let mut keystream = KeystreamIter::new(&secret_key, &nonce, u32::MAX - 1);
+            assert_eq!(keystream.size_hint(), (2, Some(2)));
+            assert!(keystream.next().is_some());
+            assert_eq!(keystream.size_hint(), (1, Some(1)));
+            assert_eq!(keystream.next().unwrap(), expected);
+            assert_eq!(keystream.size_hint(), (0, Some(0)));
+            assert!(keystream.next().is_none());
+            assert!(keystream.next().is_none());
+        }
+
+        #[test]
+        /// Starting at counter 0, the last block is the one for counter `2^32-2`,
+        /// because `next_produceable()` allows at most `2^32-1` blocks.
+        fn test_size_hint_at_produceable_limit() {
+            let secret_key = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+
+            let keystream = KeystreamIter::new(&secret_key, &nonce, 0);
+            if let Ok(max) = usize::try_from(u32::MAX) {
+                assert_eq!(keystream.size_hint(), (max, Some(max)));
+            }
+
+            // The state after the blocks for counters 0 through 2^32-4 have been produced.
+            let mut keystream = KeystreamIter::new(&secret_key, &nonce, 0);
+            keystream.ctx.internal_counter = u32::MAX - 3;
+            keystream.next_block_counter = Some(u32::MAX - 3);
+
+            assert_eq!(keystream.size_hint(), (3, Some(3)));
+            assert!(keystream.next().is_some());
+            assert_eq!(keystream.size_hint(), (2, Some(2)));
+            assert!(keystream.next().is_some());
+            assert_eq!(keystream.size_hint(), (1, Some(1)));
+            assert!(keystream.next().is_some());
+            assert_eq!(keystream.size_hint(), (0, Some(0)));
+            assert!(keystream.next().is_none());
+            assert_eq!(keystream.next_block_counter, Some(u32::MAX));
+        }
+
+        #[test]
+        #[cfg(feature = "safe_api")]
+        fn test_debug_omits_state() {
+            let secret_key = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+            let keystream = KeystreamIter::new(&secret_key, &nonce, 0);
+
+            assert_eq!(
+                format!("{:?}", keystream),
+                "KeystreamIter { ctx: [***OMITTED***], next_block_counter: Some(0) }"
+            );
+        }
+    }
+
     // hex crate uses Vec<u8>, so we need std.
     mod test_hchacha20 {
         use super::*;
@@ -989,6 +1452,177 @@ mod private {
         }
     }
 
+    mod test_keystream_blocks_wide {
+        use super::*;
+
+        /// Compare `keystream_blocks_wide()` against `WIDE_BLOCKS` calls to `keystream_block()`.
+        fn wide_equals_single(key: &[u8], nonce: &[u8], block_counter: u32) -> bool {
+            let mut wide_ctx = ChaCha20::new(key, nonce, true).unwrap();
+            let mut single_ctx = ChaCha20::new(key, nonce, true).unwrap();
+
+            let mut wide = [0u8; WIDE_BLOCKSIZE];
+            wide_ctx.keystream_blocks_wide(block_counter, &mut wide);
+
+            let mut single = [0u8; WIDE_BLOCKSIZE];
+            for (ctr, block) in single.chunks_exact_mut(CHACHA_BLOCKSIZE).enumerate() {
+                single_ctx.keystream_block(block_counter + ctr as u32, block);
+            }
+
+            wide[..] == single[..] && wide_ctx.internal_counter == single_ctx.internal_counter
+        }
+
+        #[test]
+        fn test_wide_equals_single() {
+            let max_counter = u32::MAX - (WIDE_BLOCKS as u32 - 1);
+            for block_counter in [0, 1, 7, max_counter - 1, max_counter] {
+                assert!(wide_equals_single(
+                    &[0u8; CHACHA_KEYSIZE],
+                    &[0u8; IETF_CHACHA_NONCESIZE],
+                    block_counter
+                ));
+                assert!(wide_equals_single(
+                    &[255u8; CHACHA_KEYSIZE],
+                    &[255u8; IETF_CHACHA_NONCESIZE],
+                    block_counter
+                ));
+            }
+        }
+
+        #[test]
+        fn test_wide_produceable() {
+            let ctx =
+                ChaCha20::new(&[0u8; CHACHA_KEYSIZE], &[0u8; IETF_CHACHA_NONCESIZE], true).unwrap();
+            assert!(ctx.wide_produceable(u32::MAX - 3));
+            assert!(!ctx.wide_produceable(u32::MAX - 2));
+
+            let hchacha =
+                ChaCha20::new(&[0u8; CHACHA_KEYSIZE], &[0u8; HCHACHA_NONCESIZE], false).unwrap();
+            assert!(!hchacha.wide_produceable(0));
+        }
+
+        #[test]
+        /// The wide path is compared directly, so that it is also tested when `encrypt()`
+        /// does not use it because of the `force-reference` feature.
+        fn test_wide_path_equals_single_block_path() {
+            let key = [7u8; CHACHA_KEYSIZE];
+            let nonce = [9u8; IETF_CHACHA_NONCESIZE];
+            let initial_counter = 3;
+
+            let mut wide = [0u8; 4 * WIDE_BLOCKSIZE];
+            let mut wide_ctx = ChaCha20::new(&key, &nonce, true).unwrap();
+            for (ctr, chunk) in (initial_counter..)
+                .step_by(WIDE_BLOCKS)
+                .zip(wide.chunks_exact_mut(WIDE_BLOCKSIZE))
+            {
+                wide_ctx.keystream_blocks_wide(ctr, chunk);
+            }
+
+            let mut single = [0u8; 4 * WIDE_BLOCKSIZE];
+            let mut single_ctx = ChaCha20::new(&key, &nonce, true).unwrap();
+            for (ctr, block) in (initial_counter..).zip(single.chunks_exact_mut(CHACHA_BLOCKSIZE)) {
+                single_ctx.keystream_block(ctr, block);
+            }
+            assert_eq!(wide, single);
+
+            let mut encrypted = [0u8; 4 * WIDE_BLOCKSIZE];
+            encrypt(
+                &SecretKey::from(key),
+                &Nonce::from(nonce),
+                initial_counter,
+                &[0u8; 4 * WIDE_BLOCKSIZE],
+                &mut encrypted,
+            )
+            .unwrap();
+            assert_eq!(encrypted, wide);
+        }
+
+        #[cfg(feature = "safe_api")]
+        /// The amount of times `encrypt()` runs the wide path for a message that spans
+        /// several `WIDE_BLOCKSIZE` chunks.
+        fn wide_path_calls_in_encrypt() -> usize {
+            let sk = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+            let mut dst = [0u8; 4 * WIDE_BLOCKSIZE];
+
+            let before = WIDE_PATH_CALLS.with(|calls| calls.get());
+            encrypt(&sk, &nonce, 0, &[0u8; 4 * WIDE_BLOCKSIZE], &mut dst).unwrap();
+            WIDE_PATH_CALLS.with(|calls| calls.get()) - before
+        }
+
+        #[test]
+        #[cfg(all(feature = "safe_api", feature = "force-reference"))]
+        fn test_force_reference_selects_single_block_path() {
+            assert_eq!(wide_path_calls_in_encrypt(), 0);
+        }
+
+        #[test]
+        #[cfg(all(feature = "safe_api", not(feature = "force-reference")))]
+        fn test_default_selects_wide_path() {
+            assert_eq!(wide_path_calls_in_encrypt(), 4);
+        }
+
+        #[test]
+        /// Lengths around multiples of `WIDE_BLOCKSIZE` mix the wide and single-block paths.
+        fn test_encrypt_equals_single_block_reference() {
+            let sk = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+            let plaintext = [3u8; 3 * WIDE_BLOCKSIZE + 1];
+
+            for len in [
+                1,
+                CHACHA_BLOCKSIZE,
+                WIDE_BLOCKSIZE - 1,
+                WIDE_BLOCKSIZE,
+                WIDE_BLOCKSIZE + 1,
+                WIDE_BLOCKSIZE + CHACHA_BLOCKSIZE + 3,
+                plaintext.len(),
+            ] {
+                let mut actual = [0u8; 3 * WIDE_BLOCKSIZE + 1];
+                encrypt(&sk, &nonce, 5, &plaintext[..len], &mut actual).unwrap();
+
+                let mut expected = [0u8; 3 * WIDE_BLOCKSIZE + 1];
+                expected[..len].copy_from_slice(&plaintext[..len]);
+                let mut ctx =
+                    ChaCha20::new(sk.unprotected_as_bytes(), nonce.as_ref(), true).unwrap();
+                let mut tmp = [0u8; CHACHA_BLOCKSIZE];
+                for (ctr, block) in expected[..len].chunks_mut(CHACHA_BLOCKSIZE).enumerate() {
+                    ctx.keystream_block(5 + ctr as u32, &mut tmp);
+                    xor_slices!(tmp, block);
+                }
+
+                assert_eq!(actual[..], expected[..]);
+            }
+        }
+
+        #[test]
+        /// The wide path must not be used when it would make the block counter wrap.
+        fn test_counter_overflow_near_max() {
+            let sk = SecretKey::from_slice(&[1u8; CHACHA_KEYSIZE]).unwrap();
+            let nonce = Nonce::from_slice(&[2u8; IETF_CHACHA_NONCESIZE]).unwrap();
+            let mut dst = [0u8; WIDE_BLOCKSIZE + CHACHA_BLOCKSIZE];
+
+            assert!(encrypt(&sk, &nonce, u32::MAX - 3, &[0u8; WIDE_BLOCKSIZE], &mut dst).is_ok());
+            assert!(encrypt(&sk, &nonce, u32::MAX - 2, &[0u8; WIDE_BLOCKSIZE], &mut dst).is_err());
+            assert!(encrypt(&sk, &nonce, u32::MAX - 4, &dst.clone(), &mut dst).is_ok());
+            assert!(encrypt(&sk, &nonce, u32::MAX - 3, &dst.clone(), &mut dst).is_err());
+        }
+
+        #[quickcheck]
+        #[cfg(feature = "safe_api")]
+        fn prop_wide_equals_single(key: Vec<u8>, nonce: Vec<u8>, block_counter: u32) -> bool {
+            let mut sk = [0u8; CHACHA_KEYSIZE];
+            let mut n = [0u8; IETF_CHACHA_NONCESIZE];
+            for (dst, src) in sk.iter_mut().zip(key.iter()) {
+                *dst = *src;
+            }
+            for (dst, src) in n.iter_mut().zip(nonce.iter()) {
+                *dst = *src;
+            }
+
+            wide_equals_single(&sk, &n, block_counter.min(u32::MAX - 3))
+        }
+    }
+
     mod test_keystream_block {
         use super::*;
 
@@ -57,6 +57,9 @@
 //!   CSPRNG. [`Nonce::generate()`] can be used for this.
 //! - To securely generate a strong key, use [`SecretKey::generate()`].
 //!
+//! - When encrypting a stream over multiple calls, [`XChaCha20`] keeps track of the
+//!   position in the keystream, so that it is never reused between calls.
+//!
 //! # Recommendation:
 //! - It is recommended to use [`XChaCha20Poly1305`] when possible.
 //!
@@ -85,11 +88,15 @@
 //! [`SecretKey::generate()`]: xchacha20::SecretKey::generate()
 //! [`Nonce::generate()`]: xchacha20::Nonce::generate()
 //! [`XChaCha20Poly1305`]: super::aead::xchacha20poly1305
+//! [`XChaCha20`]: xchacha20::XChaCha20
 pub use crate::hazardous::stream::chacha20::SecretKey;
 use crate::{
     errors::UnknownCryptoError,
-    hazardous::stream::chacha20::{self, Nonce as IETFNonce, IETF_CHACHA_NONCESIZE},
+    hazardous::stream::chacha20::{
+        self, ChaCha20, Nonce as IETFNonce, CHACHA_BLOCKSIZE, IETF_CHACHA_NONCESIZE,
+    },
 };
+use zeroize::Zeroize;
 
 /// The nonce size for XChaCha20.
 pub const XCHACHA_NONCESIZE: usize = 24;
@@ -120,6 +127,117 @@ pub(crate) fn subkey_and_nonce(secret_key: &SecretKey, nonce: &Nonce) -> (Secret
     (subkey, IETFNonce::from(prefixed_nonce))
 }
 
+/// The maximum amount of keystream, in bytes, that can be produced for one key and nonce.
+const KEYSTREAM_MAX: u64 = (u32::MAX as u64) * (CHACHA_BLOCKSIZE as u64);
+
+/// XChaCha20 streaming state, for encrypting or decrypting data that is given over
+/// multiple calls.
+///
+/// The position in the keystream is tracked across calls to [`Self::apply_keystream()`],
+/// so splitting the data into chunks of any size gives the same result as passing it
+/// all at once to [`encrypt()`] with an `initial_counter` of `0`.
+///
+/// # Errors:
+/// An error will be returned if:
+/// - More than `2^32-1 * 64` bytes of keystream in total would be used.
+///
+/// # Example:
+/// ```rust
+/// # #[cfg(feature = "safe_api")] {
+/// use orion::hazardous::stream::xchacha20;
+///
+/// let secret_key = xchacha20::SecretKey::generate();
+/// let nonce = xchacha20::Nonce::generate();
+///
+/// let mut data = *b"Data to protect";
+/// let mut state = xchacha20::XChaCha20::new(&secret_key, &nonce);
+/// state.apply_keystream(&mut data[..4])?;
+/// state.apply_keystream(&mut data[4..])?;
+///
+/// let mut expected = [0u8; 15];
+/// xchacha20::encrypt(&secret_key, &nonce, 0, b"Data to protect", &mut expected)?;
+/// assert_eq!(data, expected);
+/// # }
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
+pub struct XChaCha20 {
+    stream: ChaCha20,
+    keystream_block: [u8; CHACHA_BLOCKSIZE],
+    position: u64,
+}
+
+impl Drop for XChaCha20 {
+    fn drop(&mut self) {
+        self.keystream_block.zeroize();
+    }
+}
+
+impl core::fmt::Debug for XChaCha20 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "XChaCha20 {{ stream: [***OMITTED***], keystream_block: [***OMITTED***], position: {:?} }}",
+            self.position
+        )
+    }
+}
+
+impl XChaCha20 {
+    /// Initialize a `XChaCha20` struct with a given secret key and nonce.
+    pub fn new(secret_key: &SecretKey, nonce: &Nonce) -> Self {
+        let (subkey, ietf_nonce) = subkey_and_nonce(secret_key, nonce);
+
+        Self {
+            // .unwrap() should not be able to panic because the subkey and nonce
+            // have the correct sizes.
+            stream: ChaCha20::new(subkey.unprotected_as_bytes(), ietf_nonce.as_ref(), true)
+                .unwrap(),
+            keystream_block: [0u8; CHACHA_BLOCKSIZE],
+            position: 0,
+        }
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// XOR the next `data.len()` bytes of keystream into `data`. This can be called
+    /// multiple times.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), UnknownCryptoError> {
+        let len = u64::try_from(data.len()).map_err(|_| UnknownCryptoError)?;
+        let end = self.position.checked_add(len).ok_or(UnknownCryptoError)?;
+        if end > KEYSTREAM_MAX {
+            return Err(UnknownCryptoError);
+        }
+
+        // Use what is left of the last keystream block first.
+        let offset = (self.position % CHACHA_BLOCKSIZE as u64) as usize;
+        let mut processed = 0;
+        if offset != 0 {
+            processed = core::cmp::min(CHACHA_BLOCKSIZE - offset, data.len());
+            xor_slices!(self.keystream_block[offset..], data[..processed]);
+        }
+
+        // This is at most `u32::MAX`, because `end` is at most `KEYSTREAM_MAX`.
+        let mut counter = u32::try_from(self.position.div_ceil(CHACHA_BLOCKSIZE as u64)).unwrap();
+        let remaining = &mut data[processed..];
+        let full = remaining.len() - (remaining.len() % CHACHA_BLOCKSIZE);
+        let (blocks, tail) = remaining.split_at_mut(full);
+        if !blocks.is_empty() {
+            chacha20::xor_keystream(&mut self.stream, counter, &mut self.keystream_block, blocks)?;
+            counter += (full / CHACHA_BLOCKSIZE) as u32;
+        }
+        if !tail.is_empty() {
+            // Keep the block, since the rest of it is used by the next call.
+            self.stream.next_produceable()?;
+            self.stream
+                .keystream_block(counter, &mut self.keystream_block);
+            xor_slices!(self.keystream_block, tail);
+        }
+
+        self.position = end;
+
+        Ok(())
+    }
+}
+
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
 /// XChaCha20 encryption as specified in the [draft RFC](https://tools.ietf.org/html/draft-irtf-cfrg-xchacha-03).
 pub fn encrypt(
@@ -173,4 +291,71 @@ mod public {
             true
         }
     }
+
+    mod test_xchacha20_state {
+        use super::*;
+
+        fn encrypt_in_chunks(chunk_sizes: &[usize]) {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::generate();
+            let plaintext = [1u8; 1024];
+
+            let mut expected = [0u8; 1024];
+            encrypt(&secret_key, &nonce, 0, &plaintext, &mut expected).unwrap();
+
+            let mut actual = plaintext;
+            let mut state = XChaCha20::new(&secret_key, &nonce);
+            let mut start = 0;
+            for size in chunk_sizes.iter() {
+                state
+                    .apply_keystream(&mut actual[start..start + size])
+                    .unwrap();
+                start += size;
+            }
+            state.apply_keystream(&mut actual[start..]).unwrap();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn test_sequential_matches_single() {
+            encrypt_in_chunks(&[512]);
+            encrypt_in_chunks(&[64, 64, 128]);
+            encrypt_in_chunks(&[1, 2, 3, 100, 500]);
+            encrypt_in_chunks(&[63, 1, 65, 0, 300]);
+            encrypt_in_chunks(&[1023]);
+        }
+
+        #[test]
+        fn test_decrypt_in_chunks() {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::generate();
+            let plaintext = [2u8; 300];
+
+            let mut data = plaintext;
+            let mut state = XChaCha20::new(&secret_key, &nonce);
+            state.apply_keystream(&mut data).unwrap();
+            assert_ne!(data, plaintext);
+
+            let mut state = XChaCha20::new(&secret_key, &nonce);
+            state.apply_keystream(&mut data[..150]).unwrap();
+            state.apply_keystream(&mut data[150..]).unwrap();
+            assert_eq!(data, plaintext);
+        }
+
+        #[test]
+        fn test_keystream_exhausted() {
+            let secret_key = SecretKey::generate();
+            let nonce = Nonce::generate();
+            let mut state = XChaCha20::new(&secret_key, &nonce);
+            state.position = KEYSTREAM_MAX - 70;
+
+            let mut data = [0u8; 71];
+            assert!(state.apply_keystream(&mut data).is_err());
+            assert_eq!(data, [0u8; 71]);
+            assert!(state.apply_keystream(&mut data[..70]).is_ok());
+            assert!(state.apply_keystream(&mut data[..1]).is_err());
+            assert!(state.apply_keystream(&mut []).is_ok());
+        }
+    }
 }
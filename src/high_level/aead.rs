@@ -31,18 +31,32 @@
 //!
 //! # About:
 //! - Both one-shot functions and a [`streaming`] API are provided.
-//! - The nonce is automatically generated.
+//! - The nonce is automatically generated, unless [`seal_with_nonce`] is used.
 //! - Returns a vector where the first 24 bytes are the nonce and the rest is
 //!   the authenticated ciphertext with the last 16 bytes being the corresponding Poly1305 tag.
 //! - Uses XChaCha20Poly1305 with no additional data.
 //! - When using [`seal`] and [`open`] then the separation of tags, nonces and
 //!   ciphertext are automatically handled.
+//! - [`seal_detached_nonce`] returns the generated nonce separately, and only the
+//!   authenticated ciphertext in the vector. It is opened with [`open_detached_nonce`].
+//! - [`session`] derives the key and nonces of a session from a master secret.
+//! - [`seal_padded`] pads the plaintext before encrypting it, so that only the number
+//!   of `block`-sized buckets it fills is revealed, instead of its exact length. The
+//!   plaintext is prefixed with its length as a little-endian `u64` and followed by
+//!   zeroes, up to the next multiple of `block`. [`open_padded`] removes the padding.
 //!
 //! # Parameters:
 //! - `plaintext`:  The data to be encrypted.
 //! - `secret_key`: The secret key used to encrypt the `plaintext`.
 //! - `ciphertext_with_tag_and_nonce`:  The data to be decrypted with the first
 //!   24 bytes being the nonce and the last 16 bytes being the corresponding Poly1305 tag.
+//! - `nonce`: The nonce used when calling [`seal_with_nonce`], and expected
+//!   when calling [`open_with_nonce`], or the nonce returned by [`seal_detached_nonce`]
+//!   when calling [`open_detached_nonce`].
+//! - `ciphertext_with_tag`: The data to be decrypted with [`open_detached_nonce`], with
+//!   the last 16 bytes being the corresponding Poly1305 tag.
+//! - `block`: The bucket size that the padded plaintext is a multiple of, when calling
+//!   [`seal_padded`].
 //!
 //! # Errors:
 //! An error will be returned if:
@@ -50,8 +64,14 @@
 //! - The `plaintext` is empty.
 //! - `ciphertext_with_tag_and_nonce` is less than 41 bytes
 //!   ([`XCHACHA_NONCESIZE`] + [`POLY1305_OUTSIZE`] + 1).
-//! - The received tag does not match the calculated tag when calling [`open`].
+//! - `ciphertext_with_tag` is less than 17 bytes ([`POLY1305_OUTSIZE`] + 1).
+//! - The received tag does not match the calculated tag when calling [`open`], [`open_with_nonce`]
+//!   or [`open_detached_nonce`].
+//! - The nonce in `ciphertext_with_tag_and_nonce` is not `nonce` when calling [`open_with_nonce`].
 //! - `plaintext.len()` + [`XCHACHA_NONCESIZE`] + [`POLY1305_OUTSIZE`] overflows when calling [`seal`].
+//! - `plaintext.len()` + [`POLY1305_OUTSIZE`] overflows when calling [`seal_detached_nonce`].
+//! - `block` is 0 when calling [`seal_padded`].
+//! - The padding is not valid after decrypting when calling [`open_padded`].
 //!
 //! # Panics:
 //! A panic will occur if:
@@ -63,7 +83,12 @@
 //!   key. Should this happen, the security of all data that has been encrypted with
 //!   that given key is compromised.
 //! - To securely generate a strong key, use [`SecretKey::default()`].
-//! - The length of the `plaintext` is not hidden, only its contents.
+//! - The length of the `plaintext` is not hidden, only its contents. [`seal_padded`]
+//!   can be used to only reveal the length rounded up to a multiple of `block`.
+//! - When using [`seal_with_nonce`], the caller is responsible for never using the same
+//!   nonce twice with the same key. Reusing a nonce does not produce an error, but reveals
+//!   the XOR of the plaintexts and allows forging messages. A deterministic nonce, such as
+//!   one derived from a message sequence number, must never repeat for a given key.
 //!
 //! # Example:
 //! ```rust
@@ -76,47 +101,113 @@
 //! assert_eq!(decrypted_data, b"Secret message");
 //! # Ok::<(), orion::errors::UnknownCryptoError>(())
 //! ```
+//!
+//! Using a deterministic nonce:
+//! ```rust
+//! use orion::aead;
+//!
+//! let secret_key = aead::SecretKey::default();
+//! let sequence_number: u64 = 1;
+//! let mut nonce = [0u8; 24];
+//! nonce[..8].copy_from_slice(&sequence_number.to_le_bytes());
+//! let nonce = aead::Nonce::from(nonce);
+//!
+//! let ciphertext = aead::seal_with_nonce(&secret_key, &nonce, b"Secret message")?;
+//! let decrypted_data = aead::open_with_nonce(&secret_key, &nonce, &ciphertext)?;
+//!
+//! assert_eq!(decrypted_data, b"Secret message");
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//!
+//! Storing the nonce separately:
+//! ```rust
+//! use orion::aead;
+//!
+//! let secret_key = aead::SecretKey::default();
+//! let (nonce, ciphertext) = aead::seal_detached_nonce(&secret_key, b"Secret message")?;
+//! let decrypted_data = aead::open_detached_nonce(&secret_key, &nonce, &ciphertext)?;
+//!
+//! assert_eq!(decrypted_data, b"Secret message");
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//!
+//! Hiding the length of the plaintext:
+//! ```rust
+//! use orion::aead;
+//!
+//! let secret_key = aead::SecretKey::default();
+//! let short = aead::seal_padded(&secret_key, b"Yes", 256)?;
+//! let long = aead::seal_padded(&secret_key, b"No, not this time", 256)?;
+//! assert_eq!(short.len(), long.len());
+//!
+//! let decrypted_data = aead::open_padded(&secret_key, &short)?;
+//! assert_eq!(decrypted_data, b"Yes");
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
 
 #![cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
 
 pub use super::hltypes::SecretKey;
+pub use crate::hazardous::stream::xchacha20::Nonce;
 use crate::{
     errors::UnknownCryptoError,
     hazardous::{
         aead,
         mac::poly1305::POLY1305_OUTSIZE,
-        stream::{
-            chacha20,
-            xchacha20::{Nonce, XCHACHA_NONCESIZE},
-        },
+        stream::{chacha20, xchacha20::XCHACHA_NONCESIZE},
     },
 };
+use zeroize::Zeroizing;
+
+/// The size of the length prefix added by [`seal_padded`].
+const PADDING_LEN_PREFIX: usize = size_of::<u64>();
 
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
 /// Authenticated encryption using XChaCha20Poly1305.
 pub fn seal(secret_key: &SecretKey, plaintext: &[u8]) -> Result<Vec<u8>, UnknownCryptoError> {
+    seal_with_nonce(secret_key, &Nonce::generate(), plaintext)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated encryption using XChaCha20Poly1305 with a caller-supplied `nonce`.
+///
+/// __WARNING:__ The `nonce` must never be reused with the same key. See the security section.
+pub fn seal_with_nonce(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, UnknownCryptoError> {
+    let mut dst_out = seal_after_prefix(secret_key, nonce, plaintext, XCHACHA_NONCESIZE)?;
+    dst_out[..XCHACHA_NONCESIZE].copy_from_slice(nonce.as_ref());
+
+    Ok(dst_out)
+}
+
+/// Encrypt and authenticate `plaintext` into a new buffer, after `prefix_len`
+/// zero bytes left for the caller to fill in.
+fn seal_after_prefix(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    prefix_len: usize,
+) -> Result<Vec<u8>, UnknownCryptoError> {
     if plaintext.is_empty() {
         return Err(UnknownCryptoError);
     }
 
-    let out_len = match plaintext
-        .len()
-        .checked_add(XCHACHA_NONCESIZE + POLY1305_OUTSIZE)
-    {
+    let out_len = match plaintext.len().checked_add(prefix_len + POLY1305_OUTSIZE) {
         Some(min_out_len) => min_out_len,
         None => return Err(UnknownCryptoError),
     };
 
     let mut dst_out = vec![0u8; out_len];
-    let nonce = Nonce::generate();
-    dst_out[..XCHACHA_NONCESIZE].copy_from_slice(nonce.as_ref());
 
     aead::xchacha20poly1305::seal(
         &chacha20::SecretKey::from_slice(secret_key.unprotected_as_bytes())?,
-        &nonce,
+        nonce,
         plaintext,
         None,
-        &mut dst_out[XCHACHA_NONCESIZE..],
+        &mut dst_out[prefix_len..],
     )?;
 
     Ok(dst_out)
@@ -133,13 +224,63 @@ pub fn open(
         return Err(UnknownCryptoError);
     }
 
-    let mut dst_out =
-        vec![0u8; ciphertext_with_tag_and_nonce.len() - (XCHACHA_NONCESIZE + POLY1305_OUTSIZE)];
+    open_detached_nonce(
+        secret_key,
+        &Nonce::from_slice(&ciphertext_with_tag_and_nonce[..XCHACHA_NONCESIZE])?,
+        &ciphertext_with_tag_and_nonce[XCHACHA_NONCESIZE..],
+    )
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated decryption using XChaCha20Poly1305, requiring the nonce in
+/// `ciphertext_with_tag_and_nonce` to be `nonce`.
+pub fn open_with_nonce(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    ciphertext_with_tag_and_nonce: &[u8],
+) -> Result<Vec<u8>, UnknownCryptoError> {
+    if ciphertext_with_tag_and_nonce.len() <= (XCHACHA_NONCESIZE + POLY1305_OUTSIZE) {
+        return Err(UnknownCryptoError);
+    }
+    if Nonce::from_slice(&ciphertext_with_tag_and_nonce[..XCHACHA_NONCESIZE])? != *nonce {
+        return Err(UnknownCryptoError);
+    }
+
+    open(secret_key, ciphertext_with_tag_and_nonce)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated encryption using XChaCha20Poly1305, returning the automatically
+/// generated nonce separately from the authenticated ciphertext.
+pub fn seal_detached_nonce(
+    secret_key: &SecretKey,
+    plaintext: &[u8],
+) -> Result<(Nonce, Vec<u8>), UnknownCryptoError> {
+    let nonce = Nonce::generate();
+    let dst_out = seal_after_prefix(secret_key, &nonce, plaintext, 0)?;
+
+    Ok((nonce, dst_out))
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated decryption using XChaCha20Poly1305, of a ciphertext produced by
+/// [`seal_detached_nonce`] with `nonce`.
+pub fn open_detached_nonce(
+    secret_key: &SecretKey,
+    nonce: &Nonce,
+    ciphertext_with_tag: &[u8],
+) -> Result<Vec<u8>, UnknownCryptoError> {
+    // Avoid empty ciphertexts
+    if ciphertext_with_tag.len() <= POLY1305_OUTSIZE {
+        return Err(UnknownCryptoError);
+    }
+
+    let mut dst_out = vec![0u8; ciphertext_with_tag.len() - POLY1305_OUTSIZE];
 
     aead::xchacha20poly1305::open(
         &chacha20::SecretKey::from_slice(secret_key.unprotected_as_bytes())?,
-        &Nonce::from_slice(&ciphertext_with_tag_and_nonce[..XCHACHA_NONCESIZE])?,
-        &ciphertext_with_tag_and_nonce[XCHACHA_NONCESIZE..],
+        nonce,
+        ciphertext_with_tag,
         None,
         &mut dst_out,
     )?;
@@ -147,6 +288,63 @@ pub fn open(
     Ok(dst_out)
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated encryption using XChaCha20Poly1305, of `plaintext` padded to
+/// the next multiple of `block` bytes. `plaintext` may be empty.
+pub fn seal_padded(
+    secret_key: &SecretKey,
+    plaintext: &[u8],
+    block: usize,
+) -> Result<Vec<u8>, UnknownCryptoError> {
+    if block == 0 {
+        return Err(UnknownCryptoError);
+    }
+
+    let unpadded_len = plaintext
+        .len()
+        .checked_add(PADDING_LEN_PREFIX)
+        .ok_or(UnknownCryptoError)?;
+    let padded_len = unpadded_len
+        .div_ceil(block)
+        .checked_mul(block)
+        .filter(|len| *len <= isize::MAX as usize)
+        .ok_or(UnknownCryptoError)?;
+
+    let mut padded = Zeroizing::new(vec![0u8; padded_len]);
+    padded[..PADDING_LEN_PREFIX].copy_from_slice(&(plaintext.len() as u64).to_le_bytes());
+    padded[PADDING_LEN_PREFIX..unpadded_len].copy_from_slice(plaintext);
+
+    seal(secret_key, &padded)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Authenticated decryption using XChaCha20Poly1305, of a ciphertext produced by
+/// [`seal_padded`], removing the padding.
+pub fn open_padded(
+    secret_key: &SecretKey,
+    ciphertext_with_tag_and_nonce: &[u8],
+) -> Result<Vec<u8>, UnknownCryptoError> {
+    let padded = Zeroizing::new(open(secret_key, ciphertext_with_tag_and_nonce)?);
+    if padded.len() < PADDING_LEN_PREFIX {
+        return Err(UnknownCryptoError);
+    }
+
+    let mut len_prefix = [0u8; PADDING_LEN_PREFIX];
+    len_prefix.copy_from_slice(&padded[..PADDING_LEN_PREFIX]);
+    let plaintext_len =
+        usize::try_from(u64::from_le_bytes(len_prefix)).map_err(|_| UnknownCryptoError)?;
+    if plaintext_len > padded.len() - PADDING_LEN_PREFIX {
+        return Err(UnknownCryptoError);
+    }
+
+    let (plaintext, padding) = padded[PADDING_LEN_PREFIX..].split_at(plaintext_len);
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(UnknownCryptoError);
+    }
+
+    Ok(plaintext.to_vec())
+}
+
 pub mod streaming {
     //! Streaming AEAD based on XChaCha20Poly1305.
     //!
@@ -316,11 +514,381 @@ pub mod streaming {
     }
 }
 
+pub mod session {
+    //! Deriving the keys of an AEAD session from a master secret.
+    //!
+    //! # Use case:
+    //! `orion::aead::session` can be used when both parties share a master secret, and need
+    //! a key and a sequence of nonces for the messages of a session, like the record layer
+    //! of TLS.
+    //!
+    //! # About:
+    //! - [`derive()`] uses HKDF-SHA512 with an empty salt, the master secret as input
+    //!   keying material and `label` as info, to derive a 32-byte key followed by a 24-byte
+    //!   base nonce.
+    //! - [`SessionKeys::nonce()`] returns the base nonce with a sequence number XORed into
+    //!   its first 8 bytes, in little-endian.
+    //!
+    //! # Parameters:
+    //! - `master`: The master secret shared by both parties.
+    //! - `label`: Separates the keys derived from the same `master`, for example the
+    //!   direction or purpose of the session.
+    //! - `sequence_number`: The number of the message within the session.
+    //!
+    //! # Errors:
+    //! An error will be returned if:
+    //! - `master` is empty.
+    //!
+    //! # Security:
+    //! - `master` must be a uniformly random secret, such as one generated with
+    //!   [`SecretKey::default()`] or derived from a key exchange. It is not suitable for
+    //!   passwords.
+    //! - Each `label` must only be used for a single session with a given `master`.
+    //!   Deriving twice with the same inputs gives the same keys.
+    //! - A `sequence_number` must never be used twice with the same [`SessionKeys`].
+    //!
+    //! # Example:
+    //! ```rust
+    //! use orion::aead;
+    //!
+    //! let master = aead::SecretKey::default();
+    //! let keys = aead::session::derive(&master, b"client to server")?;
+    //!
+    //! let nonce = keys.nonce(0);
+    //! let ciphertext = aead::seal_with_nonce(keys.key(), &nonce, b"Secret message")?;
+    //! let decrypted_data = aead::open_with_nonce(keys.key(), &nonce, &ciphertext)?;
+    //!
+    //! assert_eq!(decrypted_data, b"Secret message");
+    //! # Ok::<(), orion::errors::UnknownCryptoError>(())
+    //! ```
+    //! [`derive()`]: session::derive
+    //! [`SessionKeys::nonce()`]: session::SessionKeys::nonce
+    //! [`SessionKeys`]: session::SessionKeys
+    //! [`SecretKey::default()`]: super::SecretKey::default
+
+    use super::*;
+    use crate::hazardous::kdf::hkdf;
+    use zeroize::Zeroizing;
+
+    /// The size of the key derived by [`derive()`].
+    const KEY_SIZE: usize = 32;
+
+    construct_secret_key! {
+        /// A type to represent the base nonce of a session, from which the nonce of each
+        /// message is computed.
+        ///
+        /// # Errors:
+        /// An error will be returned if:
+        /// - `slice` is not 24 bytes.
+        (BaseNonce, test_base_nonce, XCHACHA_NONCESIZE, XCHACHA_NONCESIZE)
+    }
+
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Debug, PartialEq)]
+    /// The key and base nonce of a session.
+    pub struct SessionKeys {
+        key: SecretKey,
+        base_nonce: BaseNonce,
+    }
+
+    impl SessionKeys {
+        /// Get the key used to encrypt the messages of the session.
+        pub fn key(&self) -> &SecretKey {
+            &self.key
+        }
+
+        /// Get the base nonce of the session.
+        pub fn base_nonce(&self) -> &BaseNonce {
+            &self.base_nonce
+        }
+
+        /// Get the nonce for message number `sequence_number`.
+        pub fn nonce(&self, sequence_number: u64) -> Nonce {
+            let mut nonce = [0u8; XCHACHA_NONCESIZE];
+            nonce.copy_from_slice(self.base_nonce.unprotected_as_bytes());
+            for (n, s) in nonce.iter_mut().zip(sequence_number.to_le_bytes().iter()) {
+                *n ^= s;
+            }
+
+            Nonce::from(nonce)
+        }
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    /// Derive the key and base nonce of a session from `master` and `label`.
+    pub fn derive(master: &SecretKey, label: &[u8]) -> Result<SessionKeys, UnknownCryptoError> {
+        let mut okm = Zeroizing::new([0u8; KEY_SIZE + XCHACHA_NONCESIZE]);
+        hkdf::sha512::derive_key(&[], master.unprotected_as_bytes(), Some(label), &mut *okm)?;
+
+        Ok(SessionKeys {
+            key: SecretKey::from_slice(&okm[..KEY_SIZE])?,
+            base_nonce: BaseNonce::from_slice(&okm[KEY_SIZE..])?,
+        })
+    }
+}
+
 // Testing public functions in the module.
 #[cfg(test)]
 mod public {
     use super::*;
 
+    mod test_seal_open_with_nonce {
+        use super::*;
+
+        #[test]
+        fn test_roundtrip_and_format() {
+            let key = SecretKey::default();
+            let nonce = Nonce::from([7u8; XCHACHA_NONCESIZE]);
+            let plaintext = "Secret message".as_bytes();
+
+            let dst_ciphertext = seal_with_nonce(&key, &nonce, plaintext).unwrap();
+            assert_eq!(dst_ciphertext.len(), plaintext.len() + (24 + 16));
+            assert_eq!(&dst_ciphertext[..XCHACHA_NONCESIZE], nonce.as_ref());
+            // Deterministic given the same key and nonce.
+            assert_eq!(
+                dst_ciphertext,
+                seal_with_nonce(&key, &nonce, plaintext).unwrap()
+            );
+
+            assert_eq!(
+                open_with_nonce(&key, &nonce, &dst_ciphertext).unwrap(),
+                plaintext
+            );
+            assert_eq!(open(&key, &dst_ciphertext).unwrap(), plaintext);
+        }
+
+        #[test]
+        fn test_open_with_diff_nonce_err() {
+            let key = SecretKey::default();
+            let nonce = Nonce::from([7u8; XCHACHA_NONCESIZE]);
+            let dst_ciphertext = seal_with_nonce(&key, &nonce, b"Secret message").unwrap();
+
+            let other_nonce = Nonce::from([8u8; XCHACHA_NONCESIZE]);
+            assert!(open_with_nonce(&key, &other_nonce, &dst_ciphertext).is_err());
+            // A randomly generated nonce from seal() is rejected as well.
+            let dst_ciphertext = seal(&key, b"Secret message").unwrap();
+            assert!(open_with_nonce(&key, &nonce, &dst_ciphertext).is_err());
+        }
+
+        #[test]
+        fn test_errors() {
+            let key = SecretKey::default();
+            let nonce = Nonce::from([7u8; XCHACHA_NONCESIZE]);
+
+            assert!(seal_with_nonce(&key, &nonce, &[]).is_err());
+            assert!(
+                open_with_nonce(&key, &nonce, &[0u8; XCHACHA_NONCESIZE + POLY1305_OUTSIZE])
+                    .is_err()
+            );
+            let key = SecretKey::generate(31).unwrap();
+            assert!(seal_with_nonce(&key, &nonce, b"Secret message").is_err());
+        }
+
+        #[test]
+        /// Nonce reuse is not detected and is the responsibility of the caller.
+        fn test_nonce_reuse_is_callers_responsibility() {
+            let key = SecretKey::default();
+            let nonce = Nonce::from([7u8; XCHACHA_NONCESIZE]);
+
+            let first = seal_with_nonce(&key, &nonce, b"first message").unwrap();
+            let second = seal_with_nonce(&key, &nonce, b"other message").unwrap();
+            assert_ne!(first, second);
+            assert_eq!(first[..XCHACHA_NONCESIZE], second[..XCHACHA_NONCESIZE]);
+
+            assert_eq!(
+                open_with_nonce(&key, &nonce, &first).unwrap(),
+                b"first message"
+            );
+            assert_eq!(
+                open_with_nonce(&key, &nonce, &second).unwrap(),
+                b"other message"
+            );
+        }
+    }
+
+    mod test_session {
+        use super::*;
+        use crate::hazardous::kdf::hkdf;
+
+        #[test]
+        fn test_derive_same_as_hkdf() {
+            let master = SecretKey::from_slice(&[1u8; 32]).unwrap();
+            let keys = session::derive(&master, b"label").unwrap();
+
+            let mut okm = [0u8; 56];
+            hkdf::sha512::derive_key(&[], &[1u8; 32], Some(b"label"), &mut okm).unwrap();
+            assert_eq!(*keys.key(), &okm[..32]);
+            assert_eq!(*keys.base_nonce(), &okm[32..]);
+            assert_eq!(keys, session::derive(&master, b"label").unwrap());
+        }
+
+        #[test]
+        fn test_diff_label_diff_keys() {
+            let master = SecretKey::default();
+            let keys = session::derive(&master, b"client").unwrap();
+            let other = session::derive(&master, b"server").unwrap();
+            assert_ne!(keys.key(), other.key());
+            assert_ne!(keys.base_nonce(), other.base_nonce());
+            assert_ne!(keys, session::derive(&master, b"").unwrap());
+        }
+
+        #[test]
+        fn test_nonce_from_sequence_number() {
+            let keys = session::derive(&SecretKey::default(), b"label").unwrap();
+            let base = keys.base_nonce().unprotected_as_bytes();
+            assert_eq!(keys.nonce(0).as_ref(), base);
+            assert_ne!(keys.nonce(0), keys.nonce(1));
+
+            let nonce = keys.nonce(u64::MAX);
+            for (idx, (n, b)) in nonce.as_ref().iter().zip(base.iter()).enumerate() {
+                if idx < 8 {
+                    assert_eq!(*n, !*b);
+                } else {
+                    assert_eq!(n, b);
+                }
+            }
+        }
+
+        #[test]
+        fn test_roundtrip() {
+            let keys = session::derive(&SecretKey::default(), b"label").unwrap();
+            let ciphertext = seal_with_nonce(keys.key(), &keys.nonce(3), b"Secret").unwrap();
+            assert_eq!(
+                open_with_nonce(keys.key(), &keys.nonce(3), &ciphertext).unwrap(),
+                b"Secret"
+            );
+            assert!(open_with_nonce(keys.key(), &keys.nonce(4), &ciphertext).is_err());
+        }
+    }
+
+    mod test_seal_open_detached_nonce {
+        use super::*;
+
+        #[test]
+        fn test_roundtrip_and_format() {
+            let key = SecretKey::default();
+            let plaintext = "Secret message".as_bytes();
+
+            let (nonce, dst_ciphertext) = seal_detached_nonce(&key, plaintext).unwrap();
+            assert_eq!(dst_ciphertext.len(), plaintext.len() + POLY1305_OUTSIZE);
+            assert_eq!(
+                open_detached_nonce(&key, &nonce, &dst_ciphertext).unwrap(),
+                plaintext
+            );
+
+            // Identical to the packed format, without the nonce prefix.
+            let packed = seal_with_nonce(&key, &nonce, plaintext).unwrap();
+            assert_eq!(&packed[..XCHACHA_NONCESIZE], nonce.as_ref());
+            assert_eq!(packed[XCHACHA_NONCESIZE..], dst_ciphertext[..]);
+
+            // A new nonce is generated for each call.
+            let (other_nonce, _) = seal_detached_nonce(&key, plaintext).unwrap();
+            assert_ne!(nonce, other_nonce);
+        }
+
+        #[test]
+        fn test_open_with_diff_nonce_err() {
+            let key = SecretKey::default();
+            let (nonce, dst_ciphertext) = seal_detached_nonce(&key, b"Secret message").unwrap();
+
+            let mut other_nonce = [0u8; XCHACHA_NONCESIZE];
+            other_nonce.copy_from_slice(nonce.as_ref());
+            other_nonce[0] ^= 1;
+            let other_nonce = Nonce::from(other_nonce);
+            assert!(open_detached_nonce(&key, &other_nonce, &dst_ciphertext).is_err());
+            assert!(open_detached_nonce(&SecretKey::default(), &nonce, &dst_ciphertext).is_err());
+        }
+
+        #[test]
+        fn test_errors() {
+            let key = SecretKey::default();
+            let nonce = Nonce::from([7u8; XCHACHA_NONCESIZE]);
+
+            assert!(seal_detached_nonce(&key, &[]).is_err());
+            assert!(open_detached_nonce(&key, &nonce, &[0u8; POLY1305_OUTSIZE]).is_err());
+            assert!(open_detached_nonce(&key, &nonce, &[]).is_err());
+            let key = SecretKey::generate(31).unwrap();
+            assert!(seal_detached_nonce(&key, b"Secret message").is_err());
+        }
+    }
+
+    mod test_seal_open_padded {
+        use super::*;
+
+        #[test]
+        fn test_roundtrip_bucket_sizes() {
+            let key = SecretKey::default();
+            let input = [7u8; 600];
+
+            for block in [1, 16, 64, 100, 256, 512] {
+                for plaintext_len in [0, 1, 7, 8, 55, 56, 57, 255, 256, 600] {
+                    let plaintext = &input[..plaintext_len];
+                    let ciphertext = seal_padded(&key, plaintext, block).unwrap();
+
+                    let padded_len = ciphertext.len() - (XCHACHA_NONCESIZE + POLY1305_OUTSIZE);
+                    assert_eq!(padded_len % block, 0);
+                    assert!(padded_len >= plaintext_len + PADDING_LEN_PREFIX);
+                    assert!(padded_len < plaintext_len + PADDING_LEN_PREFIX + block);
+
+                    assert_eq!(open_padded(&key, &ciphertext).unwrap(), plaintext);
+                }
+            }
+        }
+
+        #[test]
+        fn test_same_bucket_same_length() {
+            let key = SecretKey::default();
+            let short = seal_padded(&key, b"Yes", 64).unwrap();
+            let long = seal_padded(&key, &[1u8; 56], 64).unwrap();
+            let longer = seal_padded(&key, &[1u8; 57], 64).unwrap();
+
+            assert_eq!(short.len(), long.len());
+            assert_ne!(long.len(), longer.len());
+        }
+
+        #[test]
+        fn test_bad_padding_err() {
+            let key = SecretKey::default();
+            let mut padded = [0u8; 64];
+            padded[..PADDING_LEN_PREFIX].copy_from_slice(&3u64.to_le_bytes());
+            padded[PADDING_LEN_PREFIX..PADDING_LEN_PREFIX + 3].copy_from_slice(b"Yes");
+            assert_eq!(
+                open_padded(&key, &seal(&key, &padded).unwrap()).unwrap(),
+                b"Yes"
+            );
+
+            // Padding that is not all zeroes.
+            let mut bad = padded;
+            bad[63] = 1;
+            assert!(open_padded(&key, &seal(&key, &bad).unwrap()).is_err());
+
+            // A length prefix longer than the data.
+            let mut bad = padded;
+            bad[..PADDING_LEN_PREFIX].copy_from_slice(&57u64.to_le_bytes());
+            assert!(open_padded(&key, &seal(&key, &bad).unwrap()).is_err());
+            bad[..PADDING_LEN_PREFIX].copy_from_slice(&u64::MAX.to_le_bytes());
+            assert!(open_padded(&key, &seal(&key, &bad).unwrap()).is_err());
+
+            // Too short to hold the length prefix.
+            assert!(open_padded(&key, &seal(&key, &[0u8; 7]).unwrap()).is_err());
+        }
+
+        #[test]
+        fn test_errors() {
+            let key = SecretKey::default();
+            assert!(seal_padded(&key, b"Secret message", 0).is_err());
+            assert!(seal_padded(&key, b"Secret message", usize::MAX).is_err());
+
+            let mut ciphertext = seal_padded(&key, b"Secret message", 32).unwrap();
+            assert!(open_padded(&SecretKey::default(), &ciphertext).is_err());
+            ciphertext[30] ^= 1;
+            assert!(open_padded(&key, &ciphertext).is_err());
+
+            let key = SecretKey::generate(31).unwrap();
+            assert!(seal_padded(&key, b"Secret message", 32).is_err());
+        }
+    }
+
     mod test_seal_open {
         use super::*;
 
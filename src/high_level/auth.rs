@@ -98,6 +98,19 @@ pub fn authenticate(secret_key: &SecretKey, data: &[u8]) -> Result<Tag, UnknownC
 
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
 /// Authenticate and verify a message using BLAKE2b-256 in keyed mode.
+///
+/// Ignoring the result of a verification is a warning, and thus an error with
+/// `#![deny(warnings)]`:
+/// ```rust,compile_fail
+/// #![deny(unused_must_use)]
+/// use orion::auth;
+///
+/// let key = auth::SecretKey::default();
+/// let tag = auth::authenticate(&key, b"Some message.")?;
+///
+/// auth::authenticate_verify(&tag, &key, b"Some message.");
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
 pub fn authenticate_verify(
     expected: &Tag,
     secret_key: &SecretKey,
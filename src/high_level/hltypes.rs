@@ -34,10 +34,11 @@ construct_secret_key_variable_size! {
     /// - `slice` is empty.
     /// - `length` is 0.
     /// - `length` is not less than [`isize::MAX`].
+    /// - Failure to generate random bytes securely, when using `generate()`.
     ///
     /// # Panics:
     /// A panic will occur if:
-    /// - Failure to generate random bytes securely.
+    /// - Failure to generate random bytes securely, when using `default()`.
     (SecretKey, test_secret_key, 32)
 }
 
@@ -51,10 +52,11 @@ construct_salt_variable_size! {
     /// - `slice` is empty.
     /// - `length` is 0.
     /// - `length` is not less than [`isize::MAX`].
+    /// - Failure to generate random bytes securely, when using `generate()`.
     ///
     /// # Panics:
     /// A panic will occur if:
-    /// - Failure to generate random bytes securely.
+    /// - Failure to generate random bytes securely, when using `default()`.
     (Salt, test_salt, 16)
 }
 
@@ -68,9 +70,10 @@ construct_secret_key_variable_size! {
     /// - `slice` is empty.
     /// - `length` is 0.
     /// - `length` is not less than [`isize::MAX`].
+    /// - Failure to generate random bytes securely, when using `generate()`.
     ///
     /// # Panics:
     /// A panic will occur if:
-    /// - Failure to generate random bytes securely.
+    /// - Failure to generate random bytes securely, when using `default()`.
     (Password, test_password, 32)
 }
@@ -23,7 +23,7 @@
 pub mod aead;
 pub mod auth;
 pub mod hash;
-mod hltypes;
+pub(crate) mod hltypes;
 pub mod kdf;
 pub mod kex;
 pub mod pwhash;
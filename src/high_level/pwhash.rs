@@ -73,6 +73,8 @@
 //!   Either use [`hash_password_verify()`] or compare two [`PasswordHash`]es.
 //! - Choosing the correct cost parameters is important for security. Please refer to [libsodium's docs]
 //!   for a description of how to do this.
+//!   [`calibrate()`] can be used to find an amount of iterations that fits a time budget
+//!   on the current machine.
 //!
 //! If the concrete cost parameters needed are unclear, please refer to [OWASP] for recommended minimum values.
 //!
@@ -97,8 +99,9 @@ use super::hltypes::Salt;
 use crate::{
     errors::UnknownCryptoError,
     hazardous::kdf::argon2i::{self, LANES, MIN_MEMORY},
+    util::b64,
 };
-use ct_codecs::{Base64NoPadding, Decoder, Encoder};
+use std::time::{Duration, Instant};
 use zeroize::Zeroizing;
 
 #[cfg(feature = "serde")]
@@ -210,8 +213,8 @@ impl PasswordHash {
             "$argon2i$v=19$m={},t={},p=1${}${}",
             memory,
             iterations,
-            Base64NoPadding::encode_to_string(salt)?,
-            Base64NoPadding::encode_to_string(password_hash)?,
+            b64::encode_no_padding(salt)?,
+            b64::encode_no_padding(password_hash)?,
         ))
     }
 
@@ -311,18 +314,18 @@ impl PasswordHash {
             return Err(UnknownCryptoError);
         }
 
-        let salt = Base64NoPadding::decode_to_vec(parts.next().unwrap(), None)?;
+        let salt = b64::decode_no_padding(parts.next().unwrap())?;
         if salt.len() != SALT_LENGTH {
             return Err(UnknownCryptoError);
         }
-        let password_hash_raw = Base64NoPadding::decode_to_vec(parts.next().unwrap(), None)?;
+        let mut password_hash_raw = b64::decode_no_padding(parts.next().unwrap())?;
         if password_hash_raw.len() != PWHASH_LENGTH {
             return Err(UnknownCryptoError);
         }
 
         Ok(Self {
             encoded_password_hash: password_hash.into(),
-            password_hash: password_hash_raw,
+            password_hash: core::mem::take(&mut *password_hash_raw),
             salt: Salt::from_slice(&salt)?,
             iterations,
             memory,
@@ -374,6 +377,16 @@ impl core::fmt::Debug for PasswordHash {
 
 impl_ct_partialeq_trait!(PasswordHash, unprotected_as_bytes);
 
+/// Delegates to [`PasswordHash::from_encoded()`], so that an encoded password hash can be
+/// parsed with [`str::parse()`].
+impl core::str::FromStr for PasswordHash {
+    type Err = UnknownCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_encoded(s)
+    }
+}
+
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 /// `PasswordHash` serializes as would a [`String`](std::string::String). Note that
@@ -476,6 +489,71 @@ pub fn hash_password_verify(
     )
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Find an amount of `iterations` for which [`hash_password()`] takes about
+/// `target` on this machine, when using `memory` KiB.
+///
+/// The search starts at the minimum of 3 iterations and keeps doubling them, timing one
+/// Argon2i derivation per step, until a derivation takes at least `target`. The result
+/// is then scaled linearly from the last measurement. It is never less than 3.
+///
+/// The measurement is only an estimate. It depends on the load of the machine at the time
+/// of calling, so the result should be treated as a lower bound and not be decreased
+/// below recommended minimum values, see [OWASP].
+///
+/// # Errors:
+/// An error will be returned if:
+/// - `memory` is less than 8.
+///
+/// # Example:
+/// ```rust
+/// use orion::pwhash;
+/// use std::time::Duration;
+///
+/// let memory = 1 << 16;
+/// let iterations = pwhash::calibrate(Duration::from_millis(250), memory)?;
+///
+/// let password = pwhash::Password::from_slice(b"Secret password")?;
+/// let hash = pwhash::hash_password(&password, iterations, memory)?;
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
+/// [OWASP]: https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html
+pub fn calibrate(target: Duration, memory: u32) -> Result<u32, UnknownCryptoError> {
+    if memory < MIN_MEMORY {
+        return Err(UnknownCryptoError);
+    }
+
+    // The derived key is discarded, so the inputs need not be secret.
+    let password = [0u8; 16];
+    let salt = [0u8; SALT_LENGTH];
+    let mut buffer = [0u8; PWHASH_LENGTH];
+
+    let mut iterations = MIN_ITERATIONS;
+    loop {
+        let start = Instant::now();
+        argon2i::derive_key(
+            &password,
+            &salt,
+            iterations,
+            memory,
+            None,
+            None,
+            &mut buffer,
+        )?;
+        let elapsed = start.elapsed();
+
+        if elapsed >= target || iterations == u32::MAX {
+            let estimate = target.as_nanos() * u128::from(iterations) / elapsed.as_nanos().max(1);
+
+            return Ok(u32::try_from(estimate)
+                .unwrap_or(u32::MAX)
+                .max(MIN_ITERATIONS));
+        }
+
+        iterations = iterations.saturating_mul(2);
+    }
+}
+
 // Testing public functions in the module.
 #[cfg(test)]
 mod public {
@@ -868,8 +946,8 @@ mod public {
             memory: u32,
         ) -> bool {
             let res = PasswordHash::from_slice(&password[..], &salt[..], iterations, memory);
-            if res.is_ok() {
-                assert!(PasswordHash::from_encoded(res.unwrap().unprotected_as_encoded()).is_ok());
+            if let Ok(res) = res {
+                assert!(PasswordHash::from_encoded(res.unprotected_as_encoded()).is_ok());
             }
 
             true
@@ -988,4 +1066,75 @@ mod public {
             assert!(hash_password(&password, MIN_ITERATIONS, MIN_MEMORY - 1).is_err());
         }
     }
+
+    mod test_calibrate {
+        use super::*;
+
+        #[test]
+        fn test_calibrate_invalid_memory() {
+            assert!(calibrate(Duration::from_millis(1), MIN_MEMORY - 1).is_err());
+        }
+
+        #[test]
+        fn test_calibrate_zero_target() {
+            assert_eq!(
+                calibrate(Duration::ZERO, MIN_MEMORY).unwrap(),
+                MIN_ITERATIONS
+            );
+        }
+
+        #[test]
+        fn test_calibrate_result_is_usable() {
+            let iterations = calibrate(Duration::from_millis(5), 64).unwrap();
+            let password = Password::from_slice(&[0u8; 64]).unwrap();
+            let hash = hash_password(&password, iterations, 64).unwrap();
+            assert!(hash_password_verify(&hash, &password).is_ok());
+        }
+
+        #[test]
+        fn test_calibrate_monotonic() {
+            // The measurements are noisy, so only a large difference in targets is compared.
+            let low = calibrate(Duration::from_millis(2), 64).unwrap();
+            let high = calibrate(Duration::from_millis(100), 64).unwrap();
+            assert!(low <= high);
+        }
+    }
+
+    mod test_from_str {
+        use super::*;
+
+        #[test]
+        fn test_parse_roundtrip() {
+            let password = Password::from_slice(b"Secret password").unwrap();
+            let hash = hash_password(&password, 3, 64).unwrap();
+
+            let parsed: PasswordHash = hash.unprotected_as_encoded().parse().unwrap();
+            assert_eq!(parsed, hash);
+            assert_eq!(
+                parsed.unprotected_as_encoded(),
+                hash.unprotected_as_encoded()
+            );
+            assert!(hash_password_verify(&parsed, &password).is_ok());
+        }
+
+        #[test]
+        fn test_parse_malformed() {
+            let valid = "$argon2i$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c";
+            assert!(valid.parse::<PasswordHash>().is_ok());
+
+            let malformed = [
+                "",
+                "$argon2id$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c",
+                "$pbkdf2-sha512$i=100000$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c",
+                "$argon2i$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/",
+                "$argon2i$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c$",
+                "argon2i$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c",
+                "$argon2i$v=19$m=65536,t=3,p=1$c29tZXNhbHRzb21lc2FsdA=$fRsRY9PAt5H+qAKuXRzL0/6JbFShsCd62W5aHzESk/c",
+            ];
+
+            for encoded in malformed.iter() {
+                assert!(encoded.parse::<PasswordHash>().is_err());
+            }
+        }
+    }
 }
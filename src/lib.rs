@@ -66,9 +66,6 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-#[cfg(test)]
-#[cfg(feature = "safe_api")]
-extern crate quickcheck;
 #[cfg(test)]
 #[cfg(feature = "safe_api")]
 #[macro_use(quickcheck)]
@@ -85,6 +82,7 @@ mod typedefs;
 /// Utilities such as constant-time comparison.
 pub mod util;
 
+#[macro_use]
 /// Errors for Orion's cryptographic operations.
 pub mod errors;
 
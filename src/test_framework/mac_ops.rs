@@ -0,0 +1,226 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::UnknownCryptoError;
+use crate::hazardous::mac::{hmac, poly1305};
+
+/// An operation on a streaming MAC state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// Call `update()` with the given data.
+    Update(Vec<u8>),
+    /// Call `finalize()`.
+    Finalize,
+    /// Call `reset()`.
+    Reset,
+}
+
+/// The MAC driven by [`run_ops()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mac {
+    /// HMAC-SHA256.
+    HmacSha256,
+    /// HMAC-SHA384.
+    HmacSha384,
+    /// HMAC-SHA512.
+    HmacSha512,
+    /// Poly1305.
+    Poly1305,
+}
+
+/// The streaming state of a MAC, together with its key for `one_shot()`.
+trait OpsState {
+    fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError>;
+
+    fn finalize(&mut self) -> Result<Vec<u8>, UnknownCryptoError>;
+
+    fn reset(&mut self);
+
+    fn one_shot(&self, data: &[u8]) -> Result<Vec<u8>, UnknownCryptoError>;
+}
+
+macro_rules! impl_ops_state (($state:ty, $key:ty, $one_shot:path) => (
+    impl OpsState for ($state, $key) {
+        fn update(&mut self, data: &[u8]) -> Result<(), UnknownCryptoError> {
+            self.0.update(data)
+        }
+
+        fn finalize(&mut self) -> Result<Vec<u8>, UnknownCryptoError> {
+            Ok(self.0.finalize()?.unprotected_as_bytes().to_vec())
+        }
+
+        // Poly1305::reset() is deprecated, but still needs to be tested.
+        #[allow(deprecated)]
+        fn reset(&mut self) {
+            self.0.reset()
+        }
+
+        fn one_shot(&self, data: &[u8]) -> Result<Vec<u8>, UnknownCryptoError> {
+            Ok($one_shot(&self.1, data)?.unprotected_as_bytes().to_vec())
+        }
+    }
+));
+
+impl_ops_state!(
+    hmac::sha256::HmacSha256,
+    hmac::sha256::SecretKey,
+    hmac::sha256::HmacSha256::hmac
+);
+impl_ops_state!(
+    hmac::sha384::HmacSha384,
+    hmac::sha384::SecretKey,
+    hmac::sha384::HmacSha384::hmac
+);
+impl_ops_state!(
+    hmac::sha512::HmacSha512,
+    hmac::sha512::SecretKey,
+    hmac::sha512::HmacSha512::hmac
+);
+impl_ops_state!(
+    poly1305::Poly1305,
+    poly1305::OneTimeKey,
+    poly1305::Poly1305::poly1305
+);
+
+/// Apply `ops` to `state`, checking every result against the expected state machine.
+fn drive<T: OpsState>(mut state: T, ops: &[Op]) -> Vec<Result<Vec<u8>, UnknownCryptoError>> {
+    let mut results = Vec::with_capacity(ops.len());
+    // All data passed to update() since the last reset.
+    let mut message: Vec<u8> = Vec::new();
+    let mut is_finalized = false;
+
+    for op in ops.iter() {
+        match op {
+            Op::Update(data) => {
+                let res = state.update(data);
+                assert_eq!(res.is_err(), is_finalized);
+                if res.is_ok() {
+                    message.extend_from_slice(data);
+                }
+                results.push(res.map(|_| Vec::new()));
+            }
+            Op::Finalize => {
+                let res = state.finalize();
+                if is_finalized {
+                    assert!(res.is_err());
+                } else {
+                    assert_eq!(res, state.one_shot(&message));
+                }
+                is_finalized = true;
+                results.push(res);
+            }
+            Op::Reset => {
+                state.reset();
+                message.clear();
+                is_finalized = false;
+                results.push(Ok(Vec::new()));
+            }
+        }
+    }
+
+    results
+}
+
+/// Drive a streaming `mac` state initialized with `key` through `ops`, returning the
+/// result of each operation. The tag is returned for [`Op::Finalize`] and an empty
+/// vector for the other operations.
+///
+/// Intended for fuzzing: every result is checked against the expected state machine
+/// and each tag against the one-shot function over all data since the last reset,
+/// panicking on any mismatch.
+///
+/// HMAC keys of any length are accepted and padded as with `SecretKey::from_slice_padded()`.
+///
+/// # Errors:
+/// An error will be returned if:
+/// - `key` is not a valid key for `mac`.
+pub fn run_ops(
+    mac: Mac,
+    key: &[u8],
+    ops: &[Op],
+) -> Result<Vec<Result<Vec<u8>, UnknownCryptoError>>, UnknownCryptoError> {
+    Ok(match mac {
+        Mac::HmacSha256 => {
+            let sk = hmac::sha256::SecretKey::from_slice_padded(key)?;
+            drive((hmac::sha256::HmacSha256::new(&sk), sk), ops)
+        }
+        Mac::HmacSha384 => {
+            let sk = hmac::sha384::SecretKey::from_slice_padded(key)?;
+            drive((hmac::sha384::HmacSha384::new(&sk), sk), ops)
+        }
+        Mac::HmacSha512 => {
+            let sk = hmac::sha512::SecretKey::from_slice_padded(key)?;
+            drive((hmac::sha512::HmacSha512::new(&sk), sk), ops)
+        }
+        Mac::Poly1305 => {
+            let sk = poly1305::OneTimeKey::from_slice(key)?;
+            drive((poly1305::Poly1305::new(&sk), sk), ops)
+        }
+    })
+}
+
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    #[test]
+    fn test_mixed_ops() {
+        let ops = [
+            Op::Update(vec![1u8; 15]),
+            Op::Update(vec![2u8; 1]),
+            Op::Update(vec![3u8; 200]),
+            Op::Finalize,
+            Op::Update(vec![4u8; 3]),
+            Op::Finalize,
+            Op::Reset,
+            Op::Finalize,
+            Op::Reset,
+            Op::Update(vec![]),
+            Op::Update(vec![5u8; 129]),
+            Op::Finalize,
+        ];
+
+        for mac in [
+            Mac::HmacSha256,
+            Mac::HmacSha384,
+            Mac::HmacSha512,
+            Mac::Poly1305,
+        ] {
+            let results = run_ops(mac, &[1u8; 32], &ops).unwrap();
+            assert_eq!(results.len(), ops.len());
+
+            assert!(results[3].is_ok());
+            assert!(results[4].is_err());
+            assert!(results[5].is_err());
+            assert!(results[7].is_ok());
+            assert!(results[11].is_ok());
+            assert_ne!(results[3], results[7]);
+            assert_ne!(results[7], results[11]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_key_err() {
+        assert!(run_ops(Mac::Poly1305, &[0u8; 31], &[Op::Finalize]).is_err());
+        assert!(run_ops(Mac::HmacSha256, &[0u8; 31], &[Op::Finalize]).is_ok());
+    }
+}
@@ -35,3 +35,7 @@ pub mod streamcipher_interface;
 #[cfg(feature = "safe_api")]
 /// Tests for KEMs such as `mlkem`.
 pub mod kem_interface;
+
+#[cfg(feature = "safe_api")]
+/// Operation sequences for MAC streaming states such as `hmac` and `poly1305`.
+pub mod mac_ops;
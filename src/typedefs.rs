@@ -20,6 +20,145 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+/// Fixed-capacity storage for the bytes of a type made with `construct_tag!`.
+///
+/// `N` is the maximum length. The bytes are zeroized on drop and `original_length`
+/// holds the amount of bytes the value was created from.
+#[derive(Clone)]
+pub(crate) struct FixedBytes<const N: usize> {
+    value: [u8; N],
+    original_length: usize,
+}
+
+impl<const N: usize> FixedBytes<N> {
+    /// Construct from a given byte slice, which must be between `lower_bound`
+    /// and `N` bytes (inclusive).
+    pub(crate) fn from_slice(
+        slice: &[u8],
+        lower_bound: usize,
+    ) -> Result<Self, crate::errors::UnknownCryptoError> {
+        let slice_len = slice.len();
+
+        if !(lower_bound..=N).contains(&slice_len) {
+            return Err(crate::errors::UnknownCryptoError);
+        }
+
+        let mut value = [0u8; N];
+        value[..slice_len].copy_from_slice(slice);
+
+        Ok(Self {
+            value,
+            original_length: slice_len,
+        })
+    }
+
+    /// Construct from an array of the full length.
+    pub(crate) fn from_array(value: [u8; N]) -> Self {
+        Self {
+            value,
+            original_length: N,
+        }
+    }
+
+    #[inline]
+    /// Return the bytes the value was created from.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.value[..self.original_length]
+    }
+
+    #[inline]
+    /// Return the amount of bytes the value was created from.
+    pub(crate) fn len(&self) -> usize {
+        self.original_length
+    }
+
+    /// Return the XOR of `self` and `other` over the full `N` bytes. Both must have
+    /// been created from the same amount of bytes.
+    pub(crate) fn xor(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.original_length, other.original_length);
+        let mut value = [0u8; N];
+        for ((out, a), b) in value
+            .iter_mut()
+            .zip(self.value.iter())
+            .zip(other.value.iter())
+        {
+            *out = a ^ b;
+        }
+
+        Self {
+            value,
+            original_length: self.original_length,
+        }
+    }
+}
+
+impl<const N: usize> Drop for FixedBytes<N> {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.value.iter_mut().zeroize();
+    }
+}
+
+/// Check the bounds given to a `construct_*` macro: `lower_bound` must be non-zero,
+/// since these types are never empty, and no greater than `upper_bound`.
+///
+/// The macros evaluate this in a `const` item, so a wrong size fails compilation.
+pub(crate) const fn valid_bounds(lower_bound: usize, upper_bound: usize) -> bool {
+    lower_bound >= 1 && lower_bound <= upper_bound
+}
+
+/// Check that a type with fixed size takes exactly `expected_size` bytes, for types
+/// whose size follows from another constant, such as a Poly1305 tag being one block.
+pub(crate) const fn exact_size(
+    lower_bound: usize,
+    upper_bound: usize,
+    expected_size: usize,
+) -> bool {
+    lower_bound == expected_size && upper_bound == expected_size
+}
+
+/// Check that `generate()` would produce a length accepted by `from_slice()`.
+pub(crate) const fn valid_gen_length(
+    lower_bound: usize,
+    upper_bound: usize,
+    gen_length: usize,
+) -> bool {
+    valid_bounds(lower_bound, upper_bound) && gen_length >= lower_bound && gen_length <= upper_bound
+}
+
+#[cfg(feature = "safe_api")]
+/// Overwrite the entire allocation of `bytes` with zeroes, including any spare
+/// capacity, which may still hold bytes from before a truncation. The length is
+/// left at the capacity, so that the result can be inspected.
+pub(crate) fn zeroize_capacity(bytes: &mut Vec<u8>) {
+    use zeroize::Zeroize;
+    // Resizing up to the current capacity never reallocates.
+    bytes.resize(bytes.capacity(), 0u8);
+    bytes.iter_mut().zeroize();
+}
+
+#[cfg(feature = "safe_api")]
+/// Read exactly `len` bytes from `reader` into a buffer that is wiped when dropped.
+/// The buffer is allocated once, up front, so no copies of the bytes are left behind
+/// by reallocation. An error is returned if the allocation fails or if fewer than
+/// `len` bytes could be read.
+pub(crate) fn read_exact_zeroizing<R: std::io::Read + ?Sized>(
+    reader: &mut R,
+    len: usize,
+) -> Result<zeroize::Zeroizing<Vec<u8>>, crate::errors::UnknownCryptoError> {
+    let mut buffer = zeroize::Zeroizing::new(Vec::new());
+    buffer
+        .try_reserve_exact(len)
+        .map_err(|_| crate::errors::UnknownCryptoError)?;
+    // Resizing up to the reserved capacity never reallocates.
+    buffer.resize(len, 0u8);
+    reader
+        .read_exact(&mut buffer)
+        .map_err(|_| crate::errors::UnknownCryptoError)?;
+
+    Ok(buffer)
+}
+
 // Trait implementation macros
 
 #[cfg(feature = "safe_api")]
@@ -149,18 +288,45 @@ macro_rules! impl_asref_trait (($name:ident) => (
 /// which has fields `value` and `original_length`. It implements From
 /// based on `$size` and this macro should, in most cases, only be used for
 /// types which have a fixed-length.
-macro_rules! impl_from_trait (($name:ident, $size:expr) => (
-    impl From<[u8; $size]> for $name {
-        #[inline]
-        /// Make an object from a byte array.
-        fn from(bytes: [u8; $size]) -> $name {
-            $name {
-                value: bytes,
-                original_length: $size
+macro_rules! impl_from_trait {
+    ($name:ident, $size:expr) => {
+        impl From<[u8; $size]> for $name {
+            #[inline]
+            /// Make an object from a byte array.
+            fn from(bytes: [u8; $size]) -> $name {
+                $name {
+                    value: bytes,
+                    original_length: $size,
+                }
+            }
+        }
+    };
+
+    (@fixed_bytes $name:ident, $size:expr) => {
+        impl From<[u8; $size]> for $name {
+            #[inline]
+            /// Make an object from a byte array.
+            fn from(bytes: [u8; $size]) -> $name {
+                $name {
+                    bytes: crate::typedefs::FixedBytes::from_array(bytes),
+                }
             }
         }
-    }
-));
+
+        impl From<$name> for [u8; $size] {
+            #[inline]
+            /// Copy the bytes of the object into an array. __**Warning**__: This
+            /// __**breaks protections**__ that the type implements, since the array
+            /// is neither wiped on drop nor compared in constant time.
+            fn from(value: $name) -> [u8; $size] {
+                let mut bytes = [0u8; $size];
+                bytes.copy_from_slice(value.unprotected_as_bytes());
+
+                bytes
+            }
+        }
+    };
+}
 
 /// Macro that implements `TryFrom<&[u8]>` on an object called `$name` that
 /// implements the method `from_slice`.
@@ -174,6 +340,65 @@ macro_rules! impl_try_from_trait (($name:ident) => (
     }
 ));
 
+#[cfg(feature = "safe_api")]
+/// Macro that implements `TryFrom<Vec<u8>>` on an object called `$name` that
+/// implements the method `from_slice`.
+macro_rules! impl_try_from_vec_trait (($name:ident) => (
+    /// Delegates to `from_slice` implementation. The entire allocation of the vector,
+    /// including spare capacity, is zeroized before it is dropped, whether the conversion
+    /// succeeds or not.
+    impl TryFrom<Vec<u8>> for $name {
+        type Error = UnknownCryptoError;
+        fn try_from(mut bytes: Vec<u8>) -> Result<Self, Self::Error> {
+            let ret = Self::from_slice(&bytes);
+            crate::typedefs::zeroize_capacity(&mut bytes);
+
+            ret
+        }
+    }
+));
+
+/// Macro that implements `Extend<u8>` and `Extend<&u8>` on a streaming state called `$name`
+/// that implements the method `update`. Bytes are collected in a small buffer on the stack
+/// before being passed to `update`.
+macro_rules! impl_extend_trait (($name:ident) => (
+    /// Update the state with the bytes of an iterator.
+    ///
+    /// `Extend` cannot return errors, so any error from `update()` is ignored and the
+    /// input it was given is discarded. This happens in two cases:
+    /// - The state has already been finalized. `finalize()` then keeps returning an
+    ///   error until `reset()` is called.
+    /// - For types that count the processed bytes in a `u64`, the input would overflow
+    ///   that counter. The state is otherwise left as it was, so a later `finalize()`
+    ///   succeeds with a tag that does not cover the discarded input. Use `update()`
+    ///   directly if the input may be this large.
+    impl Extend<u8> for $name {
+        fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+            use zeroize::Zeroize;
+
+            let mut buffer = [0u8; 64];
+            let mut buffered = 0;
+            for byte in iter {
+                buffer[buffered] = byte;
+                buffered += 1;
+                if buffered == buffer.len() {
+                    let _ = self.update(&buffer);
+                    buffered = 0;
+                }
+            }
+            let _ = self.update(&buffer[..buffered]);
+            buffer.zeroize();
+        }
+    }
+
+    /// Update the state with the bytes of an iterator. See `Extend<u8>`.
+    impl<'a> Extend<&'a u8> for $name {
+        fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+            self.extend(iter.into_iter().copied());
+        }
+    }
+));
+
 // Function implementation macros
 
 /// Macro to implement a `from_slice()` function. Returns `UnknownCryptoError`
@@ -218,6 +443,57 @@ macro_rules! func_from_slice_variable_size (($name:ident) => (
     }
 ));
 
+/// Macro to implement a `from_reader()` function for secret types of a fixed size.
+/// Bytes are read into a stack buffer of `$upper_bound` bytes that is wiped afterwards,
+/// and then passed to `from_slice()`.
+macro_rules! func_from_reader (($name:ident, $upper_bound:expr) => (
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(feature = "safe_api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
+    /// Construct from exactly `len` bytes read from `reader`. The bytes are only
+    /// held in a buffer that is wiped once the key has been constructed, also on error.
+    /// An error is returned if `len` is greater than the maximum size of this type,
+    /// if `len` is not accepted by `from_slice()` or if fewer than `len` bytes could be read.
+    pub fn from_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        len: usize,
+    ) -> Result<$name, UnknownCryptoError> {
+        if len > $upper_bound {
+            return Err(UnknownCryptoError);
+        }
+
+        let mut buffer = zeroize::Zeroizing::new([0u8; $upper_bound]);
+        reader.read_exact(&mut buffer[..len]).map_err(|_| UnknownCryptoError)?;
+
+        Self::from_slice(&buffer[..len])
+    }
+));
+
+#[cfg(feature = "safe_api")]
+/// Macro to implement a `from_reader()` function for secret types of a variable size.
+macro_rules! func_from_reader_variable_size (($name:ident) => (
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(feature = "safe_api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
+    /// Construct from exactly `len` bytes read from `reader`. The bytes are only
+    /// held in a buffer that is wiped once the key has been constructed, also on error.
+    /// An error is returned if `len` is not accepted by `from_slice()`, if a buffer of
+    /// `len` bytes cannot be allocated or if fewer than `len` bytes could be read.
+    pub fn from_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        len: usize,
+    ) -> Result<$name, UnknownCryptoError> {
+        // See issue on `isize` limit: https://github.com/orion-rs/orion/issues/130
+        if len < 1 || len > (isize::MAX as usize) {
+            return Err(UnknownCryptoError);
+        }
+
+        let buffer = crate::typedefs::read_exact_zeroizing(reader, len)?;
+
+        Self::from_slice(&buffer)
+    }
+));
+
 /// Macro to implement a `unprotected_as_bytes()` function for objects that
 /// implement extra protections. Typically used on objects that implement
 /// `Drop`.
@@ -266,6 +542,16 @@ macro_rules! func_generate (($name:ident, $upper_bound:expr, $gen_length:expr) =
 
         $name { value, original_length: $gen_length }
     }
+
+    #[cfg(feature = "rand_core")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+    /// Randomly generate using the given CSPRNG. This is also available in `no_std` context.
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> $name {
+        let mut value = [0u8; $upper_bound];
+        rng.fill_bytes(&mut value[..$gen_length]);
+
+        $name { value, original_length: $gen_length }
+    }
 ));
 
 #[cfg(feature = "safe_api")]
@@ -283,15 +569,31 @@ macro_rules! func_generate_variable_size (($name:ident) => (
         }
 
         let mut value = vec![0u8; length];
-        // This cannot panic on size input due to above length checks.
-        crate::util::secure_rand_bytes(&mut value).unwrap();
+        crate::util::fill_rand_bytes(&mut value)?;
+
+        Ok($name { value, original_length: length })
+    }
+
+    #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+    #[cfg(all(feature = "safe_api", feature = "rand_core"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "safe_api", feature = "rand_core"))))]
+    /// Randomly generate using the given CSPRNG.
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(
+        rng: &mut R,
+        length: usize,
+    ) -> Result<$name, UnknownCryptoError> {
+        if length < 1 || length > (isize::MAX as usize) {
+            return Err(UnknownCryptoError);
+        }
+
+        let mut value = vec![0u8; length];
+        rng.fill_bytes(&mut value);
 
         Ok($name { value, original_length: length })
     }
 ));
 
-///
-/// Test implementation macros
+// Test implementation macros
 
 #[cfg(test)]
 #[cfg(feature = "serde")]
@@ -359,6 +661,32 @@ macro_rules! test_from_slice (($name:ident, $lower_bound:expr, $upper_bound:expr
     }
 ));
 
+#[cfg(test)]
+#[cfg(feature = "safe_api")]
+macro_rules! test_try_from_vec (($name:ident, $lower_bound:expr, $upper_bound:expr) => (
+    #[test]
+    fn test_try_from_vec() {
+        let mut bytes = Vec::with_capacity($upper_bound * 2);
+        bytes.extend_from_slice(&[1u8; $upper_bound]);
+        assert_eq!(
+            $name::try_from(bytes).unwrap(),
+            $name::from_slice(&[1u8; $upper_bound]).unwrap()
+        );
+        assert_eq!(
+            $name::try_from(vec![1u8; $lower_bound]).unwrap(),
+            $name::from_slice(&[1u8; $lower_bound]).unwrap()
+        );
+        assert_eq!(
+            $name::try_from(vec![1u8; $upper_bound + 1]).is_ok(),
+            $name::from_slice(&[1u8; $upper_bound + 1]).is_ok()
+        );
+        assert_eq!(
+            $name::try_from(Vec::new()).is_ok(),
+            $name::from_slice(&[]).is_ok()
+        );
+    }
+));
+
 #[cfg(test)]
 macro_rules! test_as_bytes_and_get_length (($name:ident, $lower_bound:expr, $upper_bound:expr, $bytes_function:ident) => (
     #[test]
@@ -405,6 +733,55 @@ macro_rules! test_generate (($name:ident, $gen_length:expr) => (
         // A random generated one should always be $gen_length in length.
         assert_eq!(test_rand.len(), $gen_length);
     }
+
+    #[test]
+    #[cfg(all(feature = "safe_api", feature = "rand_core"))]
+    fn test_generate_with_rng() {
+        use rand::SeedableRng;
+        use rand_core::TryRngCore;
+
+        let test_zero = $name::from_slice(&[0u8; $gen_length]).unwrap();
+        let test_rand = $name::generate_with_rng(&mut rand_core::OsRng.unwrap_err());
+        assert_ne!(test_zero, test_rand);
+        assert_eq!(test_rand.len(), $gen_length);
+
+        // The same seed gives the same value.
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let seeded = $name::generate_with_rng(&mut rng);
+        assert_ne!(seeded, test_zero);
+        assert_eq!(seeded, $name::generate_with_rng(&mut rand::rngs::StdRng::seed_from_u64(0)));
+        assert_ne!(seeded, $name::generate_with_rng(&mut rng));
+    }
+));
+
+#[cfg(test)]
+#[cfg(feature = "safe_api")]
+macro_rules! test_from_reader (($name:ident, $lower_bound:expr, $upper_bound:expr) => (
+    #[test]
+    #[cfg(feature = "safe_api")]
+    fn test_from_reader() {
+        use std::io::Cursor;
+
+        let input: Vec<u8> = (0..=$upper_bound).map(|b| b as u8).collect();
+        for len in [0, 1, $lower_bound, $upper_bound, $upper_bound + 1] {
+            let mut reader = Cursor::new(&input[..]);
+            match $name::from_slice(&input[..len]) {
+                Ok(expected) => {
+                    assert_eq!($name::from_reader(&mut reader, len).unwrap(), expected);
+                    assert_eq!(reader.position(), len as u64);
+                }
+                Err(_) => assert!($name::from_reader(&mut reader, len).is_err()),
+            }
+        }
+
+        // Short reads.
+        let mut reader = Cursor::new(&input[..$upper_bound - 1]);
+        assert!($name::from_reader(&mut reader, $upper_bound).is_err());
+        assert!($name::from_reader(&mut Cursor::new(&[]), $upper_bound).is_err());
+
+        // A length that cannot be allocated is an error, not an abort.
+        assert!($name::from_reader(&mut Cursor::new(&input[..]), isize::MAX as usize).is_err());
+    }
 ));
 
 #[cfg(test)]
@@ -462,6 +839,29 @@ macro_rules! test_generate_variable (($name:ident) => (
         let test_rand = $name::generate(128).unwrap();
         assert_ne!(test_zero, test_rand);
         assert_eq!(test_rand.len(), 128);
+
+        // Failure of the CSPRNG should be reported as an error.
+        crate::util::with_failing_rng(|| {
+            assert!($name::generate(32).is_err());
+        });
+    }
+));
+
+#[cfg(test)]
+#[cfg(all(feature = "safe_api", feature = "rand_core"))]
+macro_rules! test_generate_variable_with_rng (($name:ident) => (
+    #[test]
+    fn test_generate_variable_with_rng() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert!($name::generate_with_rng(&mut rng, 0).is_err());
+        assert!($name::generate_with_rng(&mut rng, (isize::MAX as usize) + 1).is_err());
+
+        let seeded = $name::generate_with_rng(&mut rng, 128).unwrap();
+        assert_eq!(seeded.len(), 128);
+        assert_ne!(seeded, $name::from_slice(&[0u8; 128]).unwrap());
+        assert_ne!(seeded, $name::generate_with_rng(&mut rng, 128).unwrap());
     }
 ));
 
@@ -478,6 +878,8 @@ macro_rules! test_generate_variable (($name:ident) => (
 /// - $lower_bound/$upper_bound: An inclusive range that defines what length a
 ///   secret value might be. Used to validate length of `slice` in from_slice().
 ///   $upper_bound also defines the `value` field array allocation size.
+///   The range must be non-empty and start at 1 or above, which is checked at
+///   compile time.
 ///
 /// - $gen_length: The amount of data to be randomly generated when using
 ///   generate().
@@ -515,12 +917,21 @@ macro_rules! construct_secret_key {
             original_length: usize,
         }
 
+        const _: () = assert!(
+            crate::typedefs::valid_bounds($lower_bound, $upper_bound),
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
         impl_omitted_debug_trait!($name);
         impl_drop_trait!($name);
         impl_ct_partialeq_trait!($name, unprotected_as_bytes);
 
+        #[cfg(feature = "safe_api")]
+        impl_try_from_vec_trait!($name);
+
         impl $name {
             func_from_slice!($name, $lower_bound, $upper_bound);
+            func_from_reader!($name, $upper_bound);
             func_unprotected_as_bytes!();
             func_len!();
             func_is_empty!();
@@ -541,6 +952,8 @@ macro_rules! construct_secret_key {
                 use super::*;
 
                 test_omitted_debug!($name, $upper_bound);
+                test_try_from_vec!($name, $lower_bound, $upper_bound);
+                test_from_reader!($name, $lower_bound, $upper_bound);
             }
         }
     );
@@ -578,12 +991,21 @@ macro_rules! construct_secret_key {
             original_length: usize,
         }
 
+        const _: () = assert!(
+            crate::typedefs::valid_gen_length($lower_bound, $upper_bound, $gen_length),
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
         impl_omitted_debug_trait!($name);
         impl_drop_trait!($name);
         impl_ct_partialeq_trait!($name, unprotected_as_bytes);
 
+        #[cfg(feature = "safe_api")]
+        impl_try_from_vec_trait!($name);
+
         impl $name {
             func_from_slice!($name, $lower_bound, $upper_bound);
+            func_from_reader!($name, $upper_bound);
             func_unprotected_as_bytes!();
             func_generate!($name, $upper_bound, $gen_length);
             func_len!();
@@ -606,6 +1028,8 @@ macro_rules! construct_secret_key {
 
                 test_generate!($name, $gen_length);
                 test_omitted_debug!($name, $upper_bound);
+                test_try_from_vec!($name, $lower_bound, $upper_bound);
+                test_from_reader!($name, $lower_bound, $upper_bound);
             }
         }
     );
@@ -622,6 +1046,8 @@ macro_rules! construct_secret_key {
 /// - $lower_bound/$upper_bound: An inclusive range that defines what length a
 ///   public value might be. Used to validate length of `slice` in from_slice().
 ///   $upper_bound also defines the `value` field array allocation size.
+///   The range must be non-empty and start at 1 or above, which is checked at
+///   compile time.
 ///
 /// - $gen_length: The amount of data to be randomly generated when using
 ///   generate(). If not supplied, the public newtype will not have a
@@ -637,6 +1063,11 @@ macro_rules! construct_public {
             original_length: usize,
         }
 
+        const _: () = assert!(
+            crate::typedefs::valid_bounds($lower_bound, $upper_bound),
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
         impl_ct_partialeq_trait!($name, as_ref);
         impl_normal_debug_trait!($name);
         impl_try_from_trait!($name);
@@ -684,6 +1115,11 @@ macro_rules! construct_public {
             original_length: usize,
         }
 
+        const _: () = assert!(
+            crate::typedefs::valid_gen_length($lower_bound, $upper_bound, $gen_length),
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
         impl_ct_partialeq_trait!($name, as_ref);
         impl_normal_debug_trait!($name);
         impl_try_from_trait!($name);
@@ -725,7 +1161,8 @@ macro_rules! construct_public {
 /// Macro to construct a tag type that MACs return.
 macro_rules! construct_tag {
     ($(#[$meta:meta])*
-    ($name:ident, $test_module_name:ident, $lower_bound:expr, $upper_bound:expr)) => (
+    ($name:ident, $test_module_name:ident, $lower_bound:expr, $upper_bound:expr
+    $(, size = $expected_size:expr)?)) => (
         #[derive(Clone)]
         $(#[$meta])*
         ///
@@ -752,23 +1189,101 @@ macro_rules! construct_tag {
         /// # Ok::<(), orion::errors::UnknownCryptoError>(())
         /// ```
         pub struct $name {
-            value: [u8; $upper_bound],
-            original_length: usize,
+            bytes: crate::typedefs::FixedBytes<{ $upper_bound }>,
         }
 
+        const _: () = assert!(
+            crate::typedefs::valid_bounds($lower_bound, $upper_bound),
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
+        $(
+        const _: () = assert!(
+            crate::typedefs::exact_size($lower_bound, $upper_bound, $expected_size),
+            concat!("size parameters for ", stringify!($name), " are not ", stringify!($expected_size))
+        );
+        )?
+
         impl_omitted_debug_trait!($name);
-        impl_drop_trait!($name);
         impl_ct_partialeq_trait!($name, unprotected_as_bytes);
         impl_try_from_trait!($name);
 
+        /// An all-zero tag of the maximum length, for preallocating buffers of tags.
+        /// Secret key types deliberately do not implement `Default`.
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    bytes: crate::typedefs::FixedBytes::from_array([0u8; $upper_bound]),
+                }
+            }
+        }
+
         #[cfg(feature = "serde")]
         impl_serde_traits!($name, unprotected_as_bytes);
 
         impl $name {
-            func_from_slice!($name, $lower_bound, $upper_bound);
-            func_unprotected_as_bytes!();
-            func_len!();
-            func_is_empty!();
+            #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+            /// Construct from a given byte slice.
+            pub fn from_slice(slice: &[u8]) -> Result<$name, UnknownCryptoError> {
+                Ok($name {
+                    bytes: crate::typedefs::FixedBytes::from_slice(slice, $lower_bound)?,
+                })
+            }
+
+            #[inline]
+            /// Return the object as byte slice. __**Warning**__: Should not be used unless strictly
+            /// needed. This __**breaks protections**__ that the type implements.
+            pub fn unprotected_as_bytes(&self) -> &[u8] {
+                self.bytes.as_bytes()
+            }
+
+            #[inline]
+            /// Return the length of the object.
+            pub fn len(&self) -> usize {
+                self.bytes.len()
+            }
+
+            #[inline]
+            /// Return `true` if this object does not hold any data, `false` otherwise.
+            ///
+            /// __NOTE__: This method should always return `false`, since there shouldn't be a way
+            /// to create an empty instance of this object.
+            pub fn is_empty(&self) -> bool {
+                self.bytes.len() == 0
+            }
+
+            #[cfg(feature = "bytes")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+            /// Write the tag to the end of `buf`, without an intermediate copy.
+            ///
+            /// An error is returned, and nothing is written, if `buf` cannot hold
+            /// [`Self::len()`] more bytes.
+            pub fn put_into<B: bytes::BufMut + ?Sized>(
+                &self,
+                buf: &mut B,
+            ) -> Result<(), UnknownCryptoError> {
+                if buf.remaining_mut() < self.len() {
+                    return Err(UnknownCryptoError);
+                }
+                buf.put_slice(self.unprotected_as_bytes());
+
+                Ok(())
+            }
+
+            /// Return `true` if every byte of the tag is zero, `false` otherwise.
+            /// This executes in constant time.
+            ///
+            /// An all-zero tag often indicates a buffer that was never written to.
+            pub fn is_zero(&self) -> bool {
+                use subtle::ConstantTimeEq;
+
+                let acc = self
+                    .unprotected_as_bytes()
+                    .iter()
+                    .fold(0u8, |acc, byte| acc | byte);
+
+                acc.ct_eq(&0u8).into()
+            }
         }
 
         #[cfg(test)]
@@ -781,6 +1296,48 @@ macro_rules! construct_tag {
             test_as_bytes_and_get_length!($name, $lower_bound, $upper_bound, unprotected_as_bytes);
             test_partial_eq!($name, $upper_bound);
 
+            #[test]
+            fn test_default() {
+                let tag = $name::default();
+                assert_eq!(tag.len(), $upper_bound);
+                assert_eq!(tag.unprotected_as_bytes(), &[0u8; $upper_bound][..]);
+                assert_eq!(tag, $name::from_slice(&[0u8; $upper_bound]).unwrap());
+            }
+
+            #[test]
+            fn test_is_zero() {
+                assert!($name::default().is_zero());
+                assert!($name::from_slice(&[0u8; $lower_bound]).unwrap().is_zero());
+
+                let mut bytes = [0u8; $upper_bound];
+                bytes[$upper_bound - 1] = 1;
+                assert!(!$name::from_slice(&bytes).unwrap().is_zero());
+                bytes[0] = 0xff;
+                assert!(!$name::from_slice(&bytes).unwrap().is_zero());
+                assert!(!$name::from_slice(&[1u8; $upper_bound]).unwrap().is_zero());
+            }
+
+            #[test]
+            #[cfg(feature = "bytes")]
+            fn test_put_into() {
+                let tag = $name::from_slice(&[1u8; $upper_bound]).unwrap();
+
+                let mut buf = bytes::BytesMut::new();
+                buf.extend_from_slice(b"header");
+                tag.put_into(&mut buf).unwrap();
+                assert_eq!(buf.len(), 6 + $upper_bound);
+                assert_eq!(&buf[..6], b"header");
+                assert_eq!(tag, $name::from_slice(&buf[6..]).unwrap());
+
+                let mut fixed = [0u8; $upper_bound];
+                tag.put_into(&mut &mut fixed[..]).unwrap();
+                assert_eq!(tag, &fixed[..]);
+
+                let mut short = [0u8; $upper_bound - 1];
+                assert!(tag.put_into(&mut &mut short[..]).is_err());
+                assert_eq!(short, [0u8; $upper_bound - 1]);
+            }
+
             #[cfg(feature = "serde")]
             test_serde_impls!($name, $upper_bound);
 
@@ -798,8 +1355,112 @@ macro_rules! construct_tag {
 /// Macro to construct a secret key used for HMAC. This pre-pads the given key
 /// to the required length specified by the HMAC specifications.
 macro_rules! construct_hmac_key {
+    (@from_slice $name:ident, $size:expr) => (
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Construct from a given byte slice, which must be exactly the blocksize of the hash
+        /// function. Use `from_slice_padded()` for keys of other lengths.
+        pub fn from_slice(slice: &[u8]) -> Result<$name, UnknownCryptoError> {
+            if slice.len() != $size {
+                return Err(UnknownCryptoError);
+            }
+
+            let mut secret_key = [0u8; $size];
+            secret_key.copy_from_slice(slice);
+
+            Ok($name { value: secret_key, original_length: $size })
+        }
+    );
+
+    (@from_slice $name:ident, $size:expr, padded) => (
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        /// Construct from a given byte slice. This is the same as `from_slice_padded()`.
+        pub fn from_slice(slice: &[u8]) -> Result<$name, UnknownCryptoError> {
+            Self::from_slice_padded(slice)
+        }
+    );
+
+    (@from_reader $name:ident, $size:expr) => (
+        func_from_reader!($name, $size);
+    );
+
+    (@from_reader $name:ident, $size:expr, padded) => (
+        #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+        #[cfg(feature = "safe_api")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
+        /// Construct from exactly `len` bytes read from `reader`, padded as with
+        /// `from_slice_padded()`. The bytes are only held in a buffer that is wiped once
+        /// the key has been constructed, also on error. An error is returned if a buffer
+        /// of `len` bytes cannot be allocated or if fewer than `len` bytes could be read.
+        pub fn from_reader<R: std::io::Read + ?Sized>(
+            reader: &mut R,
+            len: usize,
+        ) -> Result<$name, UnknownCryptoError> {
+            // See issue on `isize` limit: https://github.com/orion-rs/orion/issues/130
+            if len > (isize::MAX as usize) {
+                return Err(UnknownCryptoError);
+            }
+
+            let buffer = crate::typedefs::read_exact_zeroizing(reader, len)?;
+
+            Self::from_slice_padded(&buffer)
+        }
+    );
+
+    (@is_weak $name:ident, $size:expr) => (
+        /// Return `true` if the key was constructed from fewer than the minimum
+        /// recommended 64 bytes, `false` otherwise. Such a key is padded with zeroes
+        /// without any error.
+        pub fn is_weak(&self) -> bool {
+            self.original_length < 64
+        }
+    );
+
+    (@is_weak $name:ident, $size:expr, padded) => ();
+
+    (@test_is_weak $name:ident, $size:expr) => (
+        #[test]
+        fn test_is_weak() {
+            assert!($name::from_slice_padded(&[1u8; 0]).unwrap().is_weak());
+            assert!($name::from_slice_padded(&[1u8; 32]).unwrap().is_weak());
+            assert!($name::from_slice_padded(&[1u8; 63]).unwrap().is_weak());
+            assert!(!$name::from_slice_padded(&[1u8; 64]).unwrap().is_weak());
+            assert!(!$name::from_slice_padded(&[1u8; $size + 1]).unwrap().is_weak());
+            assert!(!$name::from_slice(&[1u8; $size]).unwrap().is_weak());
+        }
+    );
+
+    (@test_is_weak $name:ident, $size:expr, padded) => ();
+
+    (@test_from_slice $name:ident, $size:expr) => (
+        #[test]
+        fn test_from_slice_exact() {
+            assert!($name::from_slice(&[0u8; $size]).is_ok());
+            assert!($name::from_slice(&[0u8; $size - $size]).is_err());
+            assert!($name::from_slice(&[0u8; $size - 1]).is_err());
+            assert!($name::from_slice(&[0u8; $size + 1]).is_err());
+
+            assert_eq!(
+                $name::from_slice(&[1u8; $size]).unwrap(),
+                $name::from_slice_padded(&[1u8; $size]).unwrap()
+            );
+        }
+    );
+
+    (@test_from_slice $name:ident, $size:expr, padded) => (
+        #[test]
+        fn test_from_slice_is_padded() {
+            let input = [1u8; $size + 1];
+            for len in [0, 1, $size - 1, $size, $size + 1] {
+                assert_eq!(
+                    $name::from_slice(&input[..len]).unwrap(),
+                    $name::from_slice_padded(&input[..len]).unwrap()
+                );
+            }
+        }
+    );
+
     ($(#[$meta:meta])*
-    ($name:ident, $sha2:ident, $sha2_outsize:expr, $test_module_name:ident, $size:expr)) => (
+    ($name:ident, $sha2:ident, $sha2_outsize:expr, $test_module_name:ident, $size:expr $(, $padded:ident)?)) => (
         $(#[$meta])*
         ///
         /// # Security:
@@ -828,17 +1489,31 @@ macro_rules! construct_hmac_key {
         /// ```
         pub struct $name {
             value: [u8; $size],
+            // The length of the slice the key was constructed from, before it was padded
+            // or hashed. Unlike other secret types, this is not the length of `value` that
+            // `unprotected_as_bytes()` and `len()` use, which is always `$size`.
             original_length: usize,
         }
 
+        // The hash of a key longer than the blocksize must fit in the padded key.
+        const _: () = assert!(
+            $sha2_outsize <= $size,
+            concat!("invalid size parameters for ", stringify!($name))
+        );
+
         impl_omitted_debug_trait!($name);
         impl_drop_trait!($name);
         impl_ct_partialeq_trait!($name, unprotected_as_bytes);
 
+        #[cfg(feature = "safe_api")]
+        impl_try_from_vec_trait!($name);
+
         impl $name {
             #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
-            /// Construct from a given byte slice.
-            pub fn from_slice(slice: &[u8]) -> Result<$name, UnknownCryptoError> {
+            /// Construct from a given byte slice of any length. As specified for HMAC, a slice
+            /// shorter than the blocksize is padded with zeroes, and a longer slice is hashed
+            /// and then padded.
+            pub fn from_slice_padded(slice: &[u8]) -> Result<$name, UnknownCryptoError> {
                 let mut secret_key = [0u8; $size];
 
                 let slice_len = slice.len();
@@ -849,12 +1524,34 @@ macro_rules! construct_hmac_key {
                     secret_key[..slice_len].copy_from_slice(slice);
                 }
 
-                Ok($name { value: secret_key, original_length: $size })
+                Ok($name { value: secret_key, original_length: slice_len })
             }
 
-            func_unprotected_as_bytes!();
+            #[inline]
+            /// Return the length of the slice the key was constructed from, before it was
+            /// padded or hashed. [`Self::len()`] returns the length after padding.
+            pub fn input_len(&self) -> usize {
+                self.original_length
+            }
+
+            #[inline]
+            /// Return the object as byte slice. __**Warning**__: Should not be used unless strictly
+            /// needed. This __**breaks protections**__ that the type implements.
+            pub fn unprotected_as_bytes(&self) -> &[u8] {
+                self.value.as_ref()
+            }
+
+            #[inline]
+            /// Return the length of the object, after padding. This is always the blocksize
+            /// of the hash function. [`Self::input_len()`] returns the length before padding.
+            pub fn len(&self) -> usize {
+                $size
+            }
+
+            construct_hmac_key!(@from_slice $name, $size $(, $padded)?);
+            construct_hmac_key!(@from_reader $name, $size $(, $padded)?);
+            construct_hmac_key!(@is_weak $name, $size $(, $padded)?);
             func_generate!($name, $size, $size);
-            func_len!();
             func_is_empty!();
         }
 
@@ -866,11 +1563,39 @@ macro_rules! construct_hmac_key {
 
             #[test]
             fn test_key_size() {
-                assert!($name::from_slice(&[0u8; $size]).is_ok());
-                assert!($name::from_slice(&[0u8; $size - $size]).is_ok());
-                assert!($name::from_slice(&[0u8; $size + 1]).is_ok());
+                assert!($name::from_slice_padded(&[0u8; $size]).is_ok());
+                assert!($name::from_slice_padded(&[0u8; $size - $size]).is_ok());
+                assert!($name::from_slice_padded(&[0u8; $size + 1]).is_ok());
+            }
+
+            #[test]
+            fn test_from_slice_padded() {
+                let mut padded = [0u8; $size];
+                padded[..5].copy_from_slice(&[1u8; 5]);
+                assert_eq!(
+                    $name::from_slice_padded(&[1u8; 5]).unwrap(),
+                    $name::from_slice_padded(&padded).unwrap()
+                );
+                assert_eq!($name::from_slice_padded(&[1u8; 5]).unwrap(), &padded[..]);
+                assert_ne!(
+                    $name::from_slice_padded(&[1u8; $size + 1]).unwrap(),
+                    &[1u8; $size][..]
+                );
             }
 
+            #[test]
+            fn test_input_len() {
+                let input = [1u8; $size + 1];
+                for len in [0, 1, 32, 64, $size - 1, $size, $size + 1] {
+                    let key = $name::from_slice_padded(&input[..len]).unwrap();
+                    assert_eq!(key.input_len(), len);
+                    assert_eq!(key.len(), $size);
+                }
+            }
+
+            construct_hmac_key!(@test_from_slice $name, $size $(, $padded)?);
+            construct_hmac_key!(@test_is_weak $name, $size $(, $padded)?);
+
             #[cfg(test)]
             #[cfg(feature = "safe_api")]
             mod tests_with_std {
@@ -878,6 +1603,13 @@ macro_rules! construct_hmac_key {
 
                 test_generate!($name, $size);
                 test_omitted_debug!($name, $size);
+                test_try_from_vec!($name, $size, $size);
+
+                #[test]
+                fn test_generate_input_len() {
+                    assert_eq!($name::generate().input_len(), $size);
+                }
+                test_from_reader!($name, $size, $size);
             }
         }
     );
@@ -928,6 +1660,7 @@ macro_rules! construct_secret_key_variable_size {
 
         impl $name {
             func_from_slice_variable_size!($name);
+            func_from_reader_variable_size!($name);
             func_unprotected_as_bytes!();
             func_len!();
             func_is_empty!();
@@ -941,8 +1674,11 @@ macro_rules! construct_secret_key_variable_size {
             test_from_slice_variable!($name);
             test_as_bytes_and_get_length!($name, 1, $default_size + 1, unprotected_as_bytes);
             test_generate_variable!($name);
+            #[cfg(feature = "rand_core")]
+            test_generate_variable_with_rng!($name);
             test_omitted_debug!($name, $default_size);
             test_partial_eq!($name, $default_size);
+            test_from_reader!($name, 1, $default_size);
         }
     );
 }
@@ -984,6 +1720,8 @@ macro_rules! construct_salt_variable_size {
             test_from_slice_variable!($name);
             test_as_bytes_and_get_length!($name, 1, $default_size + 1, as_ref);
             test_generate_variable!($name);
+            #[cfg(feature = "rand_core")]
+            test_generate_variable_with_rng!($name);
             test_partial_eq!($name, $default_size);
             test_normal_debug!($name, $default_size);
 
@@ -992,3 +1730,135 @@ macro_rules! construct_salt_variable_size {
         }
     );
 }
+
+#[cfg(test)]
+mod test_fixed_bytes {
+    use super::*;
+
+    #[test]
+    fn test_from_slice_bounds() {
+        assert!(FixedBytes::<16>::from_slice(&[0u8; 16], 16).is_ok());
+        assert!(FixedBytes::<16>::from_slice(&[0u8; 15], 16).is_err());
+        assert!(FixedBytes::<16>::from_slice(&[0u8; 17], 16).is_err());
+        assert!(FixedBytes::<16>::from_slice(&[0u8; 0], 1).is_err());
+        assert!(FixedBytes::<16>::from_slice(&[0u8; 1], 1).is_ok());
+    }
+
+    #[test]
+    fn test_len_and_bytes() {
+        let short = FixedBytes::<16>::from_slice(&[1u8; 5], 1).unwrap();
+        assert_eq!(short.len(), 5);
+        assert_eq!(short.as_bytes(), &[1u8; 5]);
+
+        let full = FixedBytes::<16>::from_array([2u8; 16]);
+        assert_eq!(full.len(), 16);
+        assert_eq!(full.as_bytes(), &[2u8; 16]);
+    }
+
+    #[test]
+    fn test_xor() {
+        let a = FixedBytes::<16>::from_array([0b1010u8; 16]);
+        let b = FixedBytes::<16>::from_array([0b0110u8; 16]);
+        assert_eq!(a.xor(&b).as_bytes(), &[0b1100u8; 16]);
+        assert_eq!(a.xor(&a).as_bytes(), &[0u8; 16]);
+    }
+}
+
+#[cfg(test)]
+mod test_ct_partialeq {
+    /// Minimal type to test `impl_ct_partialeq_trait!` in isolation.
+    struct Bytes([u8; 64]);
+
+    impl Bytes {
+        fn as_bytes(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl_ct_partialeq_trait!(Bytes, as_bytes);
+
+    #[test]
+    fn test_difference_at_every_position() {
+        let a = Bytes([0u8; 64]);
+
+        for idx in 0..64 {
+            let mut other = [0u8; 64];
+            other[idx] = 1;
+            // Both an early and late difference must be caught.
+            assert!(a != Bytes(other));
+            assert!(a != &other[..]);
+        }
+        assert!(a == Bytes([0u8; 64]));
+        assert!(a == &[0u8; 64][..]);
+    }
+
+    #[test]
+    fn test_difference_in_length() {
+        let a = Bytes([0u8; 64]);
+
+        // A prefix of equal bytes is not equality.
+        assert!(a != &[0u8; 63][..]);
+        assert!(a != &[0u8; 65][..]);
+        assert!(a != &[0u8; 0][..]);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "safe_api")]
+mod test_zeroize_capacity {
+    use super::zeroize_capacity;
+
+    #[test]
+    fn test_zeroize_full_allocation() {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&[255u8; 64]);
+        // The truncated bytes remain in the spare capacity.
+        bytes.truncate(10);
+        let capacity = bytes.capacity();
+
+        zeroize_capacity(&mut bytes);
+        assert_eq!(bytes.capacity(), capacity);
+        assert_eq!(bytes.len(), capacity);
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_zeroize_empty() {
+        let mut bytes = Vec::new();
+        zeroize_capacity(&mut bytes);
+        assert!(bytes.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_size_bounds {
+    use super::{exact_size, valid_bounds, valid_gen_length};
+
+    #[test]
+    fn test_valid_bounds() {
+        assert!(valid_bounds(1, 1));
+        assert!(valid_bounds(16, 16));
+        assert!(valid_bounds(1, 64));
+        // Empty types and inverted ranges are rejected.
+        assert!(!valid_bounds(0, 16));
+        assert!(!valid_bounds(16, 15));
+    }
+
+    #[test]
+    fn test_exact_size() {
+        assert!(exact_size(16, 16, 16));
+        // A typo in either bound is rejected, e.g. a Poly1305 tag of 15 bytes.
+        assert!(!exact_size(15, 15, 16));
+        assert!(!exact_size(15, 16, 16));
+        assert!(!exact_size(16, 17, 16));
+    }
+
+    #[test]
+    fn test_valid_gen_length() {
+        assert!(valid_gen_length(1, 64, 32));
+        assert!(valid_gen_length(32, 32, 32));
+        assert!(!valid_gen_length(32, 32, 31));
+        assert!(!valid_gen_length(16, 32, 33));
+        assert!(!valid_gen_length(0, 32, 0));
+    }
+}
@@ -0,0 +1,200 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Constant-time Base64 encoding and decoding.
+//!
+//! # About:
+//! This wraps the constant-time codecs of [`ct-codecs`], so that encoding and decoding
+//! do not leak the contents of the data through timing. [`encode()`] and [`decode()`]
+//! use the standard alphabet with padding, while [`encode_no_padding()`] and
+//! [`decode_no_padding()`] use the standard alphabet without padding, as in the
+//! encoding of a [`PasswordHash`].
+//!
+//! # Errors:
+//! An error will be returned if:
+//! - The input to a decoding function contains characters outside the standard alphabet.
+//! - The input to [`decode()`] is not correctly padded.
+//! - The input to [`decode_no_padding()`] contains padding.
+//! - The length of the encoded input overflows a `usize`.
+//!
+//! # Example:
+//! ```rust
+//! use orion::util::b64;
+//!
+//! let encoded = b64::encode(b"Orion")?;
+//! assert_eq!(encoded, "T3Jpb24=");
+//! assert_eq!(b64::decode(&encoded)?.as_slice(), b"Orion");
+//! assert!(b64::decode("T3Jpb24").is_err());
+//! # Ok::<(), orion::errors::UnknownCryptoError>(())
+//! ```
+//! [`ct-codecs`]: https://github.com/jedisct1/rust-ct-codecs
+//! [`encode()`]: crate::util::b64::encode
+//! [`decode()`]: crate::util::b64::decode
+//! [`encode_no_padding()`]: crate::util::b64::encode_no_padding
+//! [`decode_no_padding()`]: crate::util::b64::decode_no_padding
+//! [`PasswordHash`]: crate::pwhash::PasswordHash
+
+use crate::errors::UnknownCryptoError;
+use ct_codecs::{Base64, Base64NoPadding, Decoder, Encoder};
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::{string::String, vec, vec::Vec};
+
+/// Encode `bin` into `E`, allocating exactly the needed length.
+fn encode_with<E: Encoder>(bin: &[u8]) -> Result<String, UnknownCryptoError> {
+    let mut encoded = vec![0u8; E::encoded_len(bin.len())?];
+    E::encode(&mut encoded, bin)?;
+
+    // The alphabet is ASCII, so this cannot fail.
+    String::from_utf8(encoded).map_err(|_| UnknownCryptoError)
+}
+
+/// Decode `encoded` from `D`. The buffer holding the output is zeroized
+/// if decoding fails, and when the returned value is dropped.
+fn decode_with<D: Decoder>(encoded: &str) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+    // Every group of four characters decodes to at most three bytes.
+    let mut bin = vec![0u8; (encoded.len() / 4 + 1) * 3];
+
+    match D::decode(&mut bin, encoded, None) {
+        Ok(decoded) => {
+            let decoded_len = decoded.len();
+            bin[decoded_len..].zeroize();
+            bin.truncate(decoded_len);
+
+            Ok(Zeroizing::new(bin))
+        }
+        Err(err) => {
+            bin.zeroize();
+            Err(err.into())
+        }
+    }
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Encode `bin` as padded Base64, using the standard alphabet.
+pub fn encode(bin: &[u8]) -> Result<String, UnknownCryptoError> {
+    encode_with::<Base64>(bin)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Decode padded Base64, using the standard alphabet. The decoded bytes may be key
+/// material, so they are zeroized when the returned value is dropped.
+pub fn decode(encoded: &str) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+    decode_with::<Base64>(encoded)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Encode `bin` as Base64 without padding, using the standard alphabet.
+pub fn encode_no_padding(bin: &[u8]) -> Result<String, UnknownCryptoError> {
+    encode_with::<Base64NoPadding>(bin)
+}
+
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+/// Decode Base64 without padding, using the standard alphabet. The decoded bytes may be
+/// key material, so they are zeroized when the returned value is dropped.
+pub fn decode_no_padding(encoded: &str) -> Result<Zeroizing<Vec<u8>>, UnknownCryptoError> {
+    decode_with::<Base64NoPadding>(encoded)
+}
+
+#[cfg(test)]
+mod public {
+    use super::*;
+
+    // RFC 4648, Section 10.
+    const RFC_VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn test_rfc_vectors() {
+        for (bin, encoded) in RFC_VECTORS.iter() {
+            assert_eq!(encode(bin.as_bytes()).unwrap(), *encoded);
+            assert_eq!(decode(encoded).unwrap().as_slice(), bin.as_bytes());
+
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode_no_padding(bin.as_bytes()).unwrap(), unpadded);
+            assert_eq!(
+                decode_no_padding(unpadded).unwrap().as_slice(),
+                bin.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_edge_lengths() {
+        let mut bin = [0u8; 130];
+        for (idx, byte) in bin.iter_mut().enumerate() {
+            *byte = (idx as u8).wrapping_mul(151);
+        }
+
+        for len in [0, 1, 2, 3, 4, 31, 32, 33, 63, 64, 65, 127, 128, 129, 130] {
+            let encoded = encode(&bin[..len]).unwrap();
+            assert_eq!(encoded.len() % 4, 0);
+            assert_eq!(decode(&encoded).unwrap().as_slice(), &bin[..len]);
+
+            let encoded = encode_no_padding(&bin[..len]).unwrap();
+            assert!(!encoded.contains('='));
+            assert_eq!(decode_no_padding(&encoded).unwrap().as_slice(), &bin[..len]);
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid_characters() {
+        for encoded in ["Zm9v!mFy", "Zm9v YmFy", "Zm9v-mFy", "Zm9v_mFy", "Zm9vYmF\n"] {
+            assert!(decode(encoded).is_err());
+            assert!(decode_no_padding(encoded).is_err());
+        }
+    }
+
+    #[test]
+    fn test_reject_wrong_padding() {
+        // Missing, superfluous and misplaced padding.
+        for encoded in ["Zg", "Zg=", "Zg===", "Zm9v=", "Z===", "Zm=9", "=Zm9"] {
+            assert!(decode(encoded).is_err());
+        }
+        // Padding is not accepted at all without padding.
+        for encoded in ["Zg==", "Zm8=", "Z"] {
+            assert!(decode_no_padding(encoded).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decoded_output_zeroizes_on_drop() {
+        fn zeroize_on_drop<T: zeroize::ZeroizeOnDrop>(_: &T) {}
+
+        let decoded = decode("Zm9vYmFy").unwrap();
+        zeroize_on_drop(&decoded);
+        let decoded = decode_no_padding("Zm9vYmFy").unwrap();
+        zeroize_on_drop(&decoded);
+
+        let mut decoded = decode("Zm9vYmFy").unwrap();
+        decoded.zeroize();
+        assert!(decoded.is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+// MIT License
+
+// Copyright (c) 2019-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! A wrapper around secret bytes, to catch secret-dependent access patterns in
+//! constant-time code under test.
+//!
+//! Every read through [`CtGuard::get()`] is recorded in a digest of the sequence of
+//! indices that were read. Code that branches on the secret bytes, such as a comparison
+//! that returns early, reads a different sequence of indices for different contents,
+//! which the digest reveals. This is a best-effort check of memory access patterns only,
+//! and cannot catch all timing leaks.
+//!
+//! This module is only compiled for tests with `debug_assertions`. Library code does
+//! not use it, and comparisons of secret values are done with `subtle`.
+
+use zeroize::Zeroize;
+
+/// FNV-1a offset basis, used to start the digest of accessed indices.
+const TRACE_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a prime, used to mix each accessed index into the digest.
+const TRACE_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The number of reads and a digest of the indices that were read, in order.
+pub(crate) struct AccessTrace {
+    reads: usize,
+    digest: u64,
+}
+
+impl AccessTrace {
+    const fn new() -> Self {
+        Self {
+            reads: 0,
+            digest: TRACE_OFFSET,
+        }
+    }
+
+    fn record(self, idx: usize) -> Self {
+        Self {
+            reads: self.reads + 1,
+            digest: (self.digest ^ idx as u64).wrapping_mul(TRACE_PRIME),
+        }
+    }
+}
+
+/// Secret bytes whose reads are traced. Wiped when dropped.
+pub(crate) struct CtGuard<const N: usize> {
+    bytes: [u8; N],
+    trace: core::cell::Cell<AccessTrace>,
+}
+
+impl<const N: usize> Drop for CtGuard<N> {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl<const N: usize> CtGuard<N> {
+    /// Guard `bytes`.
+    pub(crate) fn new(bytes: [u8; N]) -> Self {
+        Self {
+            bytes,
+            trace: core::cell::Cell::new(AccessTrace::new()),
+        }
+    }
+
+    #[inline]
+    /// Read the byte at the public position `idx`.
+    pub(crate) fn get(&self, idx: usize) -> u8 {
+        self.trace.set(self.trace.get().record(idx));
+
+        self.bytes[idx]
+    }
+
+    /// Return the reads that have been recorded so far.
+    pub(crate) fn access_trace(&self) -> AccessTrace {
+        self.trace.get()
+    }
+}
+
+/// Run `f` on a guard for each of `inputs`, and panic unless `f` read the same
+/// sequence of indices for all of them.
+pub(crate) fn assert_access_independent<const N: usize, R, F: Fn(&CtGuard<N>) -> R>(
+    inputs: &[[u8; N]],
+    f: F,
+) {
+    let mut traces = inputs.iter().map(|input| {
+        let guard = CtGuard::new(*input);
+        let _ = f(&guard);
+        guard.access_trace()
+    });
+
+    if let Some(first) = traces.next() {
+        for trace in traces {
+            assert_eq!(first, trace, "CtGuard: access pattern depends on content");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: [[u8; 16]; 4] = [[0u8; 16], [1u8; 16], [0xff; 16], {
+        let mut bytes = [0u8; 16];
+        bytes[15] = 1;
+        bytes
+    }];
+
+    /// A comparison that reads every byte, whatever the contents.
+    fn folding_eq(guard: &CtGuard<16>, other: &[u8; 16]) -> bool {
+        let mut diff = 0u8;
+        for (idx, other_byte) in other.iter().enumerate() {
+            diff |= guard.get(idx) ^ other_byte;
+        }
+
+        diff == 0
+    }
+
+    /// A comparison that returns as soon as a byte differs, which is not constant time.
+    fn branchy_eq(guard: &CtGuard<16>, other: &[u8; 16]) -> bool {
+        for (idx, other_byte) in other.iter().enumerate() {
+            if guard.get(idx) != *other_byte {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    #[test]
+    fn test_get() {
+        let guard = CtGuard::new(INPUTS[3]);
+        assert_eq!(guard.get(0), 0);
+        assert_eq!(guard.get(15), 1);
+        assert_eq!(
+            guard.access_trace(),
+            AccessTrace::new().record(0).record(15)
+        );
+    }
+
+    #[test]
+    fn test_folding_comparison_passes() {
+        for other in INPUTS.iter() {
+            assert_access_independent(&INPUTS, |guard| folding_eq(guard, other));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "CtGuard: access pattern depends on content")]
+    fn test_catches_branchy_comparison() {
+        assert_access_independent(&INPUTS, |guard| branchy_eq(guard, &[0u8; 16]));
+    }
+
+    #[test]
+    fn test_branchy_comparison_same_first_byte_undetected() {
+        // Best-effort: when all inputs differ from `other` at the same position,
+        // the early return happens at the same index and cannot be told apart.
+        assert_access_independent(&[[1u8; 16], [2u8; 16]], |guard| {
+            branchy_eq(guard, &[0u8; 16])
+        });
+    }
+}
@@ -163,6 +163,36 @@ mod public {
         load_u32_le(&[0u8; 4]);
     }
 
+    #[test]
+    fn test_byte_order_is_host_independent() {
+        // Built with shifts, so the expected values are the same on any host.
+        let bytes: [u8; 16] = core::array::from_fn(|i| (i as u8) + 1);
+        let expected_u32: [u32; 4] = core::array::from_fn(|i| {
+            (0..4).fold(0, |acc, j| acc | (u32::from(bytes[4 * i + j]) << (8 * j)))
+        });
+        let expected_u64: [u64; 2] = core::array::from_fn(|i| {
+            (0..8).fold(0, |acc, j| acc | (u64::from(bytes[8 * i + j]) << (8 * j)))
+        });
+        assert_eq!(expected_u32[0], 0x04030201);
+        assert_eq!(expected_u64[0], 0x0807060504030201);
+
+        assert_eq!(load_u32_le(&bytes[..4]), expected_u32[0]);
+
+        let mut actual_u32 = [0u32; 4];
+        load_u32_into_le(&bytes, &mut actual_u32);
+        assert_eq!(actual_u32, expected_u32);
+
+        let mut actual_u64 = [0u64; 2];
+        load_u64_into_le(&bytes, &mut actual_u64);
+        assert_eq!(actual_u64, expected_u64);
+
+        let mut stored = [0u8; 16];
+        store_u32_into_le(&expected_u32, &mut stored);
+        assert_eq!(stored, bytes);
+        store_u64_into_le(&expected_u64, &mut stored);
+        assert_eq!(stored, bytes);
+    }
+
     #[test]
     fn test_results_store_and_load_u32_into_le() {
         let input_0: [u32; 2] = [777190791, 1465409568];
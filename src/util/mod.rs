@@ -21,6 +21,10 @@
 // SOFTWARE.
 
 use crate::errors;
+#[cfg(feature = "safe_api")]
+use crate::high_level::hltypes::SecretKey;
+#[cfg(all(feature = "alloc", not(feature = "safe_api")))]
+use alloc::string::String;
 use subtle::ConstantTimeEq;
 
 /// xor_slices!(src, destination): XOR $src into $destination slice.
@@ -34,10 +38,29 @@ macro_rules! xor_slices {
     };
 }
 
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "safe_api", feature = "alloc"))))]
+pub mod b64;
+#[cfg(all(test, debug_assertions))]
+pub(crate) mod ct_guard;
 pub(crate) mod endianness;
 pub(crate) mod u32x4;
 pub(crate) mod u64x4;
 
+#[cfg(any(feature = "safe_api", feature = "alloc"))]
+/// Encode `bytes` as lowercase hex. This is not constant-time, so only use it
+/// for public values, such as tags.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        // NOTE: Both nibbles are less than 16 so .unwrap() is OK.
+        hex.push(char::from_digit(u32::from(byte >> 4), 16).unwrap());
+        hex.push(char::from_digit(u32::from(byte & 0x0f), 16).unwrap());
+    }
+
+    hex
+}
+
 #[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
 #[cfg(feature = "safe_api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
@@ -78,7 +101,68 @@ pub fn secure_rand_bytes(dst: &mut [u8]) -> Result<(), errors::UnknownCryptoErro
         return Err(errors::UnknownCryptoError);
     }
 
-    getrandom::fill(dst).unwrap();
+    fill_rand_bytes(dst).unwrap();
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "safe_api"))]
+std::thread_local! {
+    /// Make the CSPRNG fail for the current thread. Only used to test error paths.
+    static FAIL_RNG: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+#[cfg(all(test, feature = "safe_api"))]
+/// Run `f` with a CSPRNG that always fails on the current thread.
+pub(crate) fn with_failing_rng<F: FnOnce()>(f: F) {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            FAIL_RNG.with(|fail| fail.set(false));
+        }
+    }
+
+    FAIL_RNG.with(|fail| fail.set(true));
+    let _guard = Guard;
+    f();
+}
+
+/// Zeroize a borrowed buffer when dropped. Because `Drop` also runs when unwinding
+/// from a panic, the buffer is cleared on every path out of the scope holding the guard.
+pub(crate) struct ZeroizeGuard<'a>(pub(crate) &'a mut [u8]);
+
+impl Drop for ZeroizeGuard<'_> {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+
+        self.0.iter_mut().zeroize();
+    }
+}
+
+impl core::ops::Deref for ZeroizeGuard<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl core::ops::DerefMut for ZeroizeGuard<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0
+    }
+}
+
+#[cfg(feature = "safe_api")]
+/// Generate random bytes using a CSPRNG, returning an error if the CSPRNG fails.
+pub(crate) fn fill_rand_bytes(dst: &mut [u8]) -> Result<(), errors::UnknownCryptoError> {
+    #[cfg(test)]
+    if FAIL_RNG.with(|fail| fail.get()) {
+        return Err(getrandom::Error::UNEXPECTED.into());
+    }
+
+    getrandom::fill(dst)?;
 
     Ok(())
 }
@@ -120,6 +204,61 @@ pub fn secure_cmp(a: &[u8], b: &[u8]) -> Result<(), errors::UnknownCryptoError>
     }
 }
 
+#[must_use = "SECURITY WARNING: Ignoring a Result can have real security implications."]
+#[cfg(feature = "safe_api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "safe_api")))]
+/// Select one of two secret keys in constant time.
+///
+/// # About:
+/// Returns a copy of `a` if `choose_b` is false and a copy of `b` if `choose_b` is
+/// true, without branching on `choose_b`. This can be used when rotating keys,
+/// where the decision of which key to use should not be leaked.
+///
+/// # Parameters:
+/// - `a`: The key returned if `choose_b` is false.
+/// - `b`: The key returned if `choose_b` is true.
+/// - `choose_b`: Which key to select.
+///
+/// # Errors:
+/// An error will be returned if:
+/// - `a` and `b` do not have the same length.
+///
+/// # Example:
+/// ```rust
+/// use orion::{aead::SecretKey, util};
+/// use subtle::Choice;
+///
+/// let old_key = SecretKey::default();
+/// let new_key = SecretKey::default();
+///
+/// let selected = util::ct_select(&old_key, &new_key, Choice::from(1))?;
+/// assert_eq!(selected, new_key);
+/// # Ok::<(), orion::errors::UnknownCryptoError>(())
+/// ```
+pub fn ct_select(
+    a: &SecretKey,
+    b: &SecretKey,
+    choose_b: subtle::Choice,
+) -> Result<SecretKey, errors::UnknownCryptoError> {
+    use subtle::ConditionallySelectable;
+    use zeroize::Zeroize;
+
+    if a.len() != b.len() {
+        return Err(errors::UnknownCryptoError);
+    }
+
+    let mut selected: Vec<u8> = a
+        .unprotected_as_bytes()
+        .iter()
+        .zip(b.unprotected_as_bytes().iter())
+        .map(|(x, y)| u8::conditional_select(x, y, choose_b))
+        .collect();
+    let key = SecretKey::from_slice(&selected);
+    selected.zeroize();
+
+    key
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,6 +280,73 @@ mod tests {
         assert_eq!(err, errors::UnknownCryptoError);
     }
 
+    #[cfg(feature = "safe_api")]
+    #[test]
+    fn rand_err_on_rng_failure() {
+        with_failing_rng(|| {
+            let mut dst = [0u8; 64];
+            assert!(fill_rand_bytes(&mut dst).is_err());
+        });
+
+        let mut dst = [0u8; 64];
+        assert!(fill_rand_bytes(&mut dst).is_ok());
+    }
+
+    #[cfg(feature = "safe_api")]
+    #[test]
+    #[should_panic]
+    fn rand_panic_on_rng_failure() {
+        with_failing_rng(|| {
+            let mut dst = [0u8; 64];
+            let _ = secure_rand_bytes(&mut dst);
+        });
+    }
+
+    #[cfg(feature = "safe_api")]
+    #[test]
+    #[should_panic]
+    fn generate_panic_on_rng_failure() {
+        use crate::hazardous::mac::poly1305::OneTimeKey;
+
+        with_failing_rng(|| {
+            let _ = OneTimeKey::generate();
+        });
+    }
+
+    #[cfg(feature = "safe_api")]
+    #[test]
+    fn test_to_hex() {
+        let bytes: [u8; 256] = core::array::from_fn(|i| i as u8);
+        assert_eq!(to_hex(&bytes), hex::encode(bytes));
+        assert_eq!(to_hex(&[]), "");
+    }
+
+    #[cfg(feature = "safe_api")]
+    #[test]
+    fn test_ct_select() {
+        use subtle::Choice;
+
+        let a = SecretKey::from_slice(&[0u8; 32]).unwrap();
+        let b = SecretKey::from_slice(&[1u8; 32]).unwrap();
+
+        assert_eq!(ct_select(&a, &b, Choice::from(0)).unwrap(), a);
+        assert_eq!(ct_select(&a, &b, Choice::from(1)).unwrap(), b);
+        assert_eq!(ct_select(&b, &a, Choice::from(0)).unwrap(), b);
+        assert_eq!(ct_select(&b, &a, Choice::from(1)).unwrap(), a);
+    }
+
+    #[cfg(feature = "safe_api")]
+    #[test]
+    fn test_ct_select_diff_len() {
+        use subtle::Choice;
+
+        let a = SecretKey::from_slice(&[0u8; 32]).unwrap();
+        let b = SecretKey::from_slice(&[1u8; 33]).unwrap();
+
+        assert!(ct_select(&a, &b, Choice::from(0)).is_err());
+        assert!(ct_select(&a, &b, Choice::from(1)).is_err());
+    }
+
     #[test]
     fn test_ct_eq_ok() {
         let buf_1 = [0x06; 10];
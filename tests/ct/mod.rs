@@ -0,0 +1,169 @@
+// Statistical timing tests for verify(), tag equality and HMAC key setup, in the style of dudect:
+// https://eprint.iacr.org/2016/1123.pdf
+//
+// NOTE: These tests are heuristic, not a proof of constant-time behavior. Passing only
+// means that no timing difference between the two input classes could be measured with
+// the given number of samples, on this machine and with this compiler. They are noisy,
+// so they are #[ignore]d by default and should be run on an otherwise idle machine with:
+// cargo test --release --features ct-tests -- --ignored ct::
+
+use orion::hazardous::mac::{hmac, poly1305};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Number of measurements per test.
+const SAMPLES: usize = 1_000_000;
+
+/// The t-value above which dudect considers a timing leak likely.
+const T_THRESHOLD: f64 = 4.5;
+
+/// Welford's online mean and variance.
+#[derive(Default)]
+struct Stats {
+    n: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Stats {
+    fn push(&mut self, x: f64) {
+        self.n += 1.0;
+        let delta = x - self.mean;
+        self.mean += delta / self.n;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        self.m2 / (self.n - 1.0)
+    }
+}
+
+/// Welch's t-test between the two classes.
+fn welch_t(a: &Stats, b: &Stats) -> f64 {
+    (a.mean - b.mean) / ((a.variance() / a.n) + (b.variance() / b.n)).sqrt()
+}
+
+/// Measure `op` with randomly interleaved inputs of class `false` and `true`, and
+/// return the t-value. Measurements above the 90th percentile are discarded, as they
+/// are mostly caused by interrupts and other noise.
+fn measure<F: FnMut(bool)>(mut op: F) -> f64 {
+    let classes: Vec<bool> = (0..SAMPLES).map(|_| rand::random()).collect();
+    let mut timings = Vec::with_capacity(SAMPLES);
+
+    for class in classes.iter() {
+        let start = Instant::now();
+        op(black_box(*class));
+        timings.push(start.elapsed().as_nanos() as f64);
+    }
+
+    let mut sorted = timings.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoff = sorted[(SAMPLES * 9) / 10];
+
+    let (mut a, mut b) = (Stats::default(), Stats::default());
+    for (class, timing) in classes.iter().zip(timings.iter()) {
+        if *timing > cutoff {
+            continue;
+        }
+        if *class {
+            b.push(*timing);
+        } else {
+            a.push(*timing);
+        }
+    }
+
+    welch_t(&a, &b)
+}
+
+#[test]
+#[ignore]
+fn test_hmac_sha512_verify_timing() {
+    let sk = hmac::sha512::SecretKey::generate();
+    let data = [0u8; 64];
+    let tag = hmac::sha512::HmacSha512::hmac(&sk, &data).unwrap();
+    let mut bad_tag = tag.unprotected_as_bytes().to_vec();
+    // Differ in the first byte, the fastest case for an early-exit comparison.
+    bad_tag[0] ^= 1;
+    let bad_tag = hmac::sha512::Tag::from_slice(&bad_tag).unwrap();
+
+    let t = measure(|unequal| {
+        let expected = if unequal { &bad_tag } else { &tag };
+        let _ = black_box(hmac::sha512::HmacSha512::verify(expected, &sk, &data));
+    });
+
+    assert!(t.abs() < T_THRESHOLD, "Timing difference detected: t = {t}");
+}
+
+#[test]
+#[ignore]
+fn test_poly1305_verify_timing() {
+    let sk = poly1305::OneTimeKey::generate();
+    let data = [0u8; 64];
+    let tag = poly1305::Poly1305::poly1305(&sk, &data).unwrap();
+    let mut bad_tag = tag.unprotected_as_bytes().to_vec();
+    bad_tag[0] ^= 1;
+    let bad_tag = poly1305::Tag::from_slice(&bad_tag).unwrap();
+
+    let t = measure(|unequal| {
+        let expected = if unequal { &bad_tag } else { &tag };
+        let _ = black_box(poly1305::Poly1305::verify(expected, &sk, &data));
+    });
+
+    assert!(t.abs() < T_THRESHOLD, "Timing difference detected: t = {t}");
+}
+
+#[test]
+#[ignore]
+fn test_tag_eq_timing() {
+    // Compare a difference in the first byte against one in the last byte. An
+    // early-exit comparison returns sooner for the former.
+    let tag = poly1305::Tag::from([0u8; 16]);
+    let mut first = [0u8; 16];
+    first[0] = 1;
+    let first = poly1305::Tag::from(first);
+    let mut last = [0u8; 16];
+    last[15] = 1;
+    let last = poly1305::Tag::from(last);
+
+    let t = measure(|late| {
+        let other = if late { &last } else { &first };
+        let _ = black_box(black_box(&tag) == black_box(other));
+    });
+
+    assert!(t.abs() < T_THRESHOLD, "Timing difference detected: t = {t}");
+}
+
+#[test]
+#[ignore]
+fn test_tag_eq_equal_timing() {
+    // Compare equal tags against ones differing in the first byte. An early-exit
+    // comparison returns sooner for the latter.
+    let tag = hmac::sha512::Tag::from([0u8; 64]);
+    let equal = hmac::sha512::Tag::from([0u8; 64]);
+    let mut first = [0u8; 64];
+    first[0] = 1;
+    let first = hmac::sha512::Tag::from(first);
+
+    let t = measure(|unequal| {
+        let other = if unequal { &first } else { &equal };
+        let _ = black_box(black_box(&tag) == black_box(other));
+    });
+
+    assert!(t.abs() < T_THRESHOLD, "Timing difference detected: t = {t}");
+}
+
+#[test]
+#[ignore]
+fn test_hmac_sha512_new_timing() {
+    // Initializing HMAC XORs the padded key into ipad and opad. Compare an all-zero
+    // key against random keys of the same length, which should take equally long.
+    let zero_key = hmac::sha512::SecretKey::from_slice(&[0u8; 128]).unwrap();
+    let random_key = hmac::sha512::SecretKey::generate();
+
+    let t = measure(|random| {
+        let key = if random { &random_key } else { &zero_key };
+        let _ = black_box(hmac::sha512::HmacSha512::new(black_box(key)));
+    });
+
+    assert!(t.abs() < T_THRESHOLD, "Timing difference detected: t = {t}");
+}
@@ -21,7 +21,7 @@ macro_rules! impl_hmac_test_runner (($name:ident, $hmac:ident, $hmac_tag:ident,
             None => $sha2_outsize,
         };
 
-        let key = $hmac_sk::from_slice(secret_key).unwrap();
+        let key = $hmac_sk::from_slice_padded(secret_key).unwrap();
 
         // Only use verify() on OUTSIZE length tags since this is
         // the amount that Tag requires.
@@ -16,6 +16,8 @@ pub mod kdf;
 pub mod kem;
 #[cfg(test)]
 pub mod mac;
+#[cfg(test)]
+pub mod rng;
 #[cfg(feature = "safe_api")]
 #[cfg(test)]
 pub mod stream;
@@ -24,6 +26,10 @@ pub mod stream;
 #[cfg(test)]
 pub mod cae;
 
+#[cfg(all(feature = "safe_api", feature = "ct-tests"))]
+#[cfg(test)]
+pub mod ct;
+
 use hex::decode;
 
 use std::{
@@ -35,8 +41,10 @@ use std::{
 #[derive(Debug)]
 /// A test case from a given set of tests.
 pub struct TestCase {
-    /// <Field name, Field Data>, eg.: <"Mac", "d545gfdfggf42312...">
-    pub data: HashMap<String, String>,
+    /// <Field name, Field Data>, eg.: <"Mac", ["d545gfdfggf42312..."]>. A field
+    /// that is repeated in a test case, such as "AdditionalInput" in the NIST CAVP
+    /// DRBG files, holds its data in the order it was read.
+    pub data: HashMap<String, Vec<String>>,
     /// If the test is expected to pass or fail
     pub outcome: bool,
     pub test_case_number: u64,
@@ -61,17 +69,28 @@ impl core::fmt::Display for TestCase {
 impl TestCase {
     pub fn new() -> Self {
         Self {
-            data: HashMap::<String, String>::new(),
+            data: HashMap::<String, Vec<String>>::new(),
             outcome: true,
             test_case_number: 0,
         }
     }
 
     pub fn add_input_data(&mut self, data_name: &str, input_data: &str) {
-        self.data.insert(data_name.into(), input_data.into());
+        self.data
+            .entry(data_name.into())
+            .or_default()
+            .push(input_data.into());
     }
 
     pub fn get_data(&self, field: &str) -> &str {
+        match self.get_repeated_data(field) {
+            [data] => data,
+            _ => panic!("TestCase: Test case field is repeated, use get_repeated_data()."),
+        }
+    }
+
+    /// Get the data of a `field` that may be repeated, in the order it was read.
+    pub fn get_repeated_data(&self, field: &str) -> &[String] {
         self.data
             .get(field)
             .expect("TestCase: Test case field does no exists.")
@@ -0,0 +1,114 @@
+// Testing that PBKDF2 does not allocate on the heap, so that it can be used
+// without `alloc`. This is a separate test crate, because the counting allocator
+// is installed as the global allocator of the whole test binary.
+
+use hex::decode;
+use orion::hazardous::kdf::pbkdf2::sha512;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+std::thread_local! {
+    /// Heap allocations made by the current thread. Tests run in parallel on
+    /// separate threads, so only allocations of the test itself are counted.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_allocation() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Return the amount of heap allocations made while running `f`.
+fn allocations_during<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(|count| count.get());
+    f();
+    ALLOCATIONS.with(|count| count.get()) - before
+}
+
+#[test]
+fn test_counting_allocator() {
+    assert_eq!(allocations_during(|| {}), 0);
+    assert!(allocations_during(|| drop(std::hint::black_box(vec![0u8; 64]))) > 0);
+}
+
+#[test]
+fn test_pbkdf2_sha512_no_alloc() {
+    // PBKDF2-HMAC-SHA512 test vectors, also found in tests/kdf/custom_pbkdf2.rs.
+    let vectors: [(&[u8], &[u8], usize, &str); 5] = [
+        (
+            b"password",
+            b"salt",
+            1,
+            "867f70cf1ade02cff3752599a3a53dc4af34c7a6",
+        ),
+        (
+            b"password",
+            b"salt",
+            2,
+            "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e",
+        ),
+        (
+            b"password",
+            b"salt",
+            4096,
+            "d197b1b33db0143e018b12f3d1d1479e6cdebdcc",
+        ),
+        (
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+            "8c0511f4c6e597c6ac6315d8f0362e225f3c501495ba23b868",
+        ),
+        (
+            b"pass\0word",
+            b"sa\0lt",
+            4096,
+            "9d9e9c4cd21fe4be24d5b8244c759665",
+        ),
+    ];
+
+    for (password, salt, iterations, expected_hex) in vectors.iter() {
+        // Decoding allocates, so it is done before counting.
+        let expected = decode(expected_hex).unwrap();
+        let mut dk_out = [0u8; 64];
+        let dk_out = &mut dk_out[..expected.len()];
+
+        let allocations = allocations_during(|| {
+            let password = sha512::Password::from_slice(password).unwrap();
+            sha512::derive_key(&password, salt, *iterations, dk_out).unwrap();
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(&dk_out[..], &expected[..]);
+
+        let allocations = allocations_during(|| {
+            let password = sha512::Password::from_slice(password).unwrap();
+            sha512::verify(&expected, &password, salt, *iterations, dk_out).unwrap();
+        });
+        assert_eq!(allocations, 0);
+    }
+}
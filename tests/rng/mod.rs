@@ -0,0 +1,69 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub mod nist_cavp_hmac_drbg;
+pub mod openssl_hmac_drbg;
+
+use crate::{TestCase, TestCaseReader};
+use orion::hazardous::rng::hmac_drbg::HmacDrbg;
+
+/// Instantiate, reseed and generate twice, checking the output of the second
+/// generate call, as done in the NIST CAVP tests without prediction resistance.
+fn hmac_drbg_test_runner(tc: &TestCase) {
+    let entropy = TestCaseReader::default_parse(tc.get_data("EntropyInput"));
+    let nonce = TestCaseReader::default_parse(tc.get_data("Nonce"));
+    let personalization = TestCaseReader::default_parse(tc.get_data("PersonalizationString"));
+    let entropy_reseed = TestCaseReader::default_parse(tc.get_data("EntropyInputReseed"));
+    let additional_reseed = TestCaseReader::default_parse(tc.get_data("AdditionalInputReseed"));
+    let additional: Vec<Vec<u8>> = tc
+        .get_repeated_data("AdditionalInput")
+        .iter()
+        .map(|data| TestCaseReader::default_parse(data))
+        .collect();
+    assert_eq!(additional.len(), 2, "Failed: {}", tc);
+    let expected = TestCaseReader::default_parse(tc.get_data("ReturnedBits"));
+
+    let mut drbg = HmacDrbg::instantiate(&entropy, &nonce, &personalization).unwrap();
+    drbg.reseed(&entropy_reseed, &additional_reseed).unwrap();
+    let mut actual = vec![0u8; expected.len()];
+    drbg.generate(&additional[0], &mut actual).unwrap();
+    drbg.generate(&additional[1], &mut actual).unwrap();
+
+    assert_eq!(actual, expected, "Failed: {}", tc);
+}
+
+/// The fields of a NIST CAVP HMAC_DRBG test case without prediction resistance, in order.
+/// The repeated "AdditionalInput" holds the additional input of the first generate
+/// call, then that of the second.
+fn hmac_drbg_fields() -> Vec<String> {
+    vec![
+        "COUNT".into(),
+        "EntropyInput".into(),
+        "Nonce".into(),
+        "PersonalizationString".into(),
+        "EntropyInputReseed".into(),
+        "AdditionalInputReseed".into(),
+        "AdditionalInput".into(),
+        "AdditionalInput".into(),
+        "ReturnedBits".into(),
+    ]
+}
@@ -0,0 +1,72 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::{hmac_drbg_fields, hmac_drbg_test_runner};
+use crate::TestCaseReader;
+
+#[test]
+#[ignore = "needs the NIST CAVP HMAC_DRBG.rsp without prediction resistance in tests/test_data/third_party/nist/"]
+/// Run the `[SHA-512]` sections of `HMAC_DRBG.rsp` from the NIST CAVP DRBG test
+/// vectors without prediction resistance (`drbgvectors_pr_false`).
+fn test_nist_cavp_hmac_drbg_sha512() {
+    let mut nist_cavp_reader = TestCaseReader::new(
+        "./tests/test_data/third_party/nist/HMAC_DRBG.rsp",
+        hmac_drbg_fields(),
+        "=",
+    );
+    // Set variant separators. Each hash function has several sections, with different
+    // input lengths, that all start with the name of the hash function.
+    nist_cavp_reader.set_stop_flags(vec![
+        "[SHA-1]".into(),
+        "[SHA-224]".into(),
+        "[SHA-256]".into(),
+        "[SHA-384]".into(),
+        "[SHA-512]".into(),
+        "[SHA-512/224]".into(),
+        "[SHA-512/256]".into(),
+    ]);
+    // The current hash function being tested.
+    let mut current_variant = String::new();
+    let mut count = 0;
+
+    loop {
+        match nist_cavp_reader.next() {
+            Some(ref tc) => {
+                // Orion only supports HMAC_DRBG with SHA-512.
+                if current_variant == "[SHA-512]" {
+                    hmac_drbg_test_runner(tc);
+                    count += 1;
+                }
+            }
+            None => {
+                if nist_cavp_reader.did_hit_flag() {
+                    current_variant = nist_cavp_reader.last_stop_flag();
+                    continue;
+                }
+
+                break;
+            }
+        }
+    }
+
+    assert_ne!(count, 0);
+}
@@ -0,0 +1,42 @@
+// MIT License
+
+// Copyright (c) 2018-2025 The orion Developers
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::{hmac_drbg_fields, hmac_drbg_test_runner};
+use crate::TestCaseReader;
+
+#[test]
+/// The test values are generated with OpenSSL by `generate_hmac_drbg_test_values.c`.
+/// They are not NIST CAVP test vectors, which are tested in `nist_cavp_hmac_drbg`.
+fn test_openssl_hmac_drbg_sha512() {
+    let reader = TestCaseReader::new(
+        "./tests/test_data/openssl/hmac_drbg_sha512.rsp",
+        hmac_drbg_fields(),
+        "=",
+    );
+
+    let mut count = 0;
+    for test_case in reader {
+        hmac_drbg_test_runner(&test_case);
+        count += 1;
+    }
+    assert_eq!(count, 60);
+}
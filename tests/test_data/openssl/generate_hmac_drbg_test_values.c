@@ -0,0 +1,131 @@
+/*
+ * Generate HMAC-DRBG (NIST SP 800-90A) test values for SHA-512 with OpenSSL,
+ * in the format of the NIST CAVP HMAC_DRBG.rsp file, without prediction resistance.
+ *
+ * Each test case instantiates the DRBG, reseeds it once, generates ReturnedBitsLen
+ * bits twice and records the output of the second generate call. The entropy input
+ * for both instantiate and reseed is given through the TEST-RAND parent, because
+ * entropy passed directly to EVP_RAND_reseed() is not used as the only entropy input.
+ *
+ * The inputs are derived deterministically with SHA-512 from the field name and
+ * test case number, so the file can be regenerated.
+ *
+ * Compile and run:
+ *   gcc generate_hmac_drbg_test_values.c -lcrypto -o gen && ./gen > hmac_drbg_sha512.rsp
+ */
+#include <openssl/core_names.h>
+#include <openssl/evp.h>
+#include <openssl/params.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define ENTROPY_LEN 32
+#define NONCE_LEN 16
+#define RETURNED_LEN 256
+#define COUNT 15
+
+static void derive(const char *field, int group, int count, unsigned char *out, size_t len) {
+    unsigned char digest[64];
+    char label[64];
+    snprintf(label, sizeof(label), "orion hmac-drbg %d %d %s", group, count, field);
+    EVP_Digest(label, strlen(label), digest, NULL, EVP_sha512(), NULL);
+    memcpy(out, digest, len);
+}
+
+static void print_hex(const char *name, const unsigned char *data, size_t len) {
+    printf("%s = ", name);
+    for (size_t i = 0; i < len; i++) {
+        printf("%02x", data[i]);
+    }
+    printf("\n");
+}
+
+int main(void) {
+    unsigned int strength = 256;
+    char digest_name[] = "SHA512";
+    printf("# HMAC_DRBG test values generated with OpenSSL %s\n", OpenSSL_version(OPENSSL_VERSION_STRING));
+    printf("# by generate_hmac_drbg_test_values.c, in the format of the NIST CAVP HMAC_DRBG.rsp file.\n");
+    printf("# These are not NIST CAVP test vectors, only a cross-check against OpenSSL.\n\n");
+
+    for (int group = 0; group < 4; group++) {
+        size_t pers_len = (group & 1) ? 32 : 0;
+        size_t add_len = (group & 2) ? 32 : 0;
+
+        printf("[SHA-512]\n[PredictionResistance = False]\n[EntropyInputLen = %d]\n[NonceLen = %d]\n",
+               ENTROPY_LEN * 8, NONCE_LEN * 8);
+        printf("[PersonalizationStringLen = %zu]\n[AdditionalInputLen = %zu]\n[ReturnedBitsLen = %d]\n\n",
+               pers_len * 8, add_len * 8, RETURNED_LEN * 8);
+
+        for (int count = 0; count < COUNT; count++) {
+            unsigned char entropy[ENTROPY_LEN], nonce[NONCE_LEN], pers[32];
+            unsigned char entropy_reseed[ENTROPY_LEN], add_reseed[32], add1[32], add2[32];
+            unsigned char out[RETURNED_LEN];
+
+            derive("EntropyInput", group, count, entropy, ENTROPY_LEN);
+            derive("Nonce", group, count, nonce, NONCE_LEN);
+            derive("PersonalizationString", group, count, pers, pers_len);
+            derive("EntropyInputReseed", group, count, entropy_reseed, ENTROPY_LEN);
+            derive("AdditionalInputReseed", group, count, add_reseed, add_len);
+            derive("AdditionalInput1", group, count, add1, add_len);
+            derive("AdditionalInput2", group, count, add2, add_len);
+
+            EVP_RAND *test_rand = EVP_RAND_fetch(NULL, "TEST-RAND", NULL);
+            EVP_RAND_CTX *parent = EVP_RAND_CTX_new(test_rand, NULL);
+            OSSL_PARAM parent_params[] = {
+                OSSL_PARAM_construct_uint(OSSL_RAND_PARAM_STRENGTH, &strength),
+                OSSL_PARAM_construct_octet_string(OSSL_RAND_PARAM_TEST_ENTROPY, entropy, ENTROPY_LEN),
+                OSSL_PARAM_construct_octet_string(OSSL_RAND_PARAM_TEST_NONCE, nonce, NONCE_LEN),
+                OSSL_PARAM_construct_end(),
+            };
+            if (!EVP_RAND_instantiate(parent, strength, 0, NULL, 0, parent_params)) {
+                return 1;
+            }
+
+            EVP_RAND *hmac_drbg = EVP_RAND_fetch(NULL, "HMAC-DRBG", NULL);
+            EVP_RAND_CTX *drbg = EVP_RAND_CTX_new(hmac_drbg, parent);
+            OSSL_PARAM drbg_params[] = {
+                OSSL_PARAM_construct_utf8_string(OSSL_DRBG_PARAM_MAC, "HMAC", 0),
+                OSSL_PARAM_construct_utf8_string(OSSL_DRBG_PARAM_DIGEST, digest_name, 0),
+                OSSL_PARAM_construct_end(),
+            };
+            if (!EVP_RAND_instantiate(drbg, strength, 0, pers, pers_len, drbg_params)) {
+                return 1;
+            }
+            OSSL_PARAM reseed_params[] = {
+                OSSL_PARAM_construct_octet_string(OSSL_RAND_PARAM_TEST_ENTROPY, entropy_reseed, ENTROPY_LEN),
+                OSSL_PARAM_construct_end(),
+            };
+            if (!EVP_RAND_CTX_set_params(parent, reseed_params)) {
+                return 1;
+            }
+            if (!EVP_RAND_reseed(drbg, 0, NULL, 0, add_reseed, add_len)) {
+                return 1;
+            }
+            if (!EVP_RAND_generate(drbg, out, RETURNED_LEN, strength, 0, add1, add_len)) {
+                return 1;
+            }
+            if (!EVP_RAND_generate(drbg, out, RETURNED_LEN, strength, 0, add2, add_len)) {
+                return 1;
+            }
+
+            printf("COUNT = %d\n", count);
+            print_hex("EntropyInput", entropy, ENTROPY_LEN);
+            print_hex("Nonce", nonce, NONCE_LEN);
+            print_hex("PersonalizationString", pers, pers_len);
+            print_hex("EntropyInputReseed", entropy_reseed, ENTROPY_LEN);
+            print_hex("AdditionalInputReseed", add_reseed, add_len);
+            print_hex("AdditionalInput", add1, add_len);
+            print_hex("AdditionalInput", add2, add_len);
+            print_hex("ReturnedBits", out, RETURNED_LEN);
+            printf("\n");
+
+            EVP_RAND_CTX_free(drbg);
+            EVP_RAND_free(hmac_drbg);
+            EVP_RAND_CTX_free(parent);
+            EVP_RAND_free(test_rand);
+        }
+    }
+
+    return 0;
+}